@@ -0,0 +1,246 @@
+//! Participants holding several codeword columns each.
+//!
+//! The scheme hands out one share per codeword column, so `n` is in the thousands.
+//! An [`AccessLayout`] groups the columns into a few named participants: deal
+//! emits one [`ParticipantBundle`] per participant, and a participant who is
+//! missing at reconstruction erases all of their columns at once.
+
+use crate::error::SchemeError;
+use crate::integrity::ShareCommitment;
+use crate::types::{xor_by_column, DealMetrics, F2PowElement, Share, Shares};
+
+/// A named holder of a set of codeword columns.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Participant {
+    pub name: String,
+    pub columns: Vec<u32>,
+}
+
+/// Disjoint column sets of the participants. Columns assigned to nobody are
+/// dropped after the deal and count as erasures.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccessLayout {
+    participants: Vec<Participant>,
+    output_length: u32,
+}
+
+impl AccessLayout {
+    /// Layout over a codeword of `output_length` columns; a column may belong to
+    /// one participant at most.
+    pub fn new(participants: Vec<Participant>, output_length: u32) -> Result<Self, SchemeError> {
+        let mut owned = vec![false; output_length as usize];
+        for &column in participants.iter().flat_map(|p| &p.columns) {
+            if column >= output_length {
+                return Err(SchemeError::ShareIndexOutOfRange {
+                    index: column,
+                    output_length,
+                });
+            }
+            if std::mem::replace(&mut owned[column as usize], true) {
+                return Err(SchemeError::DuplicateShare { index: column });
+            }
+        }
+        Ok(Self {
+            participants,
+            output_length,
+        })
+    }
+
+    /// Every column to participant `column % names.len()`, so each participant
+    /// holds an evenly spread slice of both the systematic and the parity part.
+    pub fn interleaved<S: AsRef<str>>(names: &[S], output_length: u32) -> Self {
+        let participants = names
+            .iter()
+            .enumerate()
+            .map(|(index, name)| Participant {
+                name: name.as_ref().to_string(),
+                columns: (index as u32..output_length).step_by(names.len()).collect(),
+            })
+            .collect();
+        Self {
+            participants,
+            output_length,
+        }
+    }
+
+    /// [`Self::interleaved`] over participants named `P1`..`P<count>`.
+    pub fn numbered(count: usize, output_length: u32) -> Self {
+        let names: Vec<String> = (1..=count).map(|index| format!("P{}", index)).collect();
+        Self::interleaved(&names, output_length)
+    }
+
+    pub fn participants(&self) -> &[Participant] {
+        &self.participants
+    }
+
+    pub fn output_length(&self) -> u32 {
+        self.output_length
+    }
+
+    /// Split the shares of one deal into one bundle per participant.
+    pub fn bundle(&self, shares: Shares) -> BundledShares {
+        let mut by_column: Vec<Option<Share>> = vec![None; self.output_length as usize];
+        for share in shares.shares {
+            if let Some(slot) = by_column.get_mut(share.i as usize) {
+                *slot = Some(share);
+            }
+        }
+        let bundles = self
+            .participants
+            .iter()
+            .map(|participant| ParticipantBundle {
+                participant: participant.name.clone(),
+                shares: participant
+                    .columns
+                    .iter()
+                    .filter_map(|&column| by_column[column as usize].take())
+                    .collect(),
+            })
+            .collect();
+        BundledShares {
+            bundles,
+            z0: shares.z0,
+            metrics: shares.metrics,
+            commitment: shares.commitment,
+        }
+    }
+}
+
+/// Everything one participant receives from the dealer.
+#[derive(Clone)]
+pub struct ParticipantBundle {
+    pub participant: String,
+    pub shares: Vec<Share>,
+}
+
+impl ParticipantBundle {
+    /// Apply this participant's bundle of a refresh update (see
+    /// [`crate::aos_core::refresh`]): every share is XORed with the update column
+    /// of the same index.
+    pub fn apply_update(&mut self, update: &ParticipantBundle) -> Result<(), SchemeError> {
+        self.shares = xor_by_column(&self.shares, &update.shares)?;
+        Ok(())
+    }
+}
+
+/// Output of a deal under an [`AccessLayout`].
+#[derive(Clone)]
+pub struct BundledShares {
+    pub bundles: Vec<ParticipantBundle>,
+    pub z0: F2PowElement,
+    pub metrics: Option<DealMetrics>,
+    pub commitment: Option<ShareCommitment>,
+}
+
+impl BundledShares {
+    /// The columns of every bundle as plain shares.
+    pub fn to_shares(&self) -> Shares {
+        Shares {
+            shares: self
+                .bundles
+                .iter()
+                .flat_map(|bundle| bundle.shares.iter().cloned())
+                .collect(),
+            z0: self.z0.clone(),
+            metrics: None,
+            commitment: self.commitment.clone(),
+        }
+    }
+
+    /// Names of the participants that contribute at least one share.
+    pub fn participant_names(&self) -> Vec<String> {
+        self.bundles
+            .iter()
+            .filter(|bundle| !bundle.shares.is_empty())
+            .map(|bundle| bundle.participant.clone())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shares(count: u32) -> Shares {
+        Shares {
+            shares: (0..count)
+                .map(|i| Share {
+                    y: F2PowElement::zero(4),
+                    i,
+                    proof: None,
+                })
+                .collect(),
+            z0: F2PowElement::zero(4),
+            metrics: None,
+            commitment: None,
+        }
+    }
+
+    #[test]
+    fn test_interleaved_layout_covers_every_column_once() {
+        let layout = AccessLayout::numbered(3, 10);
+        let columns: Vec<Vec<u32>> = layout
+            .participants()
+            .iter()
+            .map(|p| p.columns.clone())
+            .collect();
+        assert_eq!(
+            columns,
+            vec![vec![0, 3, 6, 9], vec![1, 4, 7], vec![2, 5, 8]]
+        );
+        assert_eq!(layout.participants()[2].name, "P3");
+        assert_eq!(
+            AccessLayout::new(layout.participants().to_vec(), 10),
+            Ok(layout)
+        );
+    }
+
+    #[test]
+    fn test_layout_rejects_overlap_and_out_of_range() {
+        let participant = |name: &str, columns: Vec<u32>| Participant {
+            name: name.to_string(),
+            columns,
+        };
+        assert_eq!(
+            AccessLayout::new(
+                vec![participant("a", vec![0, 1]), participant("b", vec![1])],
+                4
+            ),
+            Err(SchemeError::DuplicateShare { index: 1 })
+        );
+        assert_eq!(
+            AccessLayout::new(vec![participant("a", vec![4])], 4),
+            Err(SchemeError::ShareIndexOutOfRange {
+                index: 4,
+                output_length: 4
+            })
+        );
+    }
+
+    #[test]
+    fn test_bundle_drops_unassigned_columns() {
+        let layout = AccessLayout::new(
+            vec![
+                Participant {
+                    name: "alice".to_string(),
+                    columns: vec![4, 0],
+                },
+                Participant {
+                    name: "bob".to_string(),
+                    columns: vec![2],
+                },
+            ],
+            5,
+        )
+        .unwrap();
+        let bundled = layout.bundle(shares(5));
+        let indices: Vec<Vec<u32>> = bundled
+            .bundles
+            .iter()
+            .map(|bundle| bundle.shares.iter().map(|share| share.i).collect())
+            .collect();
+        assert_eq!(indices, vec![vec![4, 0], vec![2]]);
+        assert_eq!(bundled.to_shares().shares.len(), 3);
+        assert_eq!(bundled.participant_names(), vec!["alice", "bob"]);
+    }
+}
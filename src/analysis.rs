@@ -0,0 +1,272 @@
+//! Predictions about sets of shares, made from the code and the mask `a` alone.
+//!
+//! Every row of a deal sees the same erasure pattern, so whether a set of share
+//! indices reconstructs depends only on which columns of `H` are erased, not on
+//! the share values. This answers the question before any share is fetched.
+//!
+//! Privacy is a question about the same columns of the generator: a coalition
+//! holding columns `T` sees `r G_T` in every row, and learns `<a, r>`, and with the
+//! public `z0` the secret, exactly when `a` lies in the span of the columns of
+//! `G_T`. Otherwise `<a, r>` is uniform given its view in every row, and it learns
+//! nothing, not even a linear combination of secret bits.
+
+use rand::seq::index;
+use rand::Rng;
+use serde::Serialize;
+
+use crate::bit_matrix::{xor_into, BitMatrix};
+use crate::code::erasure::{peel_erasures, solve_erasures};
+use crate::code::ldpc_impl::LdpcCode;
+use crate::code::AdditiveCode;
+use crate::error::SchemeError;
+use crate::types::SecretParams;
+
+/// How a set of shares can be decoded, from best to worst.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum Decodability {
+    /// Belief propagation recovers every erased column within the decoder's
+    /// iteration limit, so `reconstruct` succeeds.
+    BeliefPropagation,
+    /// Belief propagation gets stuck, but the erased columns of `H` have enough
+    /// rank to determine the message, so `reconstruct_erasures` succeeds.
+    MaximumLikelihood,
+    /// The available columns do not determine the message.
+    Insufficient,
+}
+
+/// Prediction for one set of share indices.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SubsetAnalysis {
+    pub decodability: Decodability,
+    pub present: usize,
+    pub erased: usize,
+    /// Flooding rounds of peeling, comparable to belief-propagation iterations.
+    pub peeling_rounds: usize,
+    /// Erased columns peeling leaves unresolved (a stopping set of `H`).
+    pub stopping_set_size: usize,
+    /// Erased message columns the available columns do not determine; `None` when
+    /// it was not computed because fewer than `k` columns are present.
+    pub undetermined_message_columns: Option<usize>,
+}
+
+/// Presence mask over the codeword for distinct, in-range `indices`.
+fn present_mask<C: AdditiveCode>(
+    pp: &SecretParams<C>,
+    indices: &[u32],
+) -> Result<Vec<bool>, SchemeError> {
+    let output_length = pp.code.output_length;
+    let mut present = vec![false; output_length as usize];
+    for &index in indices {
+        if index >= output_length {
+            return Err(SchemeError::ShareIndexOutOfRange {
+                index,
+                output_length,
+            });
+        }
+        if std::mem::replace(&mut present[index as usize], true) {
+            return Err(SchemeError::DuplicateShare { index });
+        }
+    }
+    Ok(present)
+}
+
+/// Predict whether the shares with `indices` reconstruct, without decoding.
+///
+/// Peeling the erasure pattern on `H` stands in for belief propagation; when it
+/// gets stuck, Gaussian elimination on the erased columns decides whether the
+/// message is still determined. Fewer than `k` present columns are insufficient
+/// without further work.
+///
+/// Fails with [`SchemeError::InvalidCodeParameters`] for codes without a sparse
+/// parity-check matrix.
+pub fn analyze_subset<C: AdditiveCode>(
+    pp: &SecretParams<C>,
+    indices: &[u32],
+) -> Result<SubsetAnalysis, SchemeError> {
+    let h = pp.code.code_impl.sparse_parity_check().ok_or_else(|| {
+        SchemeError::InvalidCodeParameters(String::from(
+            "subset analysis needs a sparse parity-check matrix",
+        ))
+    })?;
+    let input_length = pp.code.input_length as usize;
+    let present = present_mask(pp, indices)?;
+
+    let peeling = peel_erasures(h, &present);
+    let mut analysis = SubsetAnalysis {
+        decodability: Decodability::Insufficient,
+        present: indices.len(),
+        erased: present.len() - indices.len(),
+        peeling_rounds: peeling.rounds,
+        stopping_set_size: peeling.unresolved.len(),
+        undetermined_message_columns: None,
+    };
+
+    if peeling.unresolved.is_empty() && peeling.rounds <= pp.code.code_impl.max_iterations() {
+        analysis.decodability = Decodability::BeliefPropagation;
+        analysis.undetermined_message_columns = Some(0);
+    } else if indices.len() >= input_length {
+        let solution = solve_erasures(h, &present, &BitMatrix::zeros(present.len(), 1));
+        let undetermined = solution
+            .erased
+            .iter()
+            .zip(&solution.recovered)
+            .filter(|(&col, recovered)| col < input_length && recovered.is_none())
+            .count();
+        analysis.undetermined_message_columns = Some(undetermined);
+        if undetermined == 0 {
+            analysis.decodability = Decodability::MaximumLikelihood;
+        }
+    }
+    Ok(analysis)
+}
+
+/// What a coalition learns about the secret.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PrivacyAnalysis {
+    pub coalition_size: usize,
+    /// Rank of the generator columns the coalition holds, at most `k`.
+    pub rank: usize,
+    /// Whether `a` lies in their span. If so the coalition computes the whole
+    /// secret; if not it learns nothing about it.
+    pub leaks: bool,
+}
+
+/// Linear span over GF(2) of vectors packed into `u64` words.
+struct Span {
+    /// Reduced vectors with the position of their lowest set bit; each is zero at
+    /// the pivots of the vectors before it.
+    basis: Vec<(usize, Vec<u64>)>,
+}
+
+impl Span {
+    fn reduce(&self, vector: &mut [u64]) {
+        for (pivot, basis_vector) in &self.basis {
+            if (vector[pivot / 64] >> (pivot % 64)) & 1 == 1 {
+                xor_into(vector, basis_vector);
+            }
+        }
+    }
+
+    fn insert(&mut self, mut vector: Vec<u64>) {
+        self.reduce(&mut vector);
+        if let Some(word) = vector.iter().position(|&word| word != 0) {
+            let pivot = word * 64 + vector[word].trailing_zeros() as usize;
+            self.basis.push((pivot, vector));
+        }
+    }
+}
+
+/// Check whether the coalition holding the shares with `indices` learns anything
+/// about the secret.
+///
+/// Systematic columns are unit vectors, so they are taken out of `a` and of the
+/// parity columns first, and only the parity columns are eliminated.
+pub fn analyze_privacy(
+    pp: &SecretParams<LdpcCode>,
+    indices: &[u32],
+) -> Result<PrivacyAnalysis, SchemeError> {
+    let present = present_mask(pp, indices)?;
+    let input_length = pp.code.input_length as usize;
+    let generator = pp.code.code_impl.generator();
+
+    let mut free = BitMatrix::zeros(1, input_length);
+    for (col, &known) in present[..input_length].iter().enumerate() {
+        free.set(0, col, !known);
+    }
+    let restrict = |words: &[u64]| -> Vec<u64> {
+        words
+            .iter()
+            .zip(free.row(0))
+            .map(|(word, mask)| word & mask)
+            .collect()
+    };
+
+    let mut span = Span { basis: Vec::new() };
+    for col in (input_length..present.len()).filter(|&col| present[col]) {
+        span.insert(restrict(generator.row(col - input_length)));
+    }
+    let mut mask = BitMatrix::zeros(1, input_length);
+    for (col, &bit) in pp.a_bits.iter().enumerate() {
+        mask.set(0, col, bit);
+    }
+    let mut a = restrict(mask.row(0));
+    span.reduce(&mut a);
+
+    let systematic = present[..input_length].iter().filter(|&&p| p).count();
+    Ok(PrivacyAnalysis {
+        coalition_size: indices.len(),
+        rank: systematic + span.basis.len(),
+        leaks: a.iter().all(|&word| word == 0),
+    })
+}
+
+/// Fraction of `samples` uniformly random coalitions of `coalition_size` shares
+/// that learn the secret. Sizes above `n` are treated as `n`.
+pub fn sampled_leak_rate<R: Rng + ?Sized>(
+    pp: &SecretParams<LdpcCode>,
+    coalition_size: usize,
+    samples: usize,
+    rng: &mut R,
+) -> f64 {
+    let output_length = pp.code.output_length as usize;
+    let coalition_size = coalition_size.min(output_length);
+    let leaks = (0..samples)
+        .filter(|_| {
+            let indices: Vec<u32> = index::sample(rng, output_length, coalition_size)
+                .into_iter()
+                .map(|col| col as u32)
+                .collect();
+            analyze_privacy(pp, &indices)
+                .expect("sampled indices are distinct and in range")
+                .leaks
+        })
+        .count();
+    leaks as f64 / samples.max(1) as f64
+}
+
+/// Sampled privacy threshold of one parameter set.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PrivacyThreshold {
+    /// Largest coalition size at which none of the sampled coalitions leaked.
+    pub leak_free_size: usize,
+    /// Smallest size at which a sampled coalition leaked.
+    pub first_leak_size: usize,
+    /// Weight of `a`. The systematic columns where `a` is set always leak, so no
+    /// threshold against chosen coalitions can exceed `mask_weight - 1`.
+    pub mask_weight: usize,
+    pub samples: usize,
+}
+
+/// Estimate the privacy threshold against random coalitions by bisecting the
+/// coalition size, drawing `samples` coalitions at each size.
+///
+/// The leak probability grows with the size (a superset of a leaking coalition
+/// leaks), so the search settles where sampled coalitions start to leak. This is a
+/// statement about random coalitions: `mask_weight` bounds what a coalition that
+/// chooses its columns needs.
+pub fn estimate_privacy_threshold<R: Rng + ?Sized>(
+    pp: &SecretParams<LdpcCode>,
+    samples: usize,
+    rng: &mut R,
+) -> PrivacyThreshold {
+    let mask_weight = pp.a_bits.iter().filter(|&&bit| bit).count();
+    let (mut leak_free, mut leaking) = (0, pp.code.output_length as usize);
+    if mask_weight == 0 {
+        // `z0` alone is the secret.
+        leaking = 0;
+    }
+    while leaking > leak_free + 1 {
+        let size = leak_free + (leaking - leak_free) / 2;
+        if sampled_leak_rate(pp, size, samples, rng) > 0.0 {
+            leaking = size;
+        } else {
+            leak_free = size;
+        }
+    }
+    PrivacyThreshold {
+        leak_free_size: leak_free.min(leaking),
+        first_leak_size: leaking,
+        mask_weight,
+        samples,
+    }
+}
@@ -1,138 +1,318 @@
 //! Sequential implementation of secret sharing operations.
 
-use ldpc_toolbox::gf2::GF2;
-use ndarray::Array2;
-use num_traits::{One, Zero};
+use rand::Rng;
 
-use crate::aos_core::{self, codeword_to_gf2_buf, ExecutionStrategy};
-use crate::code::ldpc_impl::LdpcCode;
+use crate::access::{AccessLayout, BundledShares};
+use crate::aos_core::joint::JointSimulation;
+use crate::aos_core::{self, column_rng, row_report, DealSeed, ExecutionStrategy};
+use crate::bit_matrix::{codeword_to_words, gf2_to_words, BitMatrix};
 use crate::code::AdditiveCode;
+use crate::error::SchemeError;
+use crate::integrity::ShareCommitment;
 use crate::types::{
-    CodeInitParams, DecodingStats, F2PowElement, ReconstructMetrics, SecretParams, Shares,
+    BlockShares, BlockZ0, CodeInitParams, F2PowElement, ReconstructMetrics, ReconstructOutcome,
+    RowReport, SecretParams, Share, Shares,
 };
 
 pub struct SequentialStrategy;
 
-impl ExecutionStrategy for SequentialStrategy {
-    fn generate_random_columns(len: usize, bit_len: usize) -> Vec<F2PowElement> {
-        let mut rng = rand::rng();
+impl<C: AdditiveCode> ExecutionStrategy<C> for SequentialStrategy {
+    fn generate_random_columns(len: usize, bit_len: usize, seed: &DealSeed) -> Vec<F2PowElement> {
         (0..len)
-            .map(|_| F2PowElement::random(bit_len, &mut rng))
+            .map(|idx| F2PowElement::random(bit_len, &mut column_rng(seed, idx)))
             .collect()
     }
 
-    fn create_message_matrix(columns: &[F2PowElement], nrows: usize, ncols: usize) -> Array2<GF2> {
-        let mut message_matrix = Array2::<GF2>::from_elem((nrows, ncols), GF2::zero());
-        let gf2_zero = GF2::zero();
-        let gf2_one = GF2::one();
-
-        for (col_idx, column) in columns.iter().enumerate().take(ncols) {
-            for row_idx in 0..nrows {
-                message_matrix[(row_idx, col_idx)] = if column.bit(row_idx) {
-                    gf2_one
-                } else {
-                    gf2_zero
-                };
-            }
-        }
-
-        message_matrix
+    fn create_message_matrix(columns: &[F2PowElement], nrows: usize, ncols: usize) -> BitMatrix {
+        BitMatrix::from_elements(&columns[..ncols], nrows).transpose()
     }
 
     fn encode_rows(
-        message_matrix: &Array2<GF2>,
-        code_impl: &LdpcCode,
+        message_matrix: &BitMatrix,
+        code_impl: &C,
         nrows: usize,
         output_cols: usize,
-    ) -> Array2<GF2> {
-        let mut encoded_matrix = Array2::<GF2>::from_elem((nrows, output_cols), GF2::zero());
+    ) -> BitMatrix {
+        let mut encoded_matrix = BitMatrix::zeros(nrows, output_cols);
 
         for i in 0..nrows {
-            let encoded = code_impl.encode(&message_matrix.row(i).to_owned());
-            encoded_matrix.row_mut(i).assign(&encoded);
+            let encoded = code_impl.encode(&message_matrix.row_to_gf2(i));
+            gf2_to_words(&encoded, encoded_matrix.row_mut(i));
         }
 
         encoded_matrix
     }
 
     fn decode_rows(
-        encoded_matrix: &Array2<GF2>,
-        code_impl: &LdpcCode,
+        encoded_matrix: &BitMatrix,
+        code_impl: &C,
         present_columns: &[bool],
         input_length: usize,
         nrows: usize,
-    ) -> (Array2<GF2>, DecodingStats) {
-        let mut decoded_matrix = Array2::<GF2>::from_elem((nrows, input_length), GF2::zero());
-        let mut successful_rows = 0;
-        let mut failed_rows = 0;
-        let mut total_iterations = 0;
-        let mut max_iterations_hit = 0;
-        let max_iter_limit = code_impl.max_iterations();
+    ) -> (BitMatrix, Vec<RowReport>) {
+        let mut decoded_matrix = BitMatrix::zeros(nrows, input_length);
+        let mut rows = Vec::with_capacity(nrows);
 
         for i in 0..nrows {
-            let row_input = encoded_matrix.row(i).to_owned();
+            let row_input = encoded_matrix.row_to_gf2(i);
             let decode_result = code_impl.decode(&row_input, present_columns);
 
-            total_iterations += decode_result.iterations;
-            if decode_result.iterations >= max_iter_limit {
-                max_iterations_hit += 1;
-            }
-
             if decode_result.success {
-                successful_rows += 1;
-                let mut row_mut = decoded_matrix.row_mut(i);
-                codeword_to_gf2_buf(
+                codeword_to_words(
                     &decode_result.codeword,
-                    row_mut.as_slice_mut().unwrap(),
+                    decoded_matrix.row_mut(i),
                     input_length,
                 );
-            } else {
-                failed_rows += 1;
             }
+            rows.push(row_report(code_impl, &decode_result));
         }
 
-        (
-            decoded_matrix,
-            DecodingStats::new(
-                nrows,
-                successful_rows,
-                failed_rows,
-                total_iterations,
-                max_iterations_hit,
-            ),
-        )
+        (decoded_matrix, rows)
     }
 
-    fn reconstruct_columns(
-        decoded_matrix: &Array2<GF2>,
-        input_length: usize,
-        bit_len: usize,
-    ) -> Vec<F2PowElement> {
+    fn reconstruct_columns(decoded_matrix: &BitMatrix, input_length: usize) -> Vec<F2PowElement> {
+        let columns = decoded_matrix.transpose();
         (0..input_length)
-            .map(|col_idx| {
-                let mut column = F2PowElement::zero(bit_len);
-                for row_idx in 0..bit_len {
-                    if decoded_matrix[(row_idx, col_idx)].is_one() {
-                        column.set_bit(row_idx, true);
-                    }
-                }
-                column
-            })
+            .map(|col_idx| columns.row_element(col_idx))
             .collect()
     }
 }
 
-pub fn setup(params: CodeInitParams) -> SecretParams<LdpcCode> {
+pub fn setup<C: AdditiveCode>(params: CodeInitParams) -> Result<SecretParams<C>, SchemeError> {
     aos_core::setup(params)
 }
 
-pub fn deal(pp: &SecretParams<LdpcCode>, secret: &F2PowElement) -> Shares {
-    aos_core::deal_with_strategy::<SequentialStrategy>(pp, secret)
+pub fn setup_with_rng<C, R>(
+    params: CodeInitParams,
+    rng: &mut R,
+) -> Result<SecretParams<C>, SchemeError>
+where
+    C: AdditiveCode,
+    R: Rng + ?Sized,
+{
+    aos_core::setup_with_rng(params, rng)
+}
+
+pub fn setup_with_seed<C: AdditiveCode>(
+    params: CodeInitParams,
+    seed: u64,
+) -> Result<SecretParams<C>, SchemeError> {
+    setup_with_rng(params, &mut aos_core::seeded_rng(seed))
+}
+
+pub fn deal<C: AdditiveCode>(
+    pp: &SecretParams<C>,
+    secret: &F2PowElement,
+) -> Result<Shares, SchemeError> {
+    aos_core::deal_with_strategy::<SequentialStrategy, C>(pp, secret)
+}
+
+/// Deal and commit to the shares; see [`aos_core::deal_committed_with_strategy_and_rng`].
+pub fn deal_committed<C: AdditiveCode>(
+    pp: &SecretParams<C>,
+    secret: &F2PowElement,
+) -> Result<Shares, SchemeError> {
+    aos_core::deal_committed_with_strategy::<SequentialStrategy, C>(pp, secret)
+}
+
+pub fn deal_with_rng<C, R>(
+    pp: &SecretParams<C>,
+    secret: &F2PowElement,
+    rng: &mut R,
+) -> Result<Shares, SchemeError>
+where
+    C: AdditiveCode,
+    R: Rng + ?Sized,
+{
+    aos_core::deal_with_strategy_and_rng::<SequentialStrategy, C, R>(pp, secret, rng)
+}
+
+pub fn deal_with_seed<C: AdditiveCode>(
+    pp: &SecretParams<C>,
+    secret: &F2PowElement,
+    seed: u64,
+) -> Result<Shares, SchemeError> {
+    deal_with_rng(pp, secret, &mut aos_core::seeded_rng(seed))
+}
+
+/// Deal one bundle per participant of `layout`.
+pub fn deal_bundles<C: AdditiveCode>(
+    pp: &SecretParams<C>,
+    secret: &F2PowElement,
+    layout: &AccessLayout,
+) -> Result<BundledShares, SchemeError> {
+    aos_core::deal_bundles_with_strategy::<SequentialStrategy, C>(pp, secret, layout)
+}
+
+/// One party's dealing of a random contribution for joint sharing; see
+/// [`aos_core::joint`].
+pub fn contribute<C: AdditiveCode>(pp: &SecretParams<C>) -> Result<Shares, SchemeError> {
+    aos_core::joint::contribute_with_strategy::<SequentialStrategy, C>(pp)
+}
+
+/// Run joint sharing among `parties` parties in one process; see
+/// [`aos_core::joint::simulate_joint_deal_with_strategy_and_rng`].
+pub fn simulate_joint_deal<C: AdditiveCode>(
+    pp: &SecretParams<C>,
+    parties: usize,
+) -> Result<JointSimulation, SchemeError> {
+    aos_core::joint::simulate_joint_deal_with_strategy::<SequentialStrategy, C>(pp, parties)
+}
+
+pub fn simulate_joint_deal_with_seed<C: AdditiveCode>(
+    pp: &SecretParams<C>,
+    parties: usize,
+    seed: u64,
+) -> Result<JointSimulation, SchemeError> {
+    aos_core::joint::simulate_joint_deal_with_strategy_and_rng::<SequentialStrategy, C, _>(
+        pp,
+        parties,
+        &mut aos_core::seeded_rng(seed),
+    )
+}
+
+/// Deal a byte string of any length as `ell`-bit blocks; see
+/// [`aos_core::blocks::deal_bytes_with_strategy_and_rng`].
+pub fn deal_bytes<C: AdditiveCode>(
+    pp: &SecretParams<C>,
+    secret: &[u8],
+    z0_mode: BlockZ0,
+) -> Result<BlockShares, SchemeError> {
+    aos_core::blocks::deal_bytes_with_strategy::<SequentialStrategy, C>(pp, secret, z0_mode)
+}
+
+pub fn deal_bytes_with_seed<C: AdditiveCode>(
+    pp: &SecretParams<C>,
+    secret: &[u8],
+    z0_mode: BlockZ0,
+    seed: u64,
+) -> Result<BlockShares, SchemeError> {
+    aos_core::blocks::deal_bytes_with_strategy_and_rng::<SequentialStrategy, C, _>(
+        pp,
+        secret,
+        z0_mode,
+        &mut aos_core::seeded_rng(seed),
+    )
+}
+
+/// Rerandomise `shares` without reconstructing; see
+/// [`aos_core::refresh::refresh_with_strategy_and_rng`].
+pub fn refresh<C: AdditiveCode>(
+    pp: &SecretParams<C>,
+    shares: &Shares,
+) -> Result<Shares, SchemeError> {
+    aos_core::refresh::refresh_with_strategy::<SequentialStrategy, C>(pp, shares)
 }
 
-pub fn reconstruct(
-    pp: &SecretParams<LdpcCode>,
+pub fn refresh_with_seed<C: AdditiveCode>(
+    pp: &SecretParams<C>,
     shares: &Shares,
-) -> (Option<F2PowElement>, Option<ReconstructMetrics>) {
-    aos_core::reconstruct_with_strategy::<SequentialStrategy>(pp, shares)
+    seed: u64,
+) -> Result<Shares, SchemeError> {
+    aos_core::refresh::refresh_with_strategy_and_rng::<SequentialStrategy, C, _>(
+        pp,
+        shares,
+        &mut aos_core::seeded_rng(seed),
+    )
+}
+
+/// Refresh update with one bundle per participant of `layout`; see
+/// [`aos_core::refresh::refresh_bundles_with_strategy_and_rng`].
+pub fn refresh_bundles<C: AdditiveCode>(
+    pp: &SecretParams<C>,
+    layout: &AccessLayout,
+) -> Result<BundledShares, SchemeError> {
+    aos_core::refresh::refresh_bundles_with_strategy::<SequentialStrategy, C>(pp, layout)
+}
+
+/// Regenerate the share of column `index` from the others by decoding, which
+/// exposes the secret to the repairer; see
+/// [`aos_core::repair::repair_share_with_strategy`].
+pub fn repair_share<C: AdditiveCode>(
+    pp: &SecretParams<C>,
+    available: &Shares,
+    index: u32,
+) -> Result<Option<Share>, SchemeError> {
+    aos_core::repair::repair_share_with_strategy::<SequentialStrategy, C>(pp, available, index)
+}
+
+pub fn reconstruct<C: AdditiveCode>(
+    pp: &SecretParams<C>,
+    shares: &Shares,
+) -> Result<(Option<F2PowElement>, Option<ReconstructMetrics>), SchemeError> {
+    aos_core::reconstruct_with_strategy::<SequentialStrategy, C>(pp, shares)
+}
+
+/// Reconstruct from participant bundles; see
+/// [`aos_core::reconstruct_bundles_with_strategy`].
+pub fn reconstruct_bundles<C: AdditiveCode>(
+    pp: &SecretParams<C>,
+    bundles: &BundledShares,
+) -> Result<(Option<F2PowElement>, Option<ReconstructMetrics>), SchemeError> {
+    aos_core::reconstruct_bundles_with_strategy::<SequentialStrategy, C>(pp, bundles)
+}
+
+pub fn reconstruct_bytes<C: AdditiveCode>(
+    pp: &SecretParams<C>,
+    shares: &BlockShares,
+) -> Result<(Option<Vec<u8>>, Option<ReconstructMetrics>), SchemeError> {
+    aos_core::blocks::reconstruct_bytes_with_strategy::<SequentialStrategy, C>(pp, shares)
+}
+
+/// Reconstruct with per-row diagnostics; see
+/// [`aos_core::reconstruct_outcome_with_strategy`].
+pub fn reconstruct_outcome<C: AdditiveCode>(
+    pp: &SecretParams<C>,
+    shares: &Shares,
+) -> Result<ReconstructOutcome, SchemeError> {
+    aos_core::reconstruct_outcome_with_strategy::<SequentialStrategy, C>(pp, shares)
+}
+
+/// Reconstruct from the shares that verify against `commitment`; see
+/// [`aos_core::reconstruct_verified_with_strategy`].
+pub fn reconstruct_verified<C: AdditiveCode>(
+    pp: &SecretParams<C>,
+    shares: &Shares,
+    commitment: &ShareCommitment,
+) -> Result<(Option<F2PowElement>, Option<ReconstructMetrics>), SchemeError> {
+    aos_core::reconstruct_verified_with_strategy::<SequentialStrategy, C>(pp, shares, commitment)
+}
+
+/// Reconstruct from shares that may contain bit errors, with `weights[s]` the LLR
+/// magnitude of `shares.shares[s]`; see [`aos_core::reconstruct_with_errors_with_strategy`].
+pub fn reconstruct_with_errors<C: AdditiveCode>(
+    pp: &SecretParams<C>,
+    shares: &Shares,
+    weights: &[f64],
+) -> Result<(Option<F2PowElement>, Option<ReconstructMetrics>), SchemeError> {
+    aos_core::reconstruct_with_errors_with_strategy::<SequentialStrategy, C>(pp, shares, weights)
+}
+
+/// Reconstruct from per-bit LLRs `row_llrs[row][col]` and the public `z0`; see
+/// [`aos_core::reconstruct_soft_with_strategy`].
+pub fn reconstruct_soft<C: AdditiveCode>(
+    pp: &SecretParams<C>,
+    z0: &F2PowElement,
+    row_llrs: &[Vec<f64>],
+) -> Result<(Option<F2PowElement>, Option<ReconstructMetrics>), SchemeError> {
+    aos_core::reconstruct_soft_with_strategy::<SequentialStrategy, C>(pp, z0, row_llrs)
+}
+
+/// Reconstruct by peeling and Gaussian elimination on `H` instead of belief
+/// propagation; see [`aos_core::reconstruct_erasures_with_strategy`].
+pub fn reconstruct_erasures<C: AdditiveCode>(
+    pp: &SecretParams<C>,
+    shares: &Shares,
+) -> Result<(Option<F2PowElement>, Option<ReconstructMetrics>), SchemeError> {
+    aos_core::reconstruct_erasures_with_strategy::<SequentialStrategy, C>(pp, shares)
+}
+
+/// Regenerate the share of column `index` from the parity checks of `H`; see
+/// [`aos_core::repair::repair_share_parity`].
+pub fn repair_share_parity<C: AdditiveCode>(
+    pp: &SecretParams<C>,
+    available: &Shares,
+    index: u32,
+) -> Result<Option<Share>, SchemeError> {
+    aos_core::repair::repair_share_parity(pp, available, index)
 }
@@ -0,0 +1,107 @@
+//! Sequential implementation with bitsliced encoding of 64 message rows per pass.
+
+use rand::Rng;
+
+use crate::aos::SequentialStrategy;
+use crate::aos_core::{self, DealSeed, ExecutionStrategy};
+use crate::bit_matrix::BitMatrix;
+use crate::code::ldpc_impl::LdpcCode;
+use crate::error::SchemeError;
+use crate::types::{
+    CodeInitParams, F2PowElement, ReconstructMetrics, RowReport, SecretParams, Shares,
+};
+
+/// Encodes all rows with [`LdpcCode::encode_bitsliced`]; every other phase is
+/// the sequential one.
+pub struct BitslicedStrategy;
+
+impl ExecutionStrategy<LdpcCode> for BitslicedStrategy {
+    fn generate_random_columns(len: usize, bit_len: usize, seed: &DealSeed) -> Vec<F2PowElement> {
+        <SequentialStrategy as ExecutionStrategy<LdpcCode>>::generate_random_columns(
+            len, bit_len, seed,
+        )
+    }
+
+    fn create_message_matrix(columns: &[F2PowElement], nrows: usize, ncols: usize) -> BitMatrix {
+        <SequentialStrategy as ExecutionStrategy<LdpcCode>>::create_message_matrix(
+            columns, nrows, ncols,
+        )
+    }
+
+    fn encode_rows(
+        message_matrix: &BitMatrix,
+        code_impl: &LdpcCode,
+        _nrows: usize,
+        _output_cols: usize,
+    ) -> BitMatrix {
+        code_impl.encode_bitsliced(message_matrix)
+    }
+
+    fn decode_rows(
+        encoded_matrix: &BitMatrix,
+        code_impl: &LdpcCode,
+        present_columns: &[bool],
+        input_length: usize,
+        nrows: usize,
+    ) -> (BitMatrix, Vec<RowReport>) {
+        SequentialStrategy::decode_rows(
+            encoded_matrix,
+            code_impl,
+            present_columns,
+            input_length,
+            nrows,
+        )
+    }
+
+    fn reconstruct_columns(decoded_matrix: &BitMatrix, input_length: usize) -> Vec<F2PowElement> {
+        <SequentialStrategy as ExecutionStrategy<LdpcCode>>::reconstruct_columns(
+            decoded_matrix,
+            input_length,
+        )
+    }
+}
+
+pub fn setup(params: CodeInitParams) -> Result<SecretParams<LdpcCode>, SchemeError> {
+    aos_core::setup(params)
+}
+
+pub fn setup_with_rng<R: Rng + ?Sized>(
+    params: CodeInitParams,
+    rng: &mut R,
+) -> Result<SecretParams<LdpcCode>, SchemeError> {
+    aos_core::setup_with_rng(params, rng)
+}
+
+pub fn setup_with_seed(
+    params: CodeInitParams,
+    seed: u64,
+) -> Result<SecretParams<LdpcCode>, SchemeError> {
+    setup_with_rng(params, &mut aos_core::seeded_rng(seed))
+}
+
+pub fn deal(pp: &SecretParams<LdpcCode>, secret: &F2PowElement) -> Result<Shares, SchemeError> {
+    aos_core::deal_with_strategy::<BitslicedStrategy, _>(pp, secret)
+}
+
+pub fn deal_with_rng<R: Rng + ?Sized>(
+    pp: &SecretParams<LdpcCode>,
+    secret: &F2PowElement,
+    rng: &mut R,
+) -> Result<Shares, SchemeError> {
+    aos_core::deal_with_strategy_and_rng::<BitslicedStrategy, _, R>(pp, secret, rng)
+}
+
+pub fn deal_with_seed(
+    pp: &SecretParams<LdpcCode>,
+    secret: &F2PowElement,
+    seed: u64,
+) -> Result<Shares, SchemeError> {
+    deal_with_rng(pp, secret, &mut aos_core::seeded_rng(seed))
+}
+
+pub fn reconstruct(
+    pp: &SecretParams<LdpcCode>,
+    shares: &Shares,
+) -> Result<(Option<F2PowElement>, Option<ReconstructMetrics>), SchemeError> {
+    aos_core::reconstruct_with_strategy::<BitslicedStrategy, _>(pp, shares)
+}
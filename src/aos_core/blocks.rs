@@ -0,0 +1,216 @@
+//! Byte strings of any length, dealt as `ell`-bit blocks under one parameter set.
+//!
+//! The secret is read big-endian ([`F2PowElement::from_bytes`]), zero-padded at the
+//! top to a whole number of blocks and split from the lowest bit up. Every block is
+//! an ordinary deal; share `i` of the result bundles column `i` of every block.
+
+use rand::Rng;
+use std::time::Duration;
+
+use super::{
+    deal_with_strategy_and_rng, reconstruct_outcome_with_strategy, shift_z0, z0_shift_codeword,
+    ExecutionStrategy,
+};
+use crate::code::AdditiveCode;
+use crate::error::SchemeError;
+use crate::types::{
+    BlockShares, BlockZ0, DecodingStats, F2PowElement, PhaseMetrics, ReconstructMetrics,
+    SecretParams, Share, Shares,
+};
+use crate::{log_success, log_warning};
+
+pub fn deal_bytes_with_strategy<S, C>(
+    pp: &SecretParams<C>,
+    secret: &[u8],
+    z0_mode: BlockZ0,
+) -> Result<BlockShares, SchemeError>
+where
+    S: ExecutionStrategy<C>,
+    C: AdditiveCode,
+{
+    deal_bytes_with_strategy_and_rng::<S, C, _>(pp, secret, z0_mode, &mut rand::rng())
+}
+
+/// Deal `secret` block by block with all randomness drawn from `rng`.
+///
+/// With [`BlockZ0::Shared`] one random `z0` is drawn first. Each block is then moved
+/// onto it by XORing the difference into a random column `r_j` with `a_j = 1`
+/// ([`shift_z0`]).
+pub fn deal_bytes_with_strategy_and_rng<S, C, R>(
+    pp: &SecretParams<C>,
+    secret: &[u8],
+    z0_mode: BlockZ0,
+    rng: &mut R,
+) -> Result<BlockShares, SchemeError>
+where
+    S: ExecutionStrategy<C>,
+    C: AdditiveCode,
+    R: Rng + ?Sized,
+{
+    let ell = pp.ell;
+    let block_count = BlockShares::block_count(secret.len(), ell);
+    let padded = F2PowElement::concat(&[
+        F2PowElement::from_bytes(secret),
+        F2PowElement::zero(block_count * ell - secret.len() * 8),
+    ]);
+
+    let shared = match z0_mode {
+        BlockZ0::PerBlock => None,
+        BlockZ0::Shared => {
+            let shift_codeword = z0_shift_codeword(pp).ok_or_else(|| {
+                SchemeError::InvalidCodeParameters(
+                    "the mask a is zero, so blocks cannot share z0".to_string(),
+                )
+            })?;
+            Some((F2PowElement::random(ell, rng), shift_codeword))
+        }
+    };
+
+    let mut columns: Vec<Vec<F2PowElement>> = (0..pp.code.output_length)
+        .map(|_| Vec::with_capacity(block_count))
+        .collect();
+    let mut z0 = Vec::with_capacity(block_count);
+    for block in 0..block_count {
+        let mut dealt =
+            deal_with_strategy_and_rng::<S, C, R>(pp, &padded.bits(block * ell, ell), rng)?;
+        match &shared {
+            Some((shared_z0, shift_codeword)) => {
+                let mut delta = dealt.z0;
+                delta.xor_assign(shared_z0);
+                shift_z0(&mut dealt.shares, shift_codeword, &delta);
+            }
+            None => z0.push(dealt.z0),
+        }
+        for (column, share) in columns.iter_mut().zip(dealt.shares) {
+            column.push(share.y);
+        }
+    }
+    if let Some((shared_z0, _)) = shared {
+        z0.push(shared_z0);
+    }
+
+    log_success!(
+        "Dealt {} bytes as {} blocks of {} bits ({} z0)",
+        secret.len(),
+        block_count,
+        ell,
+        z0.len()
+    );
+
+    Ok(BlockShares {
+        shares: columns
+            .into_iter()
+            .enumerate()
+            .map(|(i, parts)| Share {
+                y: F2PowElement::concat(&parts),
+                i: i as u32,
+                proof: None,
+            })
+            .collect(),
+        z0,
+        secret_len: secret.len(),
+    })
+}
+
+/// Reconstruct every block from the bundled shares and join them.
+///
+/// Blocks are decoded in order and the first one that fails ends the attempt, as
+/// the blocks share their erasure pattern. The metrics add up the phases of the
+/// attempted blocks.
+pub fn reconstruct_bytes_with_strategy<S, C>(
+    pp: &SecretParams<C>,
+    shares: &BlockShares,
+) -> Result<(Option<Vec<u8>>, Option<ReconstructMetrics>), SchemeError>
+where
+    S: ExecutionStrategy<C>,
+    C: AdditiveCode,
+{
+    let ell = pp.ell;
+    let block_count = BlockShares::block_count(shares.secret_len, ell);
+    if shares.z0.len() != 1 && shares.z0.len() != block_count {
+        return Err(SchemeError::ParameterMismatch {
+            parameter: "z0 count",
+            expected: block_count,
+            actual: shares.z0.len(),
+        });
+    }
+    for share in &shares.shares {
+        if share.y.bit_len != block_count * ell {
+            return Err(SchemeError::ShareLengthMismatch {
+                index: share.i,
+                expected: block_count * ell,
+                actual: share.y.bit_len,
+            });
+        }
+    }
+
+    let mut blocks = Vec::with_capacity(block_count);
+    let mut block_metrics = Vec::with_capacity(block_count);
+    let mut rows = Vec::with_capacity(block_count * ell);
+    for block in 0..block_count {
+        let block_shares = Shares {
+            shares: shares
+                .shares
+                .iter()
+                .map(|share| Share {
+                    y: share.y.bits(block * ell, ell),
+                    i: share.i,
+                    proof: None,
+                })
+                .collect(),
+            z0: shares.z0[if shares.z0.len() == 1 { 0 } else { block }].clone(),
+            metrics: None,
+            commitment: None,
+        };
+        let outcome = reconstruct_outcome_with_strategy::<S, C>(pp, &block_shares)?;
+        rows.extend(outcome.rows);
+        block_metrics.push(outcome.metrics);
+        match outcome.secret {
+            Some(secret) => blocks.push(secret),
+            None => {
+                log_warning!("Block {} of {} failed to decode", block + 1, block_count);
+                break;
+            }
+        }
+    }
+
+    let metrics = merge_metrics(
+        &block_metrics,
+        DecodingStats::from_rows(&rows, pp.code.code_impl.max_iterations()),
+    );
+    let secret = (blocks.len() == block_count).then(|| {
+        F2PowElement::concat(&blocks)
+            .bits(0, shares.secret_len * 8)
+            .to_bytes()
+    });
+    Ok((secret, Some(metrics)))
+}
+
+fn merge_metrics(
+    parts: &[ReconstructMetrics],
+    decoding_stats: DecodingStats,
+) -> ReconstructMetrics {
+    let sum = |phase: fn(&ReconstructMetrics) -> &PhaseMetrics| -> Duration {
+        parts.iter().map(|part| phase(part).duration).sum()
+    };
+    let total_time = parts.iter().map(|part| part.total_time).sum();
+    ReconstructMetrics {
+        matrix_setup: PhaseMetrics::new("Matrix setup", sum(|m| &m.matrix_setup), total_time),
+        row_decoding: PhaseMetrics::new("Row decoding", sum(|m| &m.row_decoding), total_time),
+        column_reconstruction: PhaseMetrics::new(
+            "Column reconstruction",
+            sum(|m| &m.column_reconstruction),
+            total_time,
+        ),
+        final_computation: PhaseMetrics::new(
+            "Final computation",
+            sum(|m| &m.final_computation),
+            total_time,
+        ),
+        total_time,
+        decoding_stats: Some(decoding_stats),
+        rejected_shares: Vec::new(),
+        corrected_shares: Vec::new(),
+        participants: Vec::new(),
+    }
+}
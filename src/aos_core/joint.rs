@@ -0,0 +1,154 @@
+//! Dealer-less joint sharing.
+//!
+//! Each of `m` parties deals a random contribution of its own under the common
+//! [`SecretParams`] and sends column `i` of its dealing to custodian `i`. Deal is
+//! linear, so the column-wise XOR of all dealings, with the XOR of their `z0`, is a
+//! sharing of the XOR of the contributions: a secret no single party knows.
+
+use rand::Rng;
+
+use super::{
+    deal_committed_with_strategy_and_rng, deal_with_strategy_and_rng, present_columns,
+    ExecutionStrategy,
+};
+use crate::code::AdditiveCode;
+use crate::error::SchemeError;
+use crate::types::{F2PowElement, SecretParams, Shares};
+use crate::{log_info, log_success};
+
+pub fn contribute_with_strategy<S, C>(pp: &SecretParams<C>) -> Result<Shares, SchemeError>
+where
+    S: ExecutionStrategy<C>,
+    C: AdditiveCode,
+{
+    contribute_with_strategy_and_rng::<S, C, _>(pp, &mut rand::rng())
+}
+
+/// One party's dealing of a random `ell`-bit contribution, which is dropped right
+/// away. The dealing carries its commitment, which the party publishes with `z0`.
+pub fn contribute_with_strategy_and_rng<S, C, R>(
+    pp: &SecretParams<C>,
+    rng: &mut R,
+) -> Result<Shares, SchemeError>
+where
+    S: ExecutionStrategy<C>,
+    C: AdditiveCode,
+    R: Rng + ?Sized,
+{
+    let contribution = F2PowElement::random(pp.ell, rng);
+    deal_committed_with_strategy_and_rng::<S, C, R>(pp, &contribution, rng)
+}
+
+/// Combine the dealings of all parties into shares of the XOR of their
+/// contributions.
+///
+/// Every dealing must fit `pp`, and a dealing that carries a commitment must match
+/// it, share by share and in `z0`; a share that does not is reported as
+/// [`SchemeError::CommitmentMismatch`] rather than dropped, since it would spoil the
+/// combined column. The combined shares hold the columns every dealing has.
+pub fn combine_dealings<C: AdditiveCode>(
+    pp: &SecretParams<C>,
+    dealings: &[Shares],
+) -> Result<Shares, SchemeError> {
+    let Some((first, rest)) = dealings.split_first() else {
+        return Err(SchemeError::NoDealings);
+    };
+    for (party, dealing) in dealings.iter().enumerate() {
+        present_columns(pp, dealing)?;
+        let Some(commitment) = &dealing.commitment else {
+            continue;
+        };
+        if !commitment.verify_z0(&dealing.z0) {
+            return Err(SchemeError::CommitmentMismatch(format!(
+                "z0 of dealing {}",
+                party
+            )));
+        }
+        if let Some(share) = dealing
+            .shares
+            .iter()
+            .find(|share| !commitment.verify_share(share))
+        {
+            return Err(SchemeError::CommitmentMismatch(format!(
+                "share {} of dealing {}",
+                share.i, party
+            )));
+        }
+    }
+
+    let mut combined = first.clone();
+    combined.metrics = None;
+    combined.commitment = None;
+    for dealing in rest {
+        combined = combined.combine(pp, dealing)?;
+    }
+    log_success!(
+        "Combined {} dealings into {} shares",
+        dealings.len(),
+        combined.shares.len()
+    );
+    Ok(combined)
+}
+
+/// Outcome of an in-process run of the joint protocol.
+#[derive(Clone)]
+pub struct JointSimulation {
+    /// The combined shares, as the custodians would hold them.
+    pub shares: Shares,
+    /// Contribution of every party. Only the simulation sees them all; in the
+    /// protocol each stays with its party.
+    pub contributions: Vec<F2PowElement>,
+}
+
+impl JointSimulation {
+    /// The jointly shared secret, the XOR of all contributions.
+    pub fn secret(&self) -> Option<F2PowElement> {
+        let (first, rest) = self.contributions.split_first()?;
+        Some(rest.iter().fold(first.clone(), |mut acc, contribution| {
+            acc.xor_assign(contribution);
+            acc
+        }))
+    }
+}
+
+pub fn simulate_joint_deal_with_strategy<S, C>(
+    pp: &SecretParams<C>,
+    parties: usize,
+) -> Result<JointSimulation, SchemeError>
+where
+    S: ExecutionStrategy<C>,
+    C: AdditiveCode,
+{
+    simulate_joint_deal_with_strategy_and_rng::<S, C, _>(pp, parties, &mut rand::rng())
+}
+
+/// Run the protocol for `parties` parties in one process: each draws a
+/// contribution and deals it, and the dealings are combined with
+/// [`combine_dealings`].
+pub fn simulate_joint_deal_with_strategy_and_rng<S, C, R>(
+    pp: &SecretParams<C>,
+    parties: usize,
+    rng: &mut R,
+) -> Result<JointSimulation, SchemeError>
+where
+    S: ExecutionStrategy<C>,
+    C: AdditiveCode,
+    R: Rng + ?Sized,
+{
+    log_info!("Simulating joint sharing with {} parties", parties);
+    let mut contributions = Vec::with_capacity(parties);
+    let mut dealings = Vec::with_capacity(parties);
+    for _ in 0..parties {
+        let contribution = F2PowElement::random(pp.ell, rng);
+        dealings.push(deal_with_strategy_and_rng::<S, C, R>(
+            pp,
+            &contribution,
+            rng,
+        )?);
+        contributions.push(contribution);
+    }
+    Ok(JointSimulation {
+        shares: combine_dealings(pp, &dealings)?,
+        contributions,
+    })
+}
@@ -1,66 +1,111 @@
 //! Core module for secret sharing operations using LDPC codes.
 
+pub mod blocks;
+pub mod joint;
+pub mod refresh;
+pub mod repair;
+
+use chacha20::ChaCha20Rng;
 use ldpc_toolbox::gf2::GF2;
-use ndarray::Array2;
+use ldpc_toolbox::sparse::SparseMatrix;
+use ndarray::Array1;
 use num_traits::{One, Zero};
+use rand::{Rng, SeedableRng};
 use std::time::Instant;
 
-use crate::code::ldpc_impl::LdpcCode;
-use crate::code::AdditiveCode;
+use crate::access::{AccessLayout, BundledShares};
+use crate::bit_matrix::{bytes_to_words, codeword_to_words, BitMatrix};
+use crate::code::erasure::{solve_erasures, ErasureSolution};
+use crate::code::{AdditiveCode, DecodeResult};
+use crate::error::SchemeError;
+use crate::integrity::ShareCommitment;
 use crate::types::{
     CodeInitParams, CodeParams, DealMetrics, DecodingStats, F2PowElement, PhaseMetrics,
-    ReconstructMetrics, SecretParams, Share, Shares,
+    ReconstructMetrics, ReconstructOutcome, RowReport, SecretParams, Share, Shares,
 };
 use crate::{log_success, log_verbose, log_warning};
 
-#[inline]
-pub fn codeword_to_gf2_buf(codeword: &[u8], out: &mut [GF2], len: usize) {
-    let gf2_one = GF2::one();
-    let gf2_zero = GF2::zero();
-    for (dst, &byte) in out[..len].iter_mut().zip(codeword.iter().take(len)) {
-        *dst = if byte == 1 { gf2_one } else { gf2_zero };
-    }
-}
-
+/// `secret` XOR the `columns` selected by `a_bits`. The mask must cover every
+/// column, and every column must be as long as `secret`.
 #[inline]
 pub fn masked_xor(
     secret: &F2PowElement,
     a_bits: &[bool],
     columns: &[F2PowElement],
-) -> F2PowElement {
-    assert_eq!(
-        a_bits.len(),
-        columns.len(),
-        "mask length and column count must match"
-    );
+) -> Result<F2PowElement, SchemeError> {
+    if a_bits.len() != columns.len() {
+        return Err(SchemeError::ParameterMismatch {
+            parameter: "mask length",
+            expected: columns.len(),
+            actual: a_bits.len(),
+        });
+    }
 
     let mut acc = secret.clone();
     for (a_bit, column) in a_bits.iter().zip(columns) {
+        if column.bit_len != secret.bit_len {
+            return Err(SchemeError::ParameterMismatch {
+                parameter: "column bit length",
+                expected: secret.bit_len,
+                actual: column.bit_len,
+            });
+        }
         if *a_bit {
             acc.xor_assign(column);
         }
     }
-    acc
+    Ok(acc)
 }
 
-pub fn setup(params: CodeInitParams) -> SecretParams<LdpcCode> {
+/// Seed from which every random column of one deal derives its own RNG stream.
+pub type DealSeed = chacha20::Seed;
+
+/// RNG behind the `*_with_seed` functions. ChaCha20 is fixed by its specification,
+/// unlike `StdRng`, so a seed gives the same parameters and shares on every machine
+/// and across `rand` versions.
+pub fn seeded_rng(seed: u64) -> ChaCha20Rng {
+    ChaCha20Rng::seed_from_u64(seed)
+}
+
+/// RNG for column `index` of a deal: ChaCha20 keyed by `seed`, on stream `index`.
+///
+/// Each column gets an independent stream, so strategies may draw columns in any
+/// order (or concurrently) and still produce bit-identical output for the same seed.
+pub fn column_rng(seed: &DealSeed, index: usize) -> ChaCha20Rng {
+    let mut rng = ChaCha20Rng::from_seed(*seed);
+    rng.set_stream(index as u64);
+    rng
+}
+
+/// Build the code `C` from `params` and draw the public mask `a`.
+pub fn setup<C: AdditiveCode>(params: CodeInitParams) -> Result<SecretParams<C>, SchemeError> {
+    setup_with_rng(params, &mut rand::rng())
+}
+
+pub fn setup_with_rng<C, R>(
+    params: CodeInitParams,
+    rng: &mut R,
+) -> Result<SecretParams<C>, SchemeError>
+where
+    C: AdditiveCode,
+    R: Rng + ?Sized,
+{
     let start_time = Instant::now();
     log_verbose!("Starting setup operation...");
 
     let ell = params.secret_bits.unwrap_or(128);
-    let code_impl = LdpcCode::setup(params);
+    let code_impl = C::setup(params)?;
     let input_length = code_impl.input_length();
     let output_length = code_impl.output_length();
 
-    assert!(
-        input_length >= ell as u32,
-        "Information length ({}) must be >= secret bits ({})",
-        input_length,
-        ell
-    );
+    if (input_length as usize) < ell {
+        return Err(SchemeError::SecretTooLong {
+            ell,
+            info_length: input_length as usize,
+        });
+    }
 
-    let mut rng = rand::rng();
-    let a_mask = F2PowElement::random(input_length as usize, &mut rng);
+    let a_mask = F2PowElement::random(input_length as usize, rng);
     let a_bits: Vec<bool> = (0..input_length as usize).map(|i| a_mask.bit(i)).collect();
 
     log_success!(
@@ -71,7 +116,7 @@ pub fn setup(params: CodeInitParams) -> SecretParams<LdpcCode> {
         ell
     );
 
-    SecretParams {
+    Ok(SecretParams {
         code: CodeParams {
             output_length,
             input_length,
@@ -79,58 +124,230 @@ pub fn setup(params: CodeInitParams) -> SecretParams<LdpcCode> {
         },
         ell,
         a_bits,
-    }
+    })
 }
 
-pub trait ExecutionStrategy {
-    fn generate_random_columns(len: usize, bit_len: usize) -> Vec<F2PowElement>;
-    fn create_message_matrix(columns: &[F2PowElement], nrows: usize, ncols: usize) -> Array2<GF2>;
+/// How the per-row phases of deal and reconstruct are executed for code `C`.
+pub trait ExecutionStrategy<C: AdditiveCode> {
+    fn generate_random_columns(len: usize, bit_len: usize, seed: &DealSeed) -> Vec<F2PowElement>;
+    fn create_message_matrix(columns: &[F2PowElement], nrows: usize, ncols: usize) -> BitMatrix;
     fn encode_rows(
-        message_matrix: &Array2<GF2>,
-        code_impl: &LdpcCode,
+        message_matrix: &BitMatrix,
+        code_impl: &C,
         nrows: usize,
         output_cols: usize,
-    ) -> Array2<GF2>;
+    ) -> BitMatrix;
     fn decode_rows(
-        encoded_matrix: &Array2<GF2>,
-        code_impl: &LdpcCode,
+        encoded_matrix: &BitMatrix,
+        code_impl: &C,
         present_columns: &[bool],
         input_length: usize,
         nrows: usize,
-    ) -> (Array2<GF2>, DecodingStats);
-    fn reconstruct_columns(
-        decoded_matrix: &Array2<GF2>,
-        input_length: usize,
-        bit_len: usize,
-    ) -> Vec<F2PowElement>;
+    ) -> (BitMatrix, Vec<RowReport>);
+    fn reconstruct_columns(decoded_matrix: &BitMatrix, input_length: usize) -> Vec<F2PowElement>;
+
+    /// Decode every row from soft inputs (see [`soft_row_llrs`]) and return the full
+    /// decoded codewords. A row succeeds only if its result has a zero syndrome;
+    /// failed rows stay zero.
+    fn decode_rows_soft(
+        encoded_matrix: &BitMatrix,
+        code_impl: &C,
+        column_llrs: &[f64],
+        nrows: usize,
+    ) -> (BitMatrix, Vec<RowReport>) {
+        let mut decoded_matrix = BitMatrix::zeros(nrows, column_llrs.len());
+        let rows = (0..nrows)
+            .map(|row| {
+                let llrs = soft_row_llrs(encoded_matrix.row(row), column_llrs);
+                soft_row_report(
+                    code_impl,
+                    code_impl.decode_soft(&llrs),
+                    decoded_matrix.row_mut(row),
+                )
+            })
+            .collect();
+
+        (decoded_matrix, rows)
+    }
+
+    /// Decode row `r` from its own LLRs `row_llrs[r]`, one per codeword column, and
+    /// check every row like [`Self::decode_rows_soft`].
+    fn decode_rows_llrs(
+        code_impl: &C,
+        row_llrs: &[Vec<f64>],
+        ncols: usize,
+    ) -> (BitMatrix, Vec<RowReport>) {
+        let mut decoded_matrix = BitMatrix::zeros(row_llrs.len(), ncols);
+        let rows = row_llrs
+            .iter()
+            .enumerate()
+            .map(|(row, llrs)| {
+                soft_row_report(
+                    code_impl,
+                    code_impl.decode_soft(llrs),
+                    decoded_matrix.row_mut(row),
+                )
+            })
+            .collect();
+
+        (decoded_matrix, rows)
+    }
+
+    /// Solve the erasure system of `h` once for all rows; see [`solve_erasures`].
+    fn solve_erasures(
+        h: &SparseMatrix,
+        present_columns: &[bool],
+        columns: &BitMatrix,
+    ) -> ErasureSolution {
+        solve_erasures(h, present_columns, columns)
+    }
+}
+
+/// Report of a row handled by [`AdditiveCode::decode`]; a failed row records the
+/// syndrome weight of the decoder's last estimate.
+pub fn row_report<C: AdditiveCode>(code_impl: &C, decode_result: &DecodeResult) -> RowReport {
+    RowReport {
+        success: decode_result.success,
+        iterations: decode_result.iterations,
+        residual_syndrome_weight: if decode_result.success {
+            0
+        } else {
+            code_impl.syndrome_weight(&decode_result.codeword)
+        },
+    }
+}
+
+/// Report of a soft-decoded row, which only counts as decoded with a zero syndrome.
+/// The codeword of a decoded row is written to `row`.
+pub fn soft_row_report<C: AdditiveCode>(
+    code_impl: &C,
+    decode_result: DecodeResult,
+    row: &mut [u64],
+) -> RowReport {
+    let residual_syndrome_weight = code_impl.syndrome_weight(&decode_result.codeword);
+    let success = decode_result.success && residual_syndrome_weight == 0;
+    if success {
+        codeword_to_words(&decode_result.codeword, row, decode_result.codeword.len());
+    }
+    RowReport {
+        success,
+        iterations: decode_result.iterations,
+        residual_syndrome_weight,
+    }
+}
+
+/// LLRs of one received row: column `j` contributes `column_llrs[j]`, negated when
+/// its bit is set.
+pub fn soft_row_llrs(row: &[u64], column_llrs: &[f64]) -> Vec<f64> {
+    column_llrs
+        .iter()
+        .enumerate()
+        .map(|(col, &llr)| {
+            if (row[col / 64] >> (col % 64)) & 1 == 1 {
+                -llr
+            } else {
+                llr
+            }
+        })
+        .collect()
 }
 
-pub fn create_shares_from_matrix(encoded_matrix: &Array2<GF2>, output_length: u32) -> Vec<Share> {
+pub fn create_shares_from_matrix(encoded_matrix: &BitMatrix, output_length: u32) -> Vec<Share> {
+    let columns = encoded_matrix.transpose();
     (0..output_length)
         .map(|i| Share {
-            y: encoded_matrix.column(i as usize).to_owned(),
+            y: columns.row_element(i as usize),
             i,
+            proof: None,
         })
         .collect()
 }
 
-pub fn deal_with_strategy<S>(pp: &SecretParams<LdpcCode>, secret: &F2PowElement) -> Shares
+/// Share columns as the rows of an `n x ell` matrix; missing shares stay zero.
+fn share_column_matrix(shares: &Shares, ncols: usize, nrows: usize) -> BitMatrix {
+    let mut columns = BitMatrix::zeros(ncols, nrows);
+    for share in &shares.shares {
+        bytes_to_words(&share.y.bytes, columns.row_mut(share.i as usize));
+    }
+    columns
+}
+
+/// Check that `z0` and every share match the setup (distinct in-range columns of
+/// `ell` bits) and return the presence mask over all codeword positions.
+pub(crate) fn present_columns<C: AdditiveCode>(
+    pp: &SecretParams<C>,
+    shares: &Shares,
+) -> Result<Vec<bool>, SchemeError> {
+    if shares.z0.bit_len != pp.ell {
+        return Err(SchemeError::ParameterMismatch {
+            parameter: "z0 bit length",
+            expected: pp.ell,
+            actual: shares.z0.bit_len,
+        });
+    }
+    let mut present = vec![false; pp.code.output_length as usize];
+    for share in &shares.shares {
+        if share.i >= pp.code.output_length {
+            return Err(SchemeError::ShareIndexOutOfRange {
+                index: share.i,
+                output_length: pp.code.output_length,
+            });
+        }
+        if share.y.bit_len != pp.ell {
+            return Err(SchemeError::ShareLengthMismatch {
+                index: share.i,
+                expected: pp.ell,
+                actual: share.y.bit_len,
+            });
+        }
+        if std::mem::replace(&mut present[share.i as usize], true) {
+            return Err(SchemeError::DuplicateShare { index: share.i });
+        }
+    }
+    Ok(present)
+}
+
+pub fn deal_with_strategy<S, C>(
+    pp: &SecretParams<C>,
+    secret: &F2PowElement,
+) -> Result<Shares, SchemeError>
 where
-    S: ExecutionStrategy,
+    S: ExecutionStrategy<C>,
+    C: AdditiveCode,
 {
-    assert_eq!(
-        secret.bit_len, pp.ell,
-        "secret bit length must match setup ell"
-    );
+    deal_with_strategy_and_rng::<S, C, _>(pp, secret, &mut rand::rng())
+}
+
+/// Deal with all randomness drawn from `rng`; the same RNG state yields the same
+/// shares for every strategy.
+pub fn deal_with_strategy_and_rng<S, C, R>(
+    pp: &SecretParams<C>,
+    secret: &F2PowElement,
+    rng: &mut R,
+) -> Result<Shares, SchemeError>
+where
+    S: ExecutionStrategy<C>,
+    C: AdditiveCode,
+    R: Rng + ?Sized,
+{
+    if secret.bit_len != pp.ell {
+        return Err(SchemeError::ParameterMismatch {
+            parameter: "secret bit length",
+            expected: pp.ell,
+            actual: secret.bit_len,
+        });
+    }
 
     let start_time = Instant::now();
 
     let rand_vec_start = Instant::now();
-    let r_vec = S::generate_random_columns(pp.code.input_length as usize, pp.ell);
+    let mut seed = DealSeed::default();
+    rng.fill_bytes(&mut seed);
+    let r_vec = S::generate_random_columns(pp.code.input_length as usize, pp.ell, &seed);
     let rand_vec_duration = rand_vec_start.elapsed();
 
     let mask_start = Instant::now();
-    let z0 = masked_xor(secret, &pp.a_bits, &r_vec);
+    let z0 = masked_xor(secret, &pp.a_bits, &r_vec)?;
     let mask_duration = mask_start.elapsed();
 
     let matrix_start = Instant::now();
@@ -186,39 +403,107 @@ where
         metrics.share_creation.percentage
     );
 
-    Shares {
+    Ok(Shares {
         shares,
         z0,
         metrics: Some(metrics),
+        commitment: None,
+    })
+}
+
+pub fn deal_committed_with_strategy<S, C>(
+    pp: &SecretParams<C>,
+    secret: &F2PowElement,
+) -> Result<Shares, SchemeError>
+where
+    S: ExecutionStrategy<C>,
+    C: AdditiveCode,
+{
+    deal_committed_with_strategy_and_rng::<S, C, _>(pp, secret, &mut rand::rng())
+}
+
+/// Deal like [`deal_with_strategy_and_rng`], then commit to the shares and `z0` with
+/// [`Shares::commit`] so that every share carries its Merkle proof. The commitment
+/// is not part of the deal metrics.
+pub fn deal_committed_with_strategy_and_rng<S, C, R>(
+    pp: &SecretParams<C>,
+    secret: &F2PowElement,
+    rng: &mut R,
+) -> Result<Shares, SchemeError>
+where
+    S: ExecutionStrategy<C>,
+    C: AdditiveCode,
+    R: Rng + ?Sized,
+{
+    let mut shares = deal_with_strategy_and_rng::<S, C, R>(pp, secret, rng)?;
+    shares.commit()?;
+    Ok(shares)
+}
+
+/// Codeword of the unit message `e_j` for the first column `j` with `a_j = 1`, or
+/// `None` if the mask is zero.
+///
+/// XORing `delta` into the shares where this codeword is set adds `delta` to `r_j`
+/// and so to `z0`, without changing the secret; see [`shift_z0`].
+pub fn z0_shift_codeword<C: AdditiveCode>(pp: &SecretParams<C>) -> Option<Array1<GF2>> {
+    let column = pp.a_bits.iter().position(|&bit| bit)?;
+    let unit: Array1<GF2> = (0..pp.code.input_length as usize)
+        .map(|index| {
+            if index == column {
+                GF2::one()
+            } else {
+                GF2::zero()
+            }
+        })
+        .collect();
+    Some(pp.code.code_impl.encode(&unit))
+}
+
+/// Move the `z0` of a deal by `delta` (see [`z0_shift_codeword`]); shares are
+/// matched to the codeword by their column index.
+pub fn shift_z0(shares: &mut [Share], shift_codeword: &Array1<GF2>, delta: &F2PowElement) {
+    for share in shares {
+        if shift_codeword[share.i as usize].is_one() {
+            share.y.xor_assign(delta);
+        }
     }
 }
 
-pub fn reconstruct_with_strategy<S>(
-    pp: &SecretParams<LdpcCode>,
+pub fn reconstruct_with_strategy<S, C>(
+    pp: &SecretParams<C>,
+    shares: &Shares,
+) -> Result<(Option<F2PowElement>, Option<ReconstructMetrics>), SchemeError>
+where
+    S: ExecutionStrategy<C>,
+    C: AdditiveCode,
+{
+    reconstruct_outcome_with_strategy::<S, C>(pp, shares).map(ReconstructOutcome::into_parts)
+}
+
+/// Reconstruct and keep the per-row results: which rows failed, how many
+/// iterations each took, the syndrome weight the failed ones were left with, and
+/// how many more shares would probably be needed.
+pub fn reconstruct_outcome_with_strategy<S, C>(
+    pp: &SecretParams<C>,
     shares: &Shares,
-) -> (Option<F2PowElement>, Option<ReconstructMetrics>)
+) -> Result<ReconstructOutcome, SchemeError>
 where
-    S: ExecutionStrategy,
+    S: ExecutionStrategy<C>,
+    C: AdditiveCode,
 {
+    let present_columns = present_columns(pp, shares)?;
+
     let start_time = Instant::now();
     let nrows = pp.ell;
     let ncols = pp.code.output_length as usize;
-
-    let mut present_columns = vec![false; ncols];
-    for share in &shares.shares {
-        present_columns[share.i as usize] = true;
-    }
     let missing_count = present_columns.iter().filter(|&&present| !present).count();
 
     let setup_start = Instant::now();
-    let mut encoded_matrix = Array2::<GF2>::from_elem((nrows, ncols), GF2::zero());
-    for share in &shares.shares {
-        encoded_matrix.column_mut(share.i as usize).assign(&share.y);
-    }
+    let encoded_matrix = share_column_matrix(shares, ncols, nrows).transpose();
     let setup_duration = setup_start.elapsed();
 
     let decoding_start = Instant::now();
-    let (decoded_matrix, decoding_stats) = S::decode_rows(
+    let (decoded_matrix, rows) = S::decode_rows(
         &encoded_matrix,
         &pp.code.code_impl,
         &present_columns,
@@ -226,14 +511,15 @@ where
         nrows,
     );
     let decoding_duration = decoding_start.elapsed();
+    let decoding_stats = DecodingStats::from_rows(&rows, pp.code.code_impl.max_iterations());
 
     let (result, reconstruction_duration, final_duration) = if decoding_stats.failed_rows == 0 {
         let reconstruction_start = Instant::now();
-        let r = S::reconstruct_columns(&decoded_matrix, pp.code.input_length as usize, pp.ell);
+        let r = S::reconstruct_columns(&decoded_matrix, pp.code.input_length as usize);
         let reconstruction_duration = reconstruction_start.elapsed();
 
         let final_start = Instant::now();
-        let result = masked_xor(&shares.z0, &pp.a_bits, &r);
+        let result = masked_xor(&shares.z0, &pp.a_bits, &r)?;
         let final_duration = final_start.elapsed();
 
         (Some(result), reconstruction_duration, final_duration)
@@ -254,6 +540,9 @@ where
         final_computation: PhaseMetrics::new("Final computation", final_duration, total_duration),
         total_time: total_duration,
         decoding_stats: Some(decoding_stats.clone()),
+        rejected_shares: Vec::new(),
+        corrected_shares: Vec::new(),
+        participants: Vec::new(),
     };
 
     let success_rate = decoding_stats.success_rate() * 100.0;
@@ -289,13 +578,510 @@ where
         final_duration
     );
 
-    (result, Some(metrics))
+    let recommended_extra_shares =
+        recommended_extra_shares(&rows, missing_count, pp.code.input_length as usize, ncols);
+    if recommended_extra_shares > 0 {
+        log_warning!(
+            "Failed rows: {:?}; about {} more shares are probably needed",
+            rows.iter()
+                .enumerate()
+                .filter(|(_, row)| !row.success)
+                .map(|(index, _)| index)
+                .collect::<Vec<_>>(),
+            recommended_extra_shares
+        );
+    }
+
+    Ok(ReconstructOutcome {
+        secret: result,
+        metrics,
+        erasures: missing_count,
+        rows,
+        recommended_extra_shares,
+    })
+}
+
+/// Rough number of further shares after some rows failed: enough to reach `k`
+/// present shares, and at least one per unsatisfied check of the worst row, as
+/// every such check still waits for one of its erased bits. Never more than the
+/// number of erasures, so 0 when all shares were present.
+///
+/// Close to the decoding threshold this tracks the erasures that have to go; far
+/// beyond it the residual weight saturates and the figure is a lower bound.
+fn recommended_extra_shares(
+    rows: &[RowReport],
+    erasures: usize,
+    input_length: usize,
+    output_length: usize,
+) -> usize {
+    let Some(worst) = rows
+        .iter()
+        .filter(|row| !row.success)
+        .map(|row| row.residual_syndrome_weight)
+        .max()
+    else {
+        return 0;
+    };
+    let present = output_length - erasures;
+    input_length
+        .saturating_sub(present)
+        .max(worst)
+        .max(1)
+        .min(erasures)
+}
+
+/// Reconstruct from the shares that verify against the dealer's `commitment`.
+///
+/// Shares with a missing or invalid proof are dropped, so a tampered share costs
+/// one erasure instead of corrupting its rows; their indices are reported in
+/// [`ReconstructMetrics::rejected_shares`]. A `z0` that does not verify is an error.
+pub fn reconstruct_verified_with_strategy<S, C>(
+    pp: &SecretParams<C>,
+    shares: &Shares,
+    commitment: &ShareCommitment,
+) -> Result<(Option<F2PowElement>, Option<ReconstructMetrics>), SchemeError>
+where
+    S: ExecutionStrategy<C>,
+    C: AdditiveCode,
+{
+    if commitment.share_count != pp.code.output_length {
+        return Err(SchemeError::ParameterMismatch {
+            parameter: "commitment share count",
+            expected: pp.code.output_length as usize,
+            actual: commitment.share_count as usize,
+        });
+    }
+    if !commitment.verify_z0(&shares.z0) {
+        return Err(SchemeError::CommitmentMismatch("z0".to_string()));
+    }
+
+    let (valid, rejected): (Vec<&Share>, Vec<&Share>) = shares
+        .shares
+        .iter()
+        .partition(|share| commitment.verify_share(share));
+    let rejected_shares: Vec<u32> = rejected.iter().map(|share| share.i).collect();
+    if !rejected_shares.is_empty() {
+        log_warning!(
+            "Rejected {} shares that fail the commitment: {:?}",
+            rejected_shares.len(),
+            rejected_shares
+        );
+    }
+
+    let verified = Shares {
+        shares: valid.into_iter().cloned().collect(),
+        z0: shares.z0.clone(),
+        metrics: None,
+        commitment: Some(commitment.clone()),
+    };
+    let (result, mut metrics) = reconstruct_with_strategy::<S, C>(pp, &verified)?;
+    if let Some(metrics) = metrics.as_mut() {
+        metrics.rejected_shares = rejected_shares;
+    }
+    Ok((result, metrics))
+}
+
+/// Deal and hand every participant of `layout` their columns as one bundle.
+pub fn deal_bundles_with_strategy<S, C>(
+    pp: &SecretParams<C>,
+    secret: &F2PowElement,
+    layout: &AccessLayout,
+) -> Result<BundledShares, SchemeError>
+where
+    S: ExecutionStrategy<C>,
+    C: AdditiveCode,
+{
+    check_layout(pp, layout)?;
+    Ok(layout.bundle(deal_with_strategy::<S, C>(pp, secret)?))
+}
+
+fn check_layout<C: AdditiveCode>(
+    pp: &SecretParams<C>,
+    layout: &AccessLayout,
+) -> Result<(), SchemeError> {
+    if layout.output_length() != pp.code.output_length {
+        return Err(SchemeError::ParameterMismatch {
+            parameter: "layout column count",
+            expected: pp.code.output_length as usize,
+            actual: layout.output_length() as usize,
+        });
+    }
+    Ok(())
+}
+
+/// Reconstruct from the bundles of the participants present; their names are
+/// reported in [`ReconstructMetrics::participants`].
+pub fn reconstruct_bundles_with_strategy<S, C>(
+    pp: &SecretParams<C>,
+    bundles: &BundledShares,
+) -> Result<(Option<F2PowElement>, Option<ReconstructMetrics>), SchemeError>
+where
+    S: ExecutionStrategy<C>,
+    C: AdditiveCode,
+{
+    let participants = bundles.participant_names();
+    log_verbose!(
+        "Reconstructing from {} participants: {:?}",
+        participants.len(),
+        participants
+    );
+    let (result, mut metrics) = reconstruct_with_strategy::<S, C>(pp, &bundles.to_shares())?;
+    if let Some(metrics) = metrics.as_mut() {
+        metrics.participants = participants;
+    }
+    Ok((result, metrics))
+}
+
+/// Reconstruct from shares whose bits may be flipped, not only missing.
+///
+/// `weights[s]` is the reliability of `shares.shares[s]` as an LLR magnitude (its
+/// sign is ignored, and 0 makes the share an erasure). Every row is decoded from
+/// these soft values and kept only if the result has a zero syndrome. The present
+/// columns the decoder changed in some row are reported in
+/// [`ReconstructMetrics::corrected_shares`].
+pub fn reconstruct_with_errors_with_strategy<S, C>(
+    pp: &SecretParams<C>,
+    shares: &Shares,
+    weights: &[f64],
+) -> Result<(Option<F2PowElement>, Option<ReconstructMetrics>), SchemeError>
+where
+    S: ExecutionStrategy<C>,
+    C: AdditiveCode,
+{
+    let present_columns = present_columns(pp, shares)?;
+    if weights.len() != shares.shares.len() {
+        return Err(SchemeError::ParameterMismatch {
+            parameter: "share weight count",
+            expected: shares.shares.len(),
+            actual: weights.len(),
+        });
+    }
+
+    let start_time = Instant::now();
+    let nrows = pp.ell;
+    let ncols = pp.code.output_length as usize;
+
+    let setup_start = Instant::now();
+    let received = share_column_matrix(shares, ncols, nrows).transpose();
+    let mut column_llrs = vec![0.0; ncols];
+    for (share, weight) in shares.shares.iter().zip(weights) {
+        column_llrs[share.i as usize] = weight.abs();
+    }
+    let setup_duration = setup_start.elapsed();
+
+    let decoding_start = Instant::now();
+    let (decoded, rows) = S::decode_rows_soft(&received, &pp.code.code_impl, &column_llrs, nrows);
+
+    finish_soft_reconstruct::<S, C>(
+        pp,
+        &shares.z0,
+        SoftDecoding {
+            received,
+            present_columns,
+            decoded,
+            rows,
+            setup_duration,
+            decoding_duration: decoding_start.elapsed(),
+        },
+        start_time,
+    )
+}
+
+/// Reconstruct from per-bit LLRs instead of shares, as received over a soft
+/// channel such as [`crate::channel_model::ChannelModel::Awgn`].
+///
+/// `row_llrs[r][j]` is the LLR of bit `r` of share `j`, positive favouring 0 and 0
+/// where the share is missing. Rows are decoded and checked as in
+/// [`reconstruct_with_errors_with_strategy`]; a column counts as present if any of
+/// its LLRs is non-zero, and as corrected if a decoded bit disagrees with the sign
+/// of its LLR.
+pub fn reconstruct_soft_with_strategy<S, C>(
+    pp: &SecretParams<C>,
+    z0: &F2PowElement,
+    row_llrs: &[Vec<f64>],
+) -> Result<(Option<F2PowElement>, Option<ReconstructMetrics>), SchemeError>
+where
+    S: ExecutionStrategy<C>,
+    C: AdditiveCode,
+{
+    if z0.bit_len != pp.ell {
+        return Err(SchemeError::ParameterMismatch {
+            parameter: "z0 bit length",
+            expected: pp.ell,
+            actual: z0.bit_len,
+        });
+    }
+    if row_llrs.len() != pp.ell {
+        return Err(SchemeError::ParameterMismatch {
+            parameter: "LLR row count",
+            expected: pp.ell,
+            actual: row_llrs.len(),
+        });
+    }
+    let ncols = pp.code.output_length as usize;
+    if let Some(row) = row_llrs.iter().find(|row| row.len() != ncols) {
+        return Err(SchemeError::ParameterMismatch {
+            parameter: "LLR row length",
+            expected: ncols,
+            actual: row.len(),
+        });
+    }
+
+    let start_time = Instant::now();
+    let setup_start = Instant::now();
+    let mut received = BitMatrix::zeros(pp.ell, ncols);
+    let mut present_columns = vec![false; ncols];
+    for (row, llrs) in row_llrs.iter().enumerate() {
+        for (col, &llr) in llrs.iter().enumerate() {
+            received.set(row, col, llr < 0.0);
+            present_columns[col] |= llr != 0.0;
+        }
+    }
+    let setup_duration = setup_start.elapsed();
+
+    let decoding_start = Instant::now();
+    let (decoded, rows) = S::decode_rows_llrs(&pp.code.code_impl, row_llrs, ncols);
+
+    finish_soft_reconstruct::<S, C>(
+        pp,
+        z0,
+        SoftDecoding {
+            received,
+            present_columns,
+            decoded,
+            rows,
+            setup_duration,
+            decoding_duration: decoding_start.elapsed(),
+        },
+        start_time,
+    )
+}
+
+/// Soft-decoded rows with the hard decisions they were decoded from.
+struct SoftDecoding {
+    received: BitMatrix,
+    present_columns: Vec<bool>,
+    decoded: BitMatrix,
+    rows: Vec<RowReport>,
+    setup_duration: std::time::Duration,
+    decoding_duration: std::time::Duration,
+}
+
+/// Secret and metrics of a soft reconstruct: the secret only if every row decoded,
+/// with the present columns the decoder changed in some row as corrected shares.
+fn finish_soft_reconstruct<S, C>(
+    pp: &SecretParams<C>,
+    z0: &F2PowElement,
+    decoding: SoftDecoding,
+    start_time: Instant,
+) -> Result<(Option<F2PowElement>, Option<ReconstructMetrics>), SchemeError>
+where
+    S: ExecutionStrategy<C>,
+    C: AdditiveCode,
+{
+    let SoftDecoding {
+        received,
+        present_columns,
+        decoded: decoded_matrix,
+        rows,
+        setup_duration,
+        decoding_duration,
+    } = decoding;
+    let nrows = pp.ell;
+    let ncols = pp.code.output_length as usize;
+    let input_length = pp.code.input_length as usize;
+    let decoding_stats = DecodingStats::from_rows(&rows, pp.code.code_impl.max_iterations());
+    let (result, corrected_shares, reconstruction_duration, final_duration) =
+        if decoding_stats.failed_rows == 0 {
+            let reconstruction_start = Instant::now();
+            let mut flipped = vec![0u64; received.words_per_row()];
+            for row in 0..nrows {
+                let changes = decoded_matrix.row(row).iter().zip(received.row(row));
+                for (acc, (decoded, original)) in flipped.iter_mut().zip(changes) {
+                    *acc |= decoded ^ original;
+                }
+            }
+            let corrected: Vec<u32> = (0..ncols)
+                .filter(|&col| present_columns[col] && (flipped[col / 64] >> (col % 64)) & 1 == 1)
+                .map(|col| col as u32)
+                .collect();
+            let r =
+                S::reconstruct_columns(&decoded_matrix.leading_columns(input_length), input_length);
+            let reconstruction_duration = reconstruction_start.elapsed();
+
+            let final_start = Instant::now();
+            let result = masked_xor(z0, &pp.a_bits, &r)?;
+            (
+                Some(result),
+                corrected,
+                reconstruction_duration,
+                final_start.elapsed(),
+            )
+        } else {
+            (
+                None,
+                Vec::new(),
+                std::time::Duration::ZERO,
+                std::time::Duration::ZERO,
+            )
+        };
+
+    let total_duration = start_time.elapsed();
+    if result.is_some() {
+        log_success!(
+            "Error-tolerant reconstruct completed in {:.2?} (corrected shares: {}, avg_iter: {:.1})",
+            total_duration,
+            corrected_shares.len(),
+            decoding_stats.avg_iterations
+        );
+    } else {
+        log_warning!(
+            "Error-tolerant reconstruct failed in {:.2?} (decoded rows: {}/{})",
+            total_duration,
+            decoding_stats.successful_rows,
+            decoding_stats.total_rows
+        );
+    }
+
+    let metrics = ReconstructMetrics {
+        matrix_setup: PhaseMetrics::new("Matrix setup", setup_duration, total_duration),
+        row_decoding: PhaseMetrics::new("Soft row decoding", decoding_duration, total_duration),
+        column_reconstruction: PhaseMetrics::new(
+            "Column reconstruction",
+            reconstruction_duration,
+            total_duration,
+        ),
+        final_computation: PhaseMetrics::new("Final computation", final_duration, total_duration),
+        total_time: total_duration,
+        decoding_stats: Some(decoding_stats),
+        rejected_shares: Vec::new(),
+        corrected_shares,
+        participants: Vec::new(),
+    };
+
+    Ok((result, Some(metrics)))
+}
+
+/// Reconstruct by solving the erasure system of `H` once for all `ell` rows.
+///
+/// No belief propagation is run: erased positions are peeled where a check has a
+/// single one left, and the rest are recovered by Gaussian elimination, so
+/// reconstruction succeeds exactly when the available shares determine the message
+/// columns (maximum-likelihood erasure decoding). Codes without a sparse `H` fail
+/// with [`SchemeError::InvalidCodeParameters`].
+pub fn reconstruct_erasures_with_strategy<S, C>(
+    pp: &SecretParams<C>,
+    shares: &Shares,
+) -> Result<(Option<F2PowElement>, Option<ReconstructMetrics>), SchemeError>
+where
+    S: ExecutionStrategy<C>,
+    C: AdditiveCode,
+{
+    let h = pp.code.code_impl.sparse_parity_check().ok_or_else(|| {
+        SchemeError::InvalidCodeParameters(String::from(
+            "erasure decoding needs a sparse parity-check matrix",
+        ))
+    })?;
+    let present_columns = present_columns(pp, shares)?;
+
+    let start_time = Instant::now();
+    let nrows = pp.ell;
+    let ncols = pp.code.output_length as usize;
+    let input_length = pp.code.input_length as usize;
+
+    let setup_start = Instant::now();
+    let columns = share_column_matrix(shares, ncols, nrows);
+    let setup_duration = setup_start.elapsed();
+
+    let solve_start = Instant::now();
+    let solution = S::solve_erasures(h, &present_columns, &columns);
+    let solve_duration = solve_start.elapsed();
+
+    let reconstruction_start = Instant::now();
+    let message_columns: Option<Vec<F2PowElement>> = (0..input_length)
+        .map(|col| {
+            solution.column(col, &columns).map(|bits| F2PowElement {
+                bytes: bits
+                    .iter()
+                    .flat_map(|word| word.to_le_bytes())
+                    .take(nrows.div_ceil(8))
+                    .collect(),
+                bit_len: nrows,
+            })
+        })
+        .collect();
+    let reconstruction_duration = reconstruction_start.elapsed();
+
+    let failed_rows = match &message_columns {
+        Some(_) => solution
+            .inconsistent_rows
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum(),
+        None => nrows,
+    };
+    let decoding_stats = DecodingStats::new(nrows, nrows - failed_rows, failed_rows, 0, 0);
+
+    let final_start = Instant::now();
+    let result = message_columns
+        .filter(|_| failed_rows == 0)
+        .map(|r| masked_xor(&shares.z0, &pp.a_bits, &r))
+        .transpose()?;
+    let final_duration = final_start.elapsed();
+
+    let total_duration = start_time.elapsed();
+    let metrics = ReconstructMetrics {
+        matrix_setup: PhaseMetrics::new("Matrix setup", setup_duration, total_duration),
+        row_decoding: PhaseMetrics::new("Erasure solve", solve_duration, total_duration),
+        column_reconstruction: PhaseMetrics::new(
+            "Column reconstruction",
+            reconstruction_duration,
+            total_duration,
+        ),
+        final_computation: PhaseMetrics::new("Final computation", final_duration, total_duration),
+        total_time: total_duration,
+        decoding_stats: Some(decoding_stats),
+        rejected_shares: Vec::new(),
+        corrected_shares: Vec::new(),
+        participants: Vec::new(),
+    };
+
+    if result.is_some() {
+        log_success!(
+            "Erasure reconstruct completed in {:.2?} (erased: {}, eliminated: {}, solve: {:.1}%)",
+            total_duration,
+            solution.erased.len(),
+            solution.eliminated,
+            metrics.row_decoding.percentage
+        );
+    } else {
+        log_warning!(
+            "Erasure reconstruct failed in {:.2?} (erased: {}, failed rows: {}/{})",
+            total_duration,
+            solution.erased.len(),
+            failed_rows,
+            nrows
+        );
+    }
+
+    Ok((result, Some(metrics)))
 }
 
 #[cfg(test)]
 mod tests {
-    use super::masked_xor;
-    use crate::types::F2PowElement;
+    use super::{column_rng, masked_xor, recommended_extra_shares};
+    use crate::types::{F2PowElement, RowReport};
+    use rand::Rng;
+
+    #[test]
+    fn test_column_streams_match_chacha20_vectors() {
+        // Keystream of the all-zero key with nonce 0 (RFC 7539, A.1) and nonce 1.
+        let mut bytes = [0u8; 8];
+        column_rng(&[0; 32], 0).fill_bytes(&mut bytes);
+        assert_eq!(bytes, [0x76, 0xb8, 0xe0, 0xad, 0xa0, 0xf1, 0x3d, 0x90]);
+        column_rng(&[0; 32], 1).fill_bytes(&mut bytes);
+        assert_eq!(bytes, [0xef, 0x3f, 0xdf, 0xd6, 0xc6, 0x15, 0x78, 0xfb]);
+    }
 
     #[test]
     fn test_masked_xor_applies_selected_columns() {
@@ -305,7 +1091,39 @@ mod tests {
             F2PowElement::from_hex("0002", 16).unwrap(),
             F2PowElement::from_hex("0004", 16).unwrap(),
         ];
-        let result = masked_xor(&secret, &[true, false, true], &cols);
+        let result = masked_xor(&secret, &[true, false, true], &cols).unwrap();
         assert_eq!(result.to_hex(), "0006");
+        assert!(matches!(
+            masked_xor(&secret, &[true, false], &cols),
+            Err(crate::error::SchemeError::ParameterMismatch {
+                parameter: "mask length",
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_recommended_extra_shares() {
+        let decoded = RowReport {
+            success: true,
+            iterations: 3,
+            residual_syndrome_weight: 0,
+        };
+        let failed = |residual_syndrome_weight| RowReport {
+            success: false,
+            iterations: 50,
+            residual_syndrome_weight,
+        };
+
+        assert_eq!(recommended_extra_shares(&[decoded; 4], 30, 80, 100), 0);
+        assert_eq!(
+            recommended_extra_shares(&[decoded, failed(5), failed(12)], 30, 80, 100),
+            12
+        );
+        // Below k present shares at least the missing ones are needed.
+        assert_eq!(recommended_extra_shares(&[failed(5)], 30, 90, 100), 20);
+        // Capped by what is missing, and nothing helps without erasures.
+        assert_eq!(recommended_extra_shares(&[failed(40)], 30, 80, 100), 30);
+        assert_eq!(recommended_extra_shares(&[failed(0)], 0, 80, 100), 0);
     }
 }
@@ -0,0 +1,119 @@
+//! Proactive refresh: rerandomise the shares of a deal without reconstructing it.
+//!
+//! An update is a deal of the zero secret whose `z0` is shifted to zero as well
+//! ([`shift_z0`]). XORing it into the shares keeps both the secret and `z0`, but
+//! shares from before and after a refresh no longer belong to the same codeword,
+//! so shares that leaked before the refresh are useless once the rest are updated.
+
+use rand::Rng;
+
+use super::{
+    check_layout, deal_with_strategy_and_rng, present_columns, shift_z0, z0_shift_codeword,
+    ExecutionStrategy,
+};
+use crate::access::{AccessLayout, BundledShares};
+use crate::code::AdditiveCode;
+use crate::error::SchemeError;
+use crate::log_success;
+use crate::types::{F2PowElement, SecretParams, Shares};
+
+pub fn refresh_update_with_strategy<S, C>(pp: &SecretParams<C>) -> Result<Shares, SchemeError>
+where
+    S: ExecutionStrategy<C>,
+    C: AdditiveCode,
+{
+    refresh_update_with_strategy_and_rng::<S, C, _>(pp, &mut rand::rng())
+}
+
+/// Deal a refresh update: shares of zero with a zero `z0`, committed to like any
+/// other deal so that every custodian can check their update column.
+pub fn refresh_update_with_strategy_and_rng<S, C, R>(
+    pp: &SecretParams<C>,
+    rng: &mut R,
+) -> Result<Shares, SchemeError>
+where
+    S: ExecutionStrategy<C>,
+    C: AdditiveCode,
+    R: Rng + ?Sized,
+{
+    let mut update = deal_with_strategy_and_rng::<S, C, R>(pp, &F2PowElement::zero(pp.ell), rng)?;
+    // With a zero mask `z0` equals the secret, which is already zero here.
+    if let Some(shift_codeword) = z0_shift_codeword(pp) {
+        let delta = std::mem::replace(&mut update.z0, F2PowElement::zero(pp.ell));
+        shift_z0(&mut update.shares, &shift_codeword, &delta);
+    }
+
+    update.commit()?;
+    Ok(update)
+}
+
+pub fn refresh_with_strategy<S, C>(
+    pp: &SecretParams<C>,
+    shares: &Shares,
+) -> Result<Shares, SchemeError>
+where
+    S: ExecutionStrategy<C>,
+    C: AdditiveCode,
+{
+    refresh_with_strategy_and_rng::<S, C, _>(pp, shares, &mut rand::rng())
+}
+
+/// Refresh every share in `shares` with a fresh update drawn from `rng`.
+///
+/// The secret and `z0` stay the same. The refreshed shares carry no commitment or
+/// proofs; a dealer holding all of them can publish a new commitment with
+/// [`Shares::commit`].
+pub fn refresh_with_strategy_and_rng<S, C, R>(
+    pp: &SecretParams<C>,
+    shares: &Shares,
+    rng: &mut R,
+) -> Result<Shares, SchemeError>
+where
+    S: ExecutionStrategy<C>,
+    C: AdditiveCode,
+    R: Rng + ?Sized,
+{
+    present_columns(pp, shares)?;
+    let update = refresh_update_with_strategy_and_rng::<S, C, R>(pp, rng)?;
+    let refreshed = shares
+        .shares
+        .iter()
+        .map(|share| share.xor(&update.shares[share.i as usize]))
+        .collect::<Result<_, _>>()?;
+
+    log_success!("Refreshed {} shares", shares.shares.len());
+    Ok(Shares {
+        shares: refreshed,
+        z0: shares.z0.clone(),
+        metrics: None,
+        commitment: None,
+    })
+}
+
+pub fn refresh_bundles_with_strategy<S, C>(
+    pp: &SecretParams<C>,
+    layout: &AccessLayout,
+) -> Result<BundledShares, SchemeError>
+where
+    S: ExecutionStrategy<C>,
+    C: AdditiveCode,
+{
+    refresh_bundles_with_strategy_and_rng::<S, C, _>(pp, layout, &mut rand::rng())
+}
+
+/// Deal a refresh update split by `layout`, so that each custodian receives only
+/// their own update columns and applies them with
+/// [`ParticipantBundle::apply_update`](crate::access::ParticipantBundle::apply_update).
+pub fn refresh_bundles_with_strategy_and_rng<S, C, R>(
+    pp: &SecretParams<C>,
+    layout: &AccessLayout,
+    rng: &mut R,
+) -> Result<BundledShares, SchemeError>
+where
+    S: ExecutionStrategy<C>,
+    C: AdditiveCode,
+    R: Rng + ?Sized,
+{
+    check_layout(pp, layout)?;
+    Ok(layout.bundle(refresh_update_with_strategy_and_rng::<S, C, R>(pp, rng)?))
+}
@@ -0,0 +1,181 @@
+//! Regenerate one lost share from the others without dealing again.
+//!
+//! Decode mode ([`repair_share_with_strategy`]) recovers the random columns `r_j`
+//! of every row, and since the repairer also holds `z0` and `s = z0 XOR <a, r>`,
+//! it learns the whole secret. Parity mode ([`repair_share_parity`]) only combines
+//! codeword columns through the checks of `H` and never computes the message bits.
+
+use std::time::Instant;
+
+use super::{present_columns, share_column_matrix, ExecutionStrategy};
+use crate::code::erasure::solve_erasures;
+use crate::code::AdditiveCode;
+use crate::error::SchemeError;
+use crate::types::{DecodingStats, F2PowElement, SecretParams, Share, Shares};
+use crate::{log_success, log_warning};
+
+/// `available` without a share at `index`, after checking that `index` is a column.
+fn without_index<C: AdditiveCode>(
+    pp: &SecretParams<C>,
+    available: &Shares,
+    index: u32,
+) -> Result<Shares, SchemeError> {
+    if index >= pp.code.output_length {
+        return Err(SchemeError::ShareIndexOutOfRange {
+            index,
+            output_length: pp.code.output_length,
+        });
+    }
+    Ok(Shares {
+        shares: available
+            .shares
+            .iter()
+            .filter(|share| share.i != index)
+            .cloned()
+            .collect(),
+        z0: available.z0.clone(),
+        metrics: None,
+        commitment: None,
+    })
+}
+
+/// Regenerate share `index` by decoding every row from the other shares in
+/// `available` and re-encoding it.
+///
+/// A share at `index` in `available` is ignored. Returns `Ok(None)` if a row fails
+/// to decode, like [`super::reconstruct_with_strategy`]. The repaired share has no
+/// proof.
+///
+/// The decoded rows are the random columns `r_j`, and together with `available.z0`
+/// they give the secret `s = z0 XOR <a, r>`: whoever runs this repair can learn the
+/// secret. Use [`repair_share_parity`] whenever the share must be repaired without
+/// exposing it.
+pub fn repair_share_with_strategy<S, C>(
+    pp: &SecretParams<C>,
+    available: &Shares,
+    index: u32,
+) -> Result<Option<Share>, SchemeError>
+where
+    S: ExecutionStrategy<C>,
+    C: AdditiveCode,
+{
+    let others = without_index(pp, available, index)?;
+    let present_columns = present_columns(pp, &others)?;
+
+    let start_time = Instant::now();
+    let nrows = pp.ell;
+    let ncols = pp.code.output_length as usize;
+    let input_length = pp.code.input_length as usize;
+
+    let encoded_matrix = share_column_matrix(&others, ncols, nrows).transpose();
+    let (decoded_matrix, rows) = S::decode_rows(
+        &encoded_matrix,
+        &pp.code.code_impl,
+        &present_columns,
+        input_length,
+        nrows,
+    );
+    let decoding_stats = DecodingStats::from_rows(&rows, pp.code.code_impl.max_iterations());
+    if decoding_stats.failed_rows > 0 {
+        log_warning!(
+            "Repair of share {} failed: {}/{} rows did not decode",
+            index,
+            decoding_stats.failed_rows,
+            nrows
+        );
+        return Ok(None);
+    }
+
+    let reencoded = S::encode_rows(&decoded_matrix, &pp.code.code_impl, nrows, ncols);
+    let mut y = F2PowElement::zero(nrows);
+    for row in 0..nrows {
+        y.set_bit(row, reencoded.get(row, index as usize));
+    }
+
+    log_success!(
+        "Repaired share {} by decoding in {:.2?}",
+        index,
+        start_time.elapsed()
+    );
+    Ok(Some(Share {
+        i: index,
+        y,
+        proof: None,
+    }))
+}
+
+/// Regenerate share `index` from the parity checks of `H` alone.
+///
+/// If some check through `index` has every other column available, the share is
+/// the XOR of those columns. Otherwise the erasures are solved by Gaussian
+/// elimination over `H` ([`solve_erasures`]), which recovers the column whenever
+/// the available shares determine it. Neither path decodes the message or checks
+/// the available shares for consistency: a wrong share in the checks used gives a
+/// wrong repair, so verify shares against the commitment first.
+///
+/// Fails with [`SchemeError::InvalidCodeParameters`] for codes without a sparse
+/// parity-check matrix.
+pub fn repair_share_parity<C: AdditiveCode>(
+    pp: &SecretParams<C>,
+    available: &Shares,
+    index: u32,
+) -> Result<Option<Share>, SchemeError> {
+    let h = pp.code.code_impl.sparse_parity_check().ok_or_else(|| {
+        SchemeError::InvalidCodeParameters(String::from(
+            "parity repair needs a sparse parity-check matrix",
+        ))
+    })?;
+    let others = without_index(pp, available, index)?;
+    let present_columns = present_columns(pp, &others)?;
+
+    let start_time = Instant::now();
+    let columns = share_column_matrix(&others, pp.code.output_length as usize, pp.ell);
+    let column = index as usize;
+
+    let single_check = h.iter_col(column).find(|&&row| {
+        h.iter_row(row)
+            .all(|&col| col == column || present_columns[col])
+    });
+    let y = match single_check {
+        Some(&row) => {
+            let mut y = F2PowElement::zero(pp.ell);
+            for &col in h.iter_row(row).filter(|&&col| col != column) {
+                y.xor_assign(&columns.row_element(col));
+            }
+            Some(y)
+        }
+        None => {
+            let solution = solve_erasures(h, &present_columns, &columns);
+            solution.column(column, &columns).map(|words| F2PowElement {
+                bytes: words
+                    .iter()
+                    .flat_map(|word| word.to_le_bytes())
+                    .take(pp.ell.div_ceil(8))
+                    .collect(),
+                bit_len: pp.ell,
+            })
+        }
+    };
+
+    match &y {
+        Some(_) => log_success!(
+            "Repaired share {} from {} in {:.2?}",
+            index,
+            if single_check.is_some() {
+                "one parity check"
+            } else {
+                "the erasure system"
+            },
+            start_time.elapsed()
+        ),
+        None => log_warning!(
+            "Repair of share {} failed: the available shares do not determine it",
+            index
+        ),
+    }
+    Ok(y.map(|y| Share {
+        i: index,
+        y,
+        proof: None,
+    }))
+}
@@ -1,170 +1,371 @@
 //! Parallel implementation of secret sharing operations using Rayon.
 
-use ldpc_toolbox::gf2::GF2;
-use ndarray::{Array1, Array2};
-use num_traits::{One, Zero};
+use ldpc_toolbox::sparse::SparseMatrix;
+use rand::Rng;
 use rayon::prelude::*;
-use std::sync::atomic::{AtomicUsize, Ordering};
-use std::sync::Arc;
 
-use crate::aos_core::{self, codeword_to_gf2_buf, ExecutionStrategy};
-use crate::code::ldpc_impl::LdpcCode;
+use crate::access::{AccessLayout, BundledShares};
+use crate::aos_core::joint::JointSimulation;
+use crate::aos_core::{
+    self, column_rng, row_report, soft_row_llrs, soft_row_report, DealSeed, ExecutionStrategy,
+};
+use crate::bit_matrix::{codeword_to_words, gf2_to_words, BitMatrix};
+use crate::code::erasure::{solve_erasures_parallel, ErasureSolution};
 use crate::code::AdditiveCode;
+use crate::error::SchemeError;
+use crate::integrity::ShareCommitment;
 use crate::types::{
-    CodeInitParams, DecodingStats, F2PowElement, ReconstructMetrics, SecretParams, Shares,
+    BlockShares, BlockZ0, CodeInitParams, F2PowElement, ReconstructMetrics, ReconstructOutcome,
+    RowReport, SecretParams, Share, Shares,
 };
 
 pub struct ParallelStrategy;
 
-impl ExecutionStrategy for ParallelStrategy {
-    fn generate_random_columns(len: usize, bit_len: usize) -> Vec<F2PowElement> {
+impl<C: AdditiveCode + Sync> ExecutionStrategy<C> for ParallelStrategy {
+    fn generate_random_columns(len: usize, bit_len: usize, seed: &DealSeed) -> Vec<F2PowElement> {
         (0..len)
             .into_par_iter()
-            .map(|_| {
-                let mut rng = rand::rng();
-                F2PowElement::random(bit_len, &mut rng)
-            })
+            .map(|idx| F2PowElement::random(bit_len, &mut column_rng(seed, idx)))
             .collect()
     }
 
-    fn create_message_matrix(columns: &[F2PowElement], nrows: usize, ncols: usize) -> Array2<GF2> {
-        let columns: Vec<Vec<GF2>> = (0..ncols)
-            .into_par_iter()
-            .map(|col_idx| {
-                (0..nrows)
-                    .map(|row_idx| {
-                        if columns[col_idx].bit(row_idx) {
-                            GF2::one()
-                        } else {
-                            GF2::zero()
-                        }
-                    })
-                    .collect()
-            })
-            .collect();
-
-        let mut flat = Vec::with_capacity(nrows * ncols);
-        for row_idx in 0..nrows {
-            for column in &columns {
-                flat.push(column[row_idx]);
-            }
-        }
-
-        Array2::from_shape_vec((nrows, ncols), flat).expect("message matrix shape mismatch")
+    fn create_message_matrix(columns: &[F2PowElement], nrows: usize, ncols: usize) -> BitMatrix {
+        BitMatrix::from_elements(&columns[..ncols], nrows).transpose()
     }
 
     fn encode_rows(
-        message_matrix: &Array2<GF2>,
-        code_impl: &LdpcCode,
+        message_matrix: &BitMatrix,
+        code_impl: &C,
         nrows: usize,
         output_cols: usize,
-    ) -> Array2<GF2> {
-        let encoded_rows: Vec<Array1<GF2>> = (0..nrows)
-            .into_par_iter()
-            .map(|row_idx| code_impl.encode(&message_matrix.row(row_idx).to_owned()))
-            .collect();
+    ) -> BitMatrix {
+        let mut encoded_matrix = BitMatrix::zeros(nrows, output_cols);
+        let words_per_row = encoded_matrix.words_per_row();
 
-        let mut flat = Vec::with_capacity(nrows * output_cols);
-        for row in &encoded_rows {
-            flat.extend(row.iter().copied());
-        }
+        encoded_matrix
+            .data_mut()
+            .par_chunks_mut(words_per_row)
+            .enumerate()
+            .for_each(|(row_idx, row)| {
+                gf2_to_words(&code_impl.encode(&message_matrix.row_to_gf2(row_idx)), row);
+            });
 
-        Array2::from_shape_vec((nrows, output_cols), flat).expect("encoded matrix shape mismatch")
+        encoded_matrix
     }
 
     fn decode_rows(
-        encoded_matrix: &Array2<GF2>,
-        code_impl: &LdpcCode,
+        encoded_matrix: &BitMatrix,
+        code_impl: &C,
         present_columns: &[bool],
         input_length: usize,
         nrows: usize,
-    ) -> (Array2<GF2>, DecodingStats) {
-        let successful_rows = Arc::new(AtomicUsize::new(0));
-        let failed_rows = Arc::new(AtomicUsize::new(0));
-        let total_iterations = Arc::new(AtomicUsize::new(0));
-        let max_iterations_hit = Arc::new(AtomicUsize::new(0));
-        let max_iter_limit = code_impl.max_iterations();
-
-        let decoded_rows: Vec<(usize, Option<Vec<GF2>>)> = (0..nrows)
-            .into_par_iter()
-            .map(|row_idx| {
-                let row_input = encoded_matrix.row(row_idx).to_owned();
-                let decode_result = code_impl.decode(&row_input, present_columns);
+    ) -> (BitMatrix, Vec<RowReport>) {
+        let mut decoded_matrix = BitMatrix::zeros(nrows, input_length);
+        let words_per_row = decoded_matrix.words_per_row();
 
-                total_iterations.fetch_add(decode_result.iterations, Ordering::Relaxed);
-                if decode_result.iterations >= max_iter_limit {
-                    max_iterations_hit.fetch_add(1, Ordering::Relaxed);
-                }
+        let rows = decoded_matrix
+            .data_mut()
+            .par_chunks_mut(words_per_row)
+            .enumerate()
+            .map(|(row_idx, row)| {
+                let row_input = encoded_matrix.row_to_gf2(row_idx);
+                let decode_result = code_impl.decode(&row_input, present_columns);
 
                 if decode_result.success {
-                    successful_rows.fetch_add(1, Ordering::Relaxed);
-                    let mut gf2_buf = vec![GF2::zero(); input_length];
-                    codeword_to_gf2_buf(&decode_result.codeword, &mut gf2_buf, input_length);
-                    (row_idx, Some(gf2_buf))
-                } else {
-                    failed_rows.fetch_add(1, Ordering::Relaxed);
-                    (row_idx, None)
+                    codeword_to_words(&decode_result.codeword, row, input_length);
                 }
+                row_report(code_impl, &decode_result)
             })
             .collect();
 
-        let mut decoded_storage = vec![GF2::zero(); nrows * input_length];
-        for (row_idx, row_data) in &decoded_rows {
-            if let Some(buf) = row_data {
-                let row_start = row_idx * input_length;
-                decoded_storage[row_start..row_start + input_length].copy_from_slice(buf);
-            }
-        }
-
-        let decoded_matrix = Array2::from_shape_vec((nrows, input_length), decoded_storage)
-            .expect("decoded matrix shape mismatch");
-
-        (
-            decoded_matrix,
-            DecodingStats::new(
-                nrows,
-                successful_rows.load(Ordering::Relaxed),
-                failed_rows.load(Ordering::Relaxed),
-                total_iterations.load(Ordering::Relaxed),
-                max_iterations_hit.load(Ordering::Relaxed),
-            ),
-        )
+        (decoded_matrix, rows)
     }
 
-    fn reconstruct_columns(
-        decoded_matrix: &Array2<GF2>,
-        input_length: usize,
-        bit_len: usize,
-    ) -> Vec<F2PowElement> {
+    fn reconstruct_columns(decoded_matrix: &BitMatrix, input_length: usize) -> Vec<F2PowElement> {
         const CHUNK_SIZE: usize = 32;
 
+        let columns = decoded_matrix.transpose();
         (0..input_length)
             .into_par_iter()
             .with_min_len(CHUNK_SIZE)
-            .map(|col_idx| {
-                let mut column = F2PowElement::zero(bit_len);
-                for row_idx in 0..bit_len {
-                    if decoded_matrix[(row_idx, col_idx)].is_one() {
-                        column.set_bit(row_idx, true);
-                    }
-                }
-                column
-            })
+            .map(|col_idx| columns.row_element(col_idx))
             .collect()
     }
+
+    fn decode_rows_soft(
+        encoded_matrix: &BitMatrix,
+        code_impl: &C,
+        column_llrs: &[f64],
+        nrows: usize,
+    ) -> (BitMatrix, Vec<RowReport>) {
+        let mut decoded_matrix = BitMatrix::zeros(nrows, column_llrs.len());
+        let words_per_row = decoded_matrix.words_per_row();
+
+        let rows = decoded_matrix
+            .data_mut()
+            .par_chunks_mut(words_per_row)
+            .enumerate()
+            .map(|(row_idx, row)| {
+                let llrs = soft_row_llrs(encoded_matrix.row(row_idx), column_llrs);
+                soft_row_report(code_impl, code_impl.decode_soft(&llrs), row)
+            })
+            .collect();
+
+        (decoded_matrix, rows)
+    }
+
+    fn decode_rows_llrs(
+        code_impl: &C,
+        row_llrs: &[Vec<f64>],
+        ncols: usize,
+    ) -> (BitMatrix, Vec<RowReport>) {
+        let mut decoded_matrix = BitMatrix::zeros(row_llrs.len(), ncols);
+        let words_per_row = decoded_matrix.words_per_row();
+
+        let rows = decoded_matrix
+            .data_mut()
+            .par_chunks_mut(words_per_row)
+            .zip(row_llrs.par_iter())
+            .map(|(row, llrs)| soft_row_report(code_impl, code_impl.decode_soft(llrs), row))
+            .collect();
+
+        (decoded_matrix, rows)
+    }
+
+    fn solve_erasures(
+        h: &SparseMatrix,
+        present_columns: &[bool],
+        columns: &BitMatrix,
+    ) -> ErasureSolution {
+        solve_erasures_parallel(h, present_columns, columns)
+    }
 }
 
-pub fn setup(params: CodeInitParams) -> SecretParams<LdpcCode> {
+pub fn setup<C: AdditiveCode>(params: CodeInitParams) -> Result<SecretParams<C>, SchemeError> {
     aos_core::setup(params)
 }
 
-pub fn deal(pp: &SecretParams<LdpcCode>, secret: &F2PowElement) -> Shares {
-    aos_core::deal_with_strategy::<ParallelStrategy>(pp, secret)
+pub fn setup_with_rng<C, R>(
+    params: CodeInitParams,
+    rng: &mut R,
+) -> Result<SecretParams<C>, SchemeError>
+where
+    C: AdditiveCode,
+    R: Rng + ?Sized,
+{
+    aos_core::setup_with_rng(params, rng)
+}
+
+pub fn setup_with_seed<C: AdditiveCode>(
+    params: CodeInitParams,
+    seed: u64,
+) -> Result<SecretParams<C>, SchemeError> {
+    setup_with_rng(params, &mut aos_core::seeded_rng(seed))
+}
+
+pub fn deal<C: AdditiveCode + Sync>(
+    pp: &SecretParams<C>,
+    secret: &F2PowElement,
+) -> Result<Shares, SchemeError> {
+    aos_core::deal_with_strategy::<ParallelStrategy, C>(pp, secret)
+}
+
+/// Deal and commit to the shares; see [`aos_core::deal_committed_with_strategy_and_rng`].
+pub fn deal_committed<C: AdditiveCode + Sync>(
+    pp: &SecretParams<C>,
+    secret: &F2PowElement,
+) -> Result<Shares, SchemeError> {
+    aos_core::deal_committed_with_strategy::<ParallelStrategy, C>(pp, secret)
+}
+
+pub fn deal_with_rng<C, R>(
+    pp: &SecretParams<C>,
+    secret: &F2PowElement,
+    rng: &mut R,
+) -> Result<Shares, SchemeError>
+where
+    C: AdditiveCode + Sync,
+    R: Rng + ?Sized,
+{
+    aos_core::deal_with_strategy_and_rng::<ParallelStrategy, C, R>(pp, secret, rng)
+}
+
+pub fn deal_with_seed<C: AdditiveCode + Sync>(
+    pp: &SecretParams<C>,
+    secret: &F2PowElement,
+    seed: u64,
+) -> Result<Shares, SchemeError> {
+    deal_with_rng(pp, secret, &mut aos_core::seeded_rng(seed))
+}
+
+/// Deal one bundle per participant of `layout`.
+pub fn deal_bundles<C: AdditiveCode + Sync>(
+    pp: &SecretParams<C>,
+    secret: &F2PowElement,
+    layout: &AccessLayout,
+) -> Result<BundledShares, SchemeError> {
+    aos_core::deal_bundles_with_strategy::<ParallelStrategy, C>(pp, secret, layout)
+}
+
+/// One party's dealing of a random contribution for joint sharing; see
+/// [`aos_core::joint`].
+pub fn contribute<C: AdditiveCode + Sync>(pp: &SecretParams<C>) -> Result<Shares, SchemeError> {
+    aos_core::joint::contribute_with_strategy::<ParallelStrategy, C>(pp)
+}
+
+/// Run joint sharing among `parties` parties in one process; see
+/// [`aos_core::joint::simulate_joint_deal_with_strategy_and_rng`].
+pub fn simulate_joint_deal<C: AdditiveCode + Sync>(
+    pp: &SecretParams<C>,
+    parties: usize,
+) -> Result<JointSimulation, SchemeError> {
+    aos_core::joint::simulate_joint_deal_with_strategy::<ParallelStrategy, C>(pp, parties)
+}
+
+pub fn simulate_joint_deal_with_seed<C: AdditiveCode + Sync>(
+    pp: &SecretParams<C>,
+    parties: usize,
+    seed: u64,
+) -> Result<JointSimulation, SchemeError> {
+    aos_core::joint::simulate_joint_deal_with_strategy_and_rng::<ParallelStrategy, C, _>(
+        pp,
+        parties,
+        &mut aos_core::seeded_rng(seed),
+    )
+}
+
+/// Deal a byte string of any length as `ell`-bit blocks; see
+/// [`aos_core::blocks::deal_bytes_with_strategy_and_rng`].
+pub fn deal_bytes<C: AdditiveCode + Sync>(
+    pp: &SecretParams<C>,
+    secret: &[u8],
+    z0_mode: BlockZ0,
+) -> Result<BlockShares, SchemeError> {
+    aos_core::blocks::deal_bytes_with_strategy::<ParallelStrategy, C>(pp, secret, z0_mode)
+}
+
+pub fn deal_bytes_with_seed<C: AdditiveCode + Sync>(
+    pp: &SecretParams<C>,
+    secret: &[u8],
+    z0_mode: BlockZ0,
+    seed: u64,
+) -> Result<BlockShares, SchemeError> {
+    aos_core::blocks::deal_bytes_with_strategy_and_rng::<ParallelStrategy, C, _>(
+        pp,
+        secret,
+        z0_mode,
+        &mut aos_core::seeded_rng(seed),
+    )
+}
+
+/// Rerandomise `shares` without reconstructing; see
+/// [`aos_core::refresh::refresh_with_strategy_and_rng`].
+pub fn refresh<C: AdditiveCode + Sync>(
+    pp: &SecretParams<C>,
+    shares: &Shares,
+) -> Result<Shares, SchemeError> {
+    aos_core::refresh::refresh_with_strategy::<ParallelStrategy, C>(pp, shares)
+}
+
+pub fn refresh_with_seed<C: AdditiveCode + Sync>(
+    pp: &SecretParams<C>,
+    shares: &Shares,
+    seed: u64,
+) -> Result<Shares, SchemeError> {
+    aos_core::refresh::refresh_with_strategy_and_rng::<ParallelStrategy, C, _>(
+        pp,
+        shares,
+        &mut aos_core::seeded_rng(seed),
+    )
+}
+
+/// Refresh update with one bundle per participant of `layout`; see
+/// [`aos_core::refresh::refresh_bundles_with_strategy_and_rng`].
+pub fn refresh_bundles<C: AdditiveCode + Sync>(
+    pp: &SecretParams<C>,
+    layout: &AccessLayout,
+) -> Result<BundledShares, SchemeError> {
+    aos_core::refresh::refresh_bundles_with_strategy::<ParallelStrategy, C>(pp, layout)
+}
+
+/// Regenerate the share of column `index` from the others by decoding, which
+/// exposes the secret to the repairer; see
+/// [`aos_core::repair::repair_share_with_strategy`].
+pub fn repair_share<C: AdditiveCode + Sync>(
+    pp: &SecretParams<C>,
+    available: &Shares,
+    index: u32,
+) -> Result<Option<Share>, SchemeError> {
+    aos_core::repair::repair_share_with_strategy::<ParallelStrategy, C>(pp, available, index)
+}
+
+pub fn reconstruct<C: AdditiveCode + Sync>(
+    pp: &SecretParams<C>,
+    shares: &Shares,
+) -> Result<(Option<F2PowElement>, Option<ReconstructMetrics>), SchemeError> {
+    aos_core::reconstruct_with_strategy::<ParallelStrategy, C>(pp, shares)
+}
+
+/// Reconstruct from participant bundles; see
+/// [`aos_core::reconstruct_bundles_with_strategy`].
+pub fn reconstruct_bundles<C: AdditiveCode + Sync>(
+    pp: &SecretParams<C>,
+    bundles: &BundledShares,
+) -> Result<(Option<F2PowElement>, Option<ReconstructMetrics>), SchemeError> {
+    aos_core::reconstruct_bundles_with_strategy::<ParallelStrategy, C>(pp, bundles)
+}
+
+pub fn reconstruct_bytes<C: AdditiveCode + Sync>(
+    pp: &SecretParams<C>,
+    shares: &BlockShares,
+) -> Result<(Option<Vec<u8>>, Option<ReconstructMetrics>), SchemeError> {
+    aos_core::blocks::reconstruct_bytes_with_strategy::<ParallelStrategy, C>(pp, shares)
+}
+
+/// Reconstruct with per-row diagnostics; see
+/// [`aos_core::reconstruct_outcome_with_strategy`].
+pub fn reconstruct_outcome<C: AdditiveCode + Sync>(
+    pp: &SecretParams<C>,
+    shares: &Shares,
+) -> Result<ReconstructOutcome, SchemeError> {
+    aos_core::reconstruct_outcome_with_strategy::<ParallelStrategy, C>(pp, shares)
+}
+
+/// Reconstruct from the shares that verify against `commitment`; see
+/// [`aos_core::reconstruct_verified_with_strategy`].
+pub fn reconstruct_verified<C: AdditiveCode + Sync>(
+    pp: &SecretParams<C>,
+    shares: &Shares,
+    commitment: &ShareCommitment,
+) -> Result<(Option<F2PowElement>, Option<ReconstructMetrics>), SchemeError> {
+    aos_core::reconstruct_verified_with_strategy::<ParallelStrategy, C>(pp, shares, commitment)
+}
+
+/// Reconstruct from shares that may contain bit errors, with `weights[s]` the LLR
+/// magnitude of `shares.shares[s]`; see [`aos_core::reconstruct_with_errors_with_strategy`].
+pub fn reconstruct_with_errors<C: AdditiveCode + Sync>(
+    pp: &SecretParams<C>,
+    shares: &Shares,
+    weights: &[f64],
+) -> Result<(Option<F2PowElement>, Option<ReconstructMetrics>), SchemeError> {
+    aos_core::reconstruct_with_errors_with_strategy::<ParallelStrategy, C>(pp, shares, weights)
+}
+
+/// Reconstruct from per-bit LLRs `row_llrs[row][col]` and the public `z0`; see
+/// [`aos_core::reconstruct_soft_with_strategy`].
+pub fn reconstruct_soft<C: AdditiveCode + Sync>(
+    pp: &SecretParams<C>,
+    z0: &F2PowElement,
+    row_llrs: &[Vec<f64>],
+) -> Result<(Option<F2PowElement>, Option<ReconstructMetrics>), SchemeError> {
+    aos_core::reconstruct_soft_with_strategy::<ParallelStrategy, C>(pp, z0, row_llrs)
 }
 
-pub fn reconstruct(
-    pp: &SecretParams<LdpcCode>,
+/// Reconstruct by peeling and Gaussian elimination on `H`, with the elimination
+/// steps run in parallel; see [`aos_core::reconstruct_erasures_with_strategy`].
+pub fn reconstruct_erasures<C: AdditiveCode + Sync>(
+    pp: &SecretParams<C>,
     shares: &Shares,
-) -> (Option<F2PowElement>, Option<ReconstructMetrics>) {
-    aos_core::reconstruct_with_strategy::<ParallelStrategy>(pp, shares)
+) -> Result<(Option<F2PowElement>, Option<ReconstructMetrics>), SchemeError> {
+    aos_core::reconstruct_erasures_with_strategy::<ParallelStrategy, C>(pp, shares)
 }
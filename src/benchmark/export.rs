@@ -0,0 +1,437 @@
+//! JSON export and import of benchmark results.
+
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::{
+    BenchmarkMetadata, BenchmarkParams, BenchmarkRun, BenchmarkStats, BenchmarkSummary, Decoding,
+    Implementation, SweepCurve, SweepPoint,
+};
+use crate::channel_model::ChannelModel;
+use crate::erasure_model::ErasureModel;
+use crate::types::{parse_decoder_type, CodeSource, F2PowElement};
+
+/// One configuration of the report, with its parameters flattened to strings.
+#[derive(Serialize, Deserialize)]
+struct ConfigurationReport {
+    implementation: Implementation,
+    decoder_type: String,
+    ldpc_rate: String,
+    ldpc_info_size: String,
+    /// Source file of an alist code; `ldpc_rate`/`ldpc_info_size` are then only labels.
+    /// Built-in codes are identified by the two labels alone.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    alist_path: Option<String>,
+    shares_to_remove: isize,
+    /// Participants dropped before shares are removed; 0 without participants.
+    #[serde(skip_serializing_if = "is_zero", default)]
+    participants_to_remove: usize,
+    /// Files written before erasure models existed removed uniform shares.
+    #[serde(default)]
+    erasure_model: ErasureModel,
+    /// Likewise, their shares arrived over a clean channel.
+    #[serde(default)]
+    channel: ChannelModel,
+    /// Files written before decodings were selectable used belief propagation.
+    #[serde(default)]
+    decoding: Decoding,
+    secret_hex: String,
+    secret_bits: usize,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    setup: Option<BenchmarkStats>,
+    deal: BenchmarkStats,
+    reconstruct: BenchmarkStats,
+    total: BenchmarkStats,
+    #[serde(default)]
+    runs: Vec<BenchmarkRun>,
+}
+
+fn is_zero(value: &usize) -> bool {
+    *value == 0
+}
+
+/// One curve of an erasure sweep, with the code flattened like in
+/// [`ConfigurationReport`]. The thresholds are written for readers of the file and
+/// recomputed from `points` on import.
+#[derive(Serialize, Deserialize)]
+struct SweepCurveReport {
+    implementation: Implementation,
+    decoder_type: String,
+    ldpc_rate: String,
+    ldpc_info_size: String,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    alist_path: Option<String>,
+    #[serde(default)]
+    erasure_model: ErasureModel,
+    #[serde(default)]
+    channel: ChannelModel,
+    #[serde(default)]
+    decoding: Decoding,
+    secret_bits: usize,
+    #[serde(skip_deserializing)]
+    threshold_50_percent: Option<f64>,
+    #[serde(skip_deserializing)]
+    threshold_99_percent: Option<f64>,
+    points: Vec<SweepPoint>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct BenchmarkReport {
+    metadata: BenchmarkMetadata,
+    configurations: Vec<ConfigurationReport>,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    sweep: Vec<SweepCurveReport>,
+}
+
+/// Rate label, size label and alist path of `code` as written to the report.
+fn code_labels(code: &CodeSource) -> (String, String, Option<String>) {
+    let rate = match code {
+        CodeSource::Builtin(builtin) => builtin.rate_label(),
+        CodeSource::ReedSolomon(rs) => rs.rate_label(),
+        CodeSource::Alist(_) => code.rate_label(),
+    };
+    let alist_path = match code {
+        CodeSource::Alist(path) => Some(path.display().to_string()),
+        CodeSource::Builtin(_) | CodeSource::ReedSolomon(_) => None,
+    };
+    (rate, code.size_label(), alist_path)
+}
+
+/// Inverse of [`code_labels`].
+fn code_from_labels(
+    rate: &str,
+    size: &str,
+    alist_path: Option<&str>,
+) -> Result<CodeSource, String> {
+    match alist_path {
+        Some(path) => Ok(CodeSource::Alist(path.into())),
+        None => CodeSource::from_labels(rate, size),
+    }
+}
+
+impl SweepCurveReport {
+    fn new(curve: &SweepCurve) -> Self {
+        let (ldpc_rate, ldpc_info_size, alist_path) = code_labels(&curve.code);
+        SweepCurveReport {
+            implementation: curve.implementation,
+            decoder_type: format!("{:?}", curve.decoder_type),
+            ldpc_rate,
+            ldpc_info_size,
+            alist_path,
+            erasure_model: curve.erasure_model,
+            channel: curve.channel,
+            decoding: curve.decoding,
+            secret_bits: curve.secret_bits,
+            threshold_50_percent: curve.threshold_50(),
+            threshold_99_percent: curve.threshold_99(),
+            points: curve.points.clone(),
+        }
+    }
+
+    fn curve(self) -> Result<SweepCurve, String> {
+        Ok(SweepCurve {
+            implementation: self.implementation,
+            decoder_type: parse_decoder_type(&self.decoder_type)?,
+            code: code_from_labels(
+                &self.ldpc_rate,
+                &self.ldpc_info_size,
+                self.alist_path.as_deref(),
+            )?,
+            erasure_model: self.erasure_model,
+            channel: self.channel,
+            decoding: self.decoding,
+            secret_bits: self.secret_bits,
+            points: self.points,
+        })
+    }
+}
+
+impl ConfigurationReport {
+    fn params(&self) -> Result<BenchmarkParams, String> {
+        Ok(BenchmarkParams {
+            implementation: self.implementation,
+            decoder_type: parse_decoder_type(&self.decoder_type)?,
+            code: code_from_labels(
+                &self.ldpc_rate,
+                &self.ldpc_info_size,
+                self.alist_path.as_deref(),
+            )?,
+            shares_to_remove: self.shares_to_remove,
+            participants_to_remove: self.participants_to_remove,
+            erasure_model: self.erasure_model,
+            channel: self.channel,
+            decoding: self.decoding,
+            secret: F2PowElement::from_hex(&self.secret_hex, self.secret_bits)?,
+        })
+    }
+}
+
+fn build_report(summary: &BenchmarkSummary) -> BenchmarkReport {
+    let mut configurations: Vec<ConfigurationReport> = summary
+        .total_stats
+        .iter()
+        .map(|(params, total)| {
+            let (ldpc_rate, ldpc_info_size, alist_path) = code_labels(&params.code);
+            ConfigurationReport {
+                implementation: params.implementation,
+                decoder_type: format!("{:?}", params.decoder_type),
+                ldpc_rate,
+                ldpc_info_size,
+                alist_path,
+                shares_to_remove: params.shares_to_remove,
+                participants_to_remove: params.participants_to_remove,
+                erasure_model: params.erasure_model,
+                channel: params.channel,
+                decoding: params.decoding,
+                secret_hex: params.secret.to_hex(),
+                secret_bits: params.secret.bit_len,
+                setup: summary.setup_stats.get(params).cloned(),
+                deal: summary.deal_stats.get(params).cloned().unwrap_or_default(),
+                reconstruct: summary
+                    .reconstruct_stats
+                    .get(params)
+                    .cloned()
+                    .unwrap_or_default(),
+                total: total.clone(),
+                runs: summary.runs.get(params).cloned().unwrap_or_default(),
+            }
+        })
+        .collect();
+
+    configurations.sort_by(|a, b| {
+        (
+            &a.ldpc_rate,
+            &a.ldpc_info_size,
+            &a.decoder_type,
+            a.participants_to_remove,
+            a.erasure_model.label(),
+            a.channel.label(),
+            a.decoding.label(),
+            a.shares_to_remove,
+        )
+            .cmp(&(
+                &b.ldpc_rate,
+                &b.ldpc_info_size,
+                &b.decoder_type,
+                b.participants_to_remove,
+                b.erasure_model.label(),
+                b.channel.label(),
+                b.decoding.label(),
+                b.shares_to_remove,
+            ))
+            .then_with(|| a.implementation.cmp(&b.implementation))
+    });
+
+    let mut sweep: Vec<SweepCurveReport> =
+        summary.sweep.iter().map(SweepCurveReport::new).collect();
+    sweep.sort_by(|a, b| {
+        (
+            &a.ldpc_rate,
+            &a.ldpc_info_size,
+            &a.decoder_type,
+            a.erasure_model.label(),
+            a.channel.label(),
+            a.decoding.label(),
+        )
+            .cmp(&(
+                &b.ldpc_rate,
+                &b.ldpc_info_size,
+                &b.decoder_type,
+                b.erasure_model.label(),
+                b.channel.label(),
+                b.decoding.label(),
+            ))
+    });
+
+    BenchmarkReport {
+        metadata: summary.metadata.clone(),
+        configurations,
+        sweep,
+    }
+}
+
+/// Resolve the output path: empty names get a timestamped name, `.json` is appended if missing.
+fn output_path(name: &str) -> PathBuf {
+    let name = name.trim();
+    if name.is_empty() {
+        PathBuf::from(format!(
+            "benchmark_results_{}.json",
+            Local::now().format("%Y%m%d_%H%M%S")
+        ))
+    } else if name.ends_with(".json") {
+        PathBuf::from(name)
+    } else {
+        PathBuf::from(format!("{}.json", name))
+    }
+}
+
+/// Write `summary` as pretty-printed JSON and return the path that was written.
+pub fn export_to_json(summary: &BenchmarkSummary, name: &str) -> Result<PathBuf, String> {
+    let path = output_path(name);
+    let json = serde_json::to_string_pretty(&build_report(summary))
+        .map_err(|err| format!("Failed to serialize results: {}", err))?;
+    fs::write(&path, json).map_err(|err| format!("Failed to write {}: {}", path.display(), err))?;
+    Ok(path)
+}
+
+fn summary_from_json(json: &str) -> Result<BenchmarkSummary, String> {
+    let report: BenchmarkReport =
+        serde_json::from_str(json).map_err(|err| format!("Invalid results file: {}", err))?;
+
+    let mut summary = BenchmarkSummary {
+        metadata: report.metadata,
+        ..Default::default()
+    };
+
+    for config in report.configurations {
+        let params = config.params()?;
+        if let Some(setup) = config.setup {
+            summary.setup_stats.insert(params.clone(), setup);
+        }
+        summary.deal_stats.insert(params.clone(), config.deal);
+        summary
+            .reconstruct_stats
+            .insert(params.clone(), config.reconstruct);
+        summary.total_stats.insert(params.clone(), config.total);
+        summary.runs.insert(params, config.runs);
+    }
+    summary.sweep = report
+        .sweep
+        .into_iter()
+        .map(SweepCurveReport::curve)
+        .collect::<Result<_, _>>()?;
+
+    Ok(summary)
+}
+
+/// Load a summary previously written by [`export_to_json`].
+pub fn import_from_json(path: &Path) -> Result<BenchmarkSummary, String> {
+    let json = fs::read_to_string(path)
+        .map_err(|err| format!("Failed to read {}: {}", path.display(), err))?;
+    summary_from_json(&json)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::code::family::BuiltinCode;
+    use ldpc_toolbox::codes::ccsds::{AR4JAInfoSize, AR4JARate};
+    use ldpc_toolbox::decoder::factory::DecoderImplementation;
+    use std::time::Duration;
+
+    fn sample_summary() -> BenchmarkSummary {
+        let params = BenchmarkParams {
+            implementation: Implementation::Parallel,
+            decoder_type: DecoderImplementation::Aminstarf32,
+            code: CodeSource::Builtin(BuiltinCode::Ar4ja {
+                rate: AR4JARate::R4_5,
+                info_size: AR4JAInfoSize::K1024,
+            }),
+            shares_to_remove: -10,
+            participants_to_remove: 0,
+            erasure_model: ErasureModel::Groups(8),
+            channel: ChannelModel::Bsc(0.001),
+            decoding: Decoding::MaximumLikelihood,
+            secret: F2PowElement::from_hex("2a", 128).unwrap(),
+        };
+        let stats = BenchmarkStats {
+            avg: Duration::from_millis(12),
+            min: Duration::from_millis(10),
+            max: Duration::from_millis(14),
+            median: Duration::from_millis(12),
+            sample_count: 3,
+            success_rate: 1.0,
+            ..Default::default()
+        };
+
+        let mut summary = BenchmarkSummary::default();
+        summary.deal_stats.insert(params.clone(), stats.clone());
+        summary
+            .reconstruct_stats
+            .insert(params.clone(), stats.clone());
+        summary.total_stats.insert(params, stats);
+        summary
+    }
+
+    #[test]
+    fn test_output_path_appends_extension() {
+        assert_eq!(output_path("results"), PathBuf::from("results.json"));
+        assert_eq!(output_path("results.json"), PathBuf::from("results.json"));
+        assert!(output_path("")
+            .to_string_lossy()
+            .starts_with("benchmark_results_"));
+    }
+
+    #[test]
+    fn test_json_roundtrip_preserves_entries() {
+        let summary = sample_summary();
+        let json = serde_json::to_string(&build_report(&summary)).unwrap();
+        let imported = summary_from_json(&json).unwrap();
+
+        let (params, stats) = imported.total_stats.iter().next().unwrap();
+        assert_eq!(imported.total_stats.len(), 1);
+        assert_eq!(params.implementation, Implementation::Parallel);
+        assert_eq!(params.shares_to_remove, -10);
+        assert_eq!(params.erasure_model, ErasureModel::Groups(8));
+        assert_eq!(params.channel, ChannelModel::Bsc(0.001));
+        assert_eq!(params.decoding, Decoding::MaximumLikelihood);
+        assert_eq!(params.secret.to_hex(), "0000000000000000000000000000002a");
+        assert_eq!(stats.avg, Duration::from_millis(12));
+        assert!(imported.setup_stats.is_empty());
+    }
+
+    #[test]
+    fn test_json_roundtrip_preserves_sweep() {
+        let mut summary = BenchmarkSummary::default();
+        summary.sweep.push(SweepCurve {
+            implementation: Implementation::Parallel,
+            decoder_type: DecoderImplementation::Aminstarf32,
+            code: CodeSource::Builtin(BuiltinCode::Ar4ja {
+                rate: AR4JARate::R4_5,
+                info_size: AR4JAInfoSize::K1024,
+            }),
+            erasure_model: ErasureModel::Adversarial,
+            channel: ChannelModel::Awgn(2.5),
+            decoding: Decoding::BeliefPropagation,
+            secret_bits: 128,
+            points: vec![
+                SweepPoint {
+                    removed_percent: 0.0,
+                    trials: 10,
+                    ..Default::default()
+                },
+                SweepPoint {
+                    removed_percent: 5.0,
+                    removed: 70,
+                    trials: 10,
+                    failures: 6,
+                    frame_error_rate: 0.6,
+                    ..Default::default()
+                },
+            ],
+        });
+        let json = serde_json::to_string(&build_report(&summary)).unwrap();
+        assert!(json.contains("\"threshold_50_percent\":0.0"));
+        assert!(json.contains("\"erasure_model\":\"adversarial\""));
+        let imported = summary_from_json(&json).unwrap();
+
+        let curve = &imported.sweep[0];
+        assert_eq!(curve.points.len(), 2);
+        assert_eq!(curve.points[1].removed, 70);
+        assert_eq!(curve.erasure_model, ErasureModel::Adversarial);
+        assert_eq!(curve.channel, ChannelModel::Awgn(2.5));
+        assert_eq!(curve.threshold_50(), Some(0.0));
+        assert!(imported.total_stats.is_empty());
+    }
+
+    #[test]
+    fn test_import_rejects_unknown_decoder() {
+        let summary = sample_summary();
+        let json = serde_json::to_string(&build_report(&summary))
+            .unwrap()
+            .replace("Aminstarf32", "NoSuchDecoder");
+        assert!(summary_from_json(&json).is_err());
+    }
+}
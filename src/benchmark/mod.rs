@@ -0,0 +1,295 @@
+//! Benchmark orchestration, statistics aggregation and JSON import/export.
+
+mod export;
+mod report;
+mod runner;
+mod stats;
+mod sweep;
+
+use ldpc_toolbox::decoder::factory::DecoderImplementation;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::time::Duration;
+
+use crate::channel_model::ChannelModel;
+use crate::erasure_model::ErasureModel;
+use crate::types::{
+    duration_as_ms, serialize_duration_as_ms, CodeSource, DecodingStats, F2PowElement,
+    ParallelMetrics, ThroughputMetrics,
+};
+
+pub use export::import_from_json;
+pub use runner::{run_comprehensive_benchmark, run_comprehensive_benchmark_for_ui};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum Implementation {
+    Sequential,
+    Parallel,
+    /// Sequential, with all rows encoded together by `aos_bitsliced`; LDPC codes only.
+    Bitsliced,
+}
+
+impl Implementation {
+    /// Inverse of the lowercase `Display` name, as given to `--implementations=`.
+    pub fn parse(s: &str) -> Result<Self, String> {
+        match s {
+            "sequential" => Ok(Implementation::Sequential),
+            "parallel" => Ok(Implementation::Parallel),
+            "bitsliced" => Ok(Implementation::Bitsliced),
+            _ => Err(format!("Unknown implementation: {}", s)),
+        }
+    }
+}
+
+impl Display for Implementation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Implementation::Sequential => write!(f, "Sequential"),
+            Implementation::Parallel => write!(f, "Parallel"),
+            Implementation::Bitsliced => write!(f, "Bitsliced"),
+        }
+    }
+}
+
+/// How a benchmark run reconstructs.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Decoding {
+    /// Belief propagation, row by row, with the configured decoder.
+    #[default]
+    BeliefPropagation,
+    /// Peeling and Gaussian elimination on `H` for all rows at once
+    /// (`reconstruct_erasures`); the decoder type is not used.
+    MaximumLikelihood,
+}
+
+impl Decoding {
+    /// The `--decoding=` value naming this method.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Decoding::BeliefPropagation => "bp",
+            Decoding::MaximumLikelihood => "ml",
+        }
+    }
+
+    /// Inverse of [`Self::label`].
+    pub fn parse(s: &str) -> Result<Self, String> {
+        match s {
+            "bp" => Ok(Decoding::BeliefPropagation),
+            "ml" => Ok(Decoding::MaximumLikelihood),
+            _ => Err(format!("Unknown decoding: {}", s)),
+        }
+    }
+}
+
+impl Display for Decoding {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.label())
+    }
+}
+
+/// One point of the benchmark matrix.
+///
+/// Decoder settings that are shared by the whole benchmark (iteration limit,
+/// LLR magnitude) live in [`BenchmarkMetadata`] instead.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BenchmarkParams {
+    pub implementation: Implementation,
+    pub decoder_type: DecoderImplementation,
+    pub code: CodeSource,
+    pub shares_to_remove: isize,
+    /// Participants dropped before `shares_to_remove` shares are removed from the
+    /// rest; 0 unless the benchmark groups shares into participants.
+    pub participants_to_remove: usize,
+    /// Pattern of the `shares_to_remove` shares removed.
+    pub erasure_model: ErasureModel,
+    /// Noise on the shares left after removal.
+    pub channel: ChannelModel,
+    pub decoding: Decoding,
+    pub secret: F2PowElement,
+}
+
+/// Aggregated statistics of one phase (e.g. "Encoding phase") over all runs.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PhaseStats {
+    #[serde(with = "duration_as_ms")]
+    pub avg_duration: Duration,
+    #[serde(with = "duration_as_ms")]
+    pub min_duration: Duration,
+    #[serde(with = "duration_as_ms")]
+    pub max_duration: Duration,
+    pub avg_percentage: f64,
+}
+
+/// Timing statistics of one operation for one [`BenchmarkParams`] entry.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BenchmarkStats {
+    #[serde(with = "duration_as_ms")]
+    pub avg: Duration,
+    #[serde(with = "duration_as_ms")]
+    pub min: Duration,
+    #[serde(with = "duration_as_ms")]
+    pub max: Duration,
+    #[serde(with = "duration_as_ms")]
+    pub median: Duration,
+    #[serde(with = "duration_as_ms")]
+    pub std_dev: Duration,
+    pub sample_count: usize,
+    pub success_rate: f64,
+    /// Share of runs in which every row decoded but the secret was wrong.
+    #[serde(default)]
+    pub wrong_secret_rate: f64,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub phase_metrics: Option<HashMap<String, PhaseStats>>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub decoding_stats: Option<DecodingStats>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub throughput: Option<ThroughputMetrics>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub parallel_metrics: Option<ParallelMetrics>,
+}
+
+/// Measurements of a single benchmark run.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BenchmarkRun {
+    /// Zero when the run reused cached setup parameters.
+    #[serde(with = "duration_as_ms", default)]
+    pub setup_time: Duration,
+    #[serde(with = "duration_as_ms")]
+    pub deal_time: Duration,
+    #[serde(with = "duration_as_ms")]
+    pub reconstruct_time: Duration,
+    #[serde(with = "duration_as_ms")]
+    pub total_time: Duration,
+    pub shares_dealt: usize,
+    pub shares_used: usize,
+    /// Participants whose bundles were used, when shares are grouped into participants.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub participants_used: Option<usize>,
+    pub success: bool,
+    /// Every row decoded, whether or not to the right secret.
+    #[serde(default)]
+    pub decoded: bool,
+    /// Decoded, but to a different secret than was dealt.
+    #[serde(default)]
+    pub wrong_secret: bool,
+    /// Decoder iterations per row.
+    #[serde(default)]
+    pub avg_iterations: f64,
+}
+
+/// Removal fractions and trial count of an erasure sweep.
+///
+/// A sweep replaces the fixed `shares_to_remove` list: every code and decoder is
+/// tried at `0, step_percent, 2 * step_percent, ..., max_percent` percent of the
+/// shares removed, with `trials` random removal patterns at each step.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SweepSettings {
+    pub max_percent: f64,
+    pub step_percent: f64,
+    pub trials: usize,
+}
+
+impl Default for SweepSettings {
+    fn default() -> Self {
+        Self {
+            max_percent: 30.0,
+            step_percent: 1.0,
+            trials: 100,
+        }
+    }
+}
+
+/// Outcome of the trials at one removal fraction of a sweep.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SweepPoint {
+    pub removed_percent: f64,
+    /// Shares removed in every trial of this step.
+    pub removed: usize,
+    pub trials: usize,
+    /// Trials that did not reconstruct the secret.
+    pub failures: usize,
+    /// `failures / trials`.
+    pub frame_error_rate: f64,
+    /// Failed trials in which every row decoded, to a wrong secret.
+    #[serde(default)]
+    pub wrong_secrets: usize,
+    /// `wrong_secrets / trials`.
+    #[serde(default)]
+    pub wrong_secret_rate: f64,
+    /// Failed rows over all rows decoded in the step.
+    pub row_failure_rate: f64,
+    /// Decoder iterations per row, averaged over the step.
+    pub avg_iterations: f64,
+}
+
+/// Failure-rate curve of one code and decoder.
+#[derive(Debug, Clone)]
+pub struct SweepCurve {
+    pub implementation: Implementation,
+    pub decoder_type: DecoderImplementation,
+    pub code: CodeSource,
+    pub erasure_model: ErasureModel,
+    pub channel: ChannelModel,
+    pub decoding: Decoding,
+    pub secret_bits: usize,
+    pub points: Vec<SweepPoint>,
+}
+
+/// Settings shared by every entry of a benchmark.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BenchmarkMetadata {
+    pub timestamp: String,
+    pub runs_per_config: usize,
+    pub warmup_runs: usize,
+    pub cache_setup: bool,
+    pub max_iterations: usize,
+    pub llr_value: f64,
+    pub removal_seed: Option<u64>,
+    /// Number of participants the shares were grouped into (interleaved layout).
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub participants: Option<usize>,
+    /// Present when the benchmark was an erasure sweep.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub sweep: Option<SweepSettings>,
+    pub thread_count: usize,
+    pub cancelled: bool,
+    #[serde(serialize_with = "serialize_duration_as_ms", skip_deserializing)]
+    pub total_duration: Duration,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct BenchmarkSummary {
+    pub metadata: BenchmarkMetadata,
+    pub setup_stats: HashMap<BenchmarkParams, BenchmarkStats>,
+    pub deal_stats: HashMap<BenchmarkParams, BenchmarkStats>,
+    pub reconstruct_stats: HashMap<BenchmarkParams, BenchmarkStats>,
+    pub total_stats: HashMap<BenchmarkParams, BenchmarkStats>,
+    pub runs: HashMap<BenchmarkParams, Vec<BenchmarkRun>>,
+    /// Curves of an erasure sweep; empty for the fixed benchmark matrix.
+    pub sweep: Vec<SweepCurve>,
+}
+
+/// Benchmark settings: the parsed `benchmark` command line, or the Configure tab
+/// of the GUI through `BenchmarkConfig::cli_config`.
+pub struct CliConfig {
+    pub shares_to_remove_values: Vec<isize>,
+    pub decoder_types: Vec<DecoderImplementation>,
+    pub codes: Vec<CodeSource>,
+    pub implementations: Vec<Implementation>,
+    pub runs_per_config: usize,
+    pub warmup_runs: usize,
+    pub cache_setup: bool,
+    pub show_detail: bool,
+    pub output_file: Option<String>,
+    pub secret: F2PowElement,
+    pub max_iterations: usize,
+    pub llr_value: f64,
+    pub removal_seed: Option<u64>,
+    pub participants: Option<usize>,
+    pub participants_to_remove_values: Vec<usize>,
+    pub sweep: Option<SweepSettings>,
+    pub erasure_models: Vec<ErasureModel>,
+    pub channels: Vec<ChannelModel>,
+    pub decodings: Vec<Decoding>,
+}
@@ -0,0 +1,276 @@
+//! Plain-text rendering of a [`BenchmarkSummary`] for the terminal and the log console.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use super::{
+    BenchmarkParams, BenchmarkStats, BenchmarkSummary, Decoding, Implementation, PhaseStats,
+};
+use crate::channel_model::ChannelModel;
+use crate::erasure_model::ErasureModel;
+
+type SortKey = (
+    String,
+    String,
+    String,
+    usize,
+    String,
+    String,
+    &'static str,
+    isize,
+    Implementation,
+);
+
+fn sort_key(params: &BenchmarkParams) -> SortKey {
+    (
+        params.code.rate_label(),
+        params.code.size_label(),
+        format!("{:?}", params.decoder_type),
+        params.participants_to_remove,
+        params.erasure_model.label(),
+        params.channel.label(),
+        params.decoding.label(),
+        params.shares_to_remove,
+        params.implementation,
+    )
+}
+
+fn sorted_entries(
+    stats: &HashMap<BenchmarkParams, BenchmarkStats>,
+) -> Vec<(&BenchmarkParams, &BenchmarkStats)> {
+    let mut entries: Vec<_> = stats.iter().collect();
+    entries.sort_by_key(|(params, _)| sort_key(params));
+    entries
+}
+
+fn ms(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+/// One line per configuration with total-time statistics.
+pub(crate) fn summary_lines(summary: &BenchmarkSummary) -> Vec<String> {
+    if summary.total_stats.is_empty() && !summary.sweep.is_empty() {
+        return Vec::new();
+    }
+    let mut lines = vec![format!(
+        "{:<10} {:>5} {:<7} {:<5} {:<24} {:>7} {:>11} {:>11} {:>11} {:>11} {:>9} {:>8}",
+        "Impl",
+        "ell",
+        "Size",
+        "Rate",
+        "Decoder",
+        "Remove",
+        "Avg (ms)",
+        "Min (ms)",
+        "Max (ms)",
+        "Median",
+        "StdDev",
+        "Success"
+    )];
+
+    for (params, stats) in sorted_entries(&summary.total_stats) {
+        let mut line = format!(
+            "{:<10} {:>5} {:<7} {:<5} {:<24} {:>7} {:>11.3} {:>11.3} {:>11.3} {:>11.3} {:>9.3} {:>7.0}%",
+            params.implementation.to_string(),
+            params.secret.bit_len,
+            params.code.size_label(),
+            params.code.rate_label(),
+            format!("{:?}", params.decoder_type),
+            params.shares_to_remove,
+            ms(stats.avg),
+            ms(stats.min),
+            ms(stats.max),
+            ms(stats.median),
+            ms(stats.std_dev),
+            stats.success_rate * 100.0
+        );
+        if params.participants_to_remove > 0 {
+            line.push_str(&format!(
+                "  participants -{}",
+                params.participants_to_remove
+            ));
+        }
+        if params.erasure_model != ErasureModel::Uniform {
+            line.push_str(&format!("  erasure {}", params.erasure_model));
+        }
+        if params.channel != ChannelModel::Clean {
+            line.push_str(&format!(
+                "  channel {} wrong {:.0}%",
+                params.channel,
+                stats.wrong_secret_rate * 100.0
+            ));
+        }
+        if params.decoding != Decoding::BeliefPropagation {
+            line.push_str(&format!("  decoding {}", params.decoding));
+        }
+        if let Some(speedup) = stats.parallel_metrics.as_ref().and_then(|p| p.speedup) {
+            line.push_str(&format!("  speedup {:.2}x", speedup));
+        }
+        lines.push(line);
+    }
+
+    lines
+}
+
+fn threshold_label(threshold: Option<f64>) -> String {
+    threshold.map_or_else(
+        || String::from("none"),
+        |percent| format!("{:.1}%", percent),
+    )
+}
+
+/// One table per sweep curve, followed by its 50% and 99% success thresholds.
+pub(crate) fn sweep_lines(summary: &BenchmarkSummary) -> Vec<String> {
+    let mut curves: Vec<_> = summary.sweep.iter().collect();
+    curves.sort_by_key(|curve| {
+        (
+            curve.code.rate_label(),
+            curve.code.size_label(),
+            format!("{:?}", curve.decoder_type),
+            curve.erasure_model.label(),
+            curve.channel.label(),
+            curve.decoding.label(),
+        )
+    });
+
+    let mut lines = Vec::new();
+    for curve in curves {
+        lines.push(format!(
+            "Erasure sweep: {} | ell={} | {} | {} | {:?} | erasure={} | channel={} | decoding={}",
+            curve.implementation,
+            curve.secret_bits,
+            curve.code.size_label(),
+            curve.code.rate_label(),
+            curve.decoder_type,
+            curve.erasure_model,
+            curve.channel,
+            curve.decoding
+        ));
+        lines.push(format!(
+            "  {:>9} {:>8} {:>7} {:>9} {:>9} {:>10} {:>10}",
+            "Removed %", "Removed", "Trials", "FER", "Wrong", "Row fail", "Avg iter"
+        ));
+        for point in &curve.points {
+            lines.push(format!(
+                "  {:>9.1} {:>8} {:>7} {:>9.4} {:>9.4} {:>10.4} {:>10.2}",
+                point.removed_percent,
+                point.removed,
+                point.trials,
+                point.frame_error_rate,
+                point.wrong_secret_rate,
+                point.row_failure_rate,
+                point.avg_iterations
+            ));
+        }
+        lines.push(format!(
+            "  Success >= 50% up to {} removed, >= 99% up to {} removed",
+            threshold_label(curve.threshold_50()),
+            threshold_label(curve.threshold_99())
+        ));
+    }
+    lines
+}
+
+fn phase_lines(lines: &mut Vec<String>, title: &str, phases: &HashMap<String, PhaseStats>) {
+    lines.push(format!("  {}:", title));
+    let mut phases: Vec<_> = phases.iter().collect();
+    phases.sort_by(|(_, a), (_, b)| b.avg_percentage.total_cmp(&a.avg_percentage));
+    for (name, phase) in phases {
+        lines.push(format!(
+            "    {:<28} avg {:>10.3} ms  min {:>10.3} ms  max {:>10.3} ms  {:>5.1}%",
+            name,
+            ms(phase.avg_duration),
+            ms(phase.min_duration),
+            ms(phase.max_duration),
+            phase.avg_percentage
+        ));
+    }
+}
+
+/// Phase breakdowns, decoding statistics and throughput per configuration.
+pub(crate) fn detail_lines(summary: &BenchmarkSummary) -> Vec<String> {
+    let mut lines = Vec::new();
+
+    for (params, total) in sorted_entries(&summary.total_stats) {
+        let mut header = format!(
+            "{} | ell={} | {} | {} | {:?} | remove={}",
+            params.implementation,
+            params.secret.bit_len,
+            params.code.size_label(),
+            params.code.rate_label(),
+            params.decoder_type,
+            params.shares_to_remove
+        );
+        if params.participants_to_remove > 0 {
+            header.push_str(&format!(
+                " | remove_participants={}",
+                params.participants_to_remove
+            ));
+        }
+        if params.erasure_model != ErasureModel::Uniform {
+            header.push_str(&format!(" | erasure={}", params.erasure_model));
+        }
+        if params.channel != ChannelModel::Clean {
+            header.push_str(&format!(" | channel={}", params.channel));
+        }
+        if params.decoding != Decoding::BeliefPropagation {
+            header.push_str(&format!(" | decoding={}", params.decoding));
+        }
+        lines.push(header);
+
+        if let Some(setup) = summary.setup_stats.get(params) {
+            lines.push(format!(
+                "  Setup: avg {:.3} ms over {} samples",
+                ms(setup.avg),
+                setup.sample_count
+            ));
+        }
+
+        if let Some(deal) = summary.deal_stats.get(params) {
+            if let Some(phases) = &deal.phase_metrics {
+                phase_lines(&mut lines, "Deal phases", phases);
+            }
+            if let Some(throughput) = &deal.throughput {
+                lines.push(format!(
+                    "  Deal throughput: {:.1} shares/s, {:.1} bits/s",
+                    throughput.shares_per_second, throughput.bits_per_second
+                ));
+            }
+        }
+
+        if let Some(reconstruct) = summary.reconstruct_stats.get(params) {
+            if let Some(phases) = &reconstruct.phase_metrics {
+                phase_lines(&mut lines, "Reconstruct phases", phases);
+            }
+            if let Some(decoding) = &reconstruct.decoding_stats {
+                lines.push(format!(
+                    "  Decoding: {}/{} rows ok ({:.1}%), avg iterations {:.2}, max-iteration hits {}",
+                    decoding.successful_rows,
+                    decoding.total_rows,
+                    decoding.success_rate() * 100.0,
+                    decoding.avg_iterations,
+                    decoding.max_iterations_hit
+                ));
+            }
+            if reconstruct.wrong_secret_rate > 0.0 {
+                lines.push(format!(
+                    "  Wrong secret: {:.1}% of runs decoded to a different secret",
+                    reconstruct.wrong_secret_rate * 100.0
+                ));
+            }
+        }
+
+        if let Some(parallel) = &total.parallel_metrics {
+            if let (Some(speedup), Some(efficiency)) = (parallel.speedup, parallel.efficiency) {
+                lines.push(format!(
+                    "  Parallel: {} threads, speedup {:.2}x, efficiency {:.1}%",
+                    parallel.thread_count,
+                    speedup,
+                    efficiency * 100.0
+                ));
+            }
+        }
+    }
+
+    lines
+}
@@ -0,0 +1,705 @@
+//! Benchmark matrix execution for the CLI and the GUI.
+
+use chrono::Local;
+use indicatif::{ProgressBar, ProgressStyle};
+use ldpc_toolbox::decoder::factory::DecoderImplementation;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use super::export::export_to_json;
+use super::report::sweep_lines;
+use super::report::{detail_lines, summary_lines};
+use super::stats::{attach_parallel_metrics, build_operation_stats, ConfigSamples};
+use super::sweep::run_sweep;
+use super::{
+    BenchmarkMetadata, BenchmarkParams, BenchmarkRun, BenchmarkSummary, CliConfig, Decoding,
+    Implementation, SweepSettings,
+};
+use crate::access::AccessLayout;
+use crate::channel_model::{ChannelModel, Received};
+use crate::code::ldpc_impl::LdpcCode;
+use crate::code::reed_solomon::ReedSolomonCode;
+use crate::code::AdditiveCode;
+use crate::erasure_model::ErasureModel;
+use crate::error::SchemeError;
+use crate::types::{
+    CodeInitParams, CodeSource, DealMetrics, F2PowElement, ReconstructMetrics, SecretParams, Shares,
+};
+use crate::utils::remove_random_participants;
+use crate::{aos, aos_bitsliced, aos_parallel, log_error, log_info, log_success, log_warning};
+
+type SetupKey = (CodeSource, DecoderImplementation);
+
+/// Mixed into a removal seed to seed the channel, so the channel noise does not
+/// replay the random stream of the erasure shuffle.
+const CHANNEL_SALT: u64 = 0x9e37_79b9_7f4a_7c15;
+
+/// Seed of the channel for a run whose erasures are seeded with `removal_seed`.
+pub(super) fn channel_seed(removal_seed: Option<u64>) -> Option<u64> {
+    removal_seed.map(|seed| seed ^ CHANNEL_SALT)
+}
+
+/// Benchmark matrix and shared decoder settings.
+pub(super) struct BenchmarkPlan<'a> {
+    shares_to_remove_values: &'a [isize],
+    decoder_types: &'a [DecoderImplementation],
+    codes: &'a [CodeSource],
+    pub(super) implementations: &'a [Implementation],
+    runs_per_config: usize,
+    warmup_runs: usize,
+    cache_setup: bool,
+    pub(super) secret: &'a F2PowElement,
+    pub(super) max_iterations: usize,
+    pub(super) llr_value: f64,
+    pub(super) removal_seed: Option<u64>,
+    participants: Option<usize>,
+    participants_to_remove_values: &'a [usize],
+    /// Run an erasure sweep instead of the matrix.
+    sweep: Option<SweepSettings>,
+    erasure_models: &'a [ErasureModel],
+    channels: &'a [ChannelModel],
+    decodings: &'a [Decoding],
+}
+
+impl<'a> BenchmarkPlan<'a> {
+    pub(super) fn new(cfg: &'a CliConfig) -> Self {
+        BenchmarkPlan {
+            shares_to_remove_values: &cfg.shares_to_remove_values,
+            decoder_types: &cfg.decoder_types,
+            codes: &cfg.codes,
+            implementations: &cfg.implementations,
+            runs_per_config: cfg.runs_per_config,
+            warmup_runs: cfg.warmup_runs,
+            cache_setup: cfg.cache_setup,
+            secret: &cfg.secret,
+            max_iterations: cfg.max_iterations,
+            llr_value: cfg.llr_value,
+            removal_seed: cfg.removal_seed,
+            participants: cfg.participants,
+            participants_to_remove_values: &cfg.participants_to_remove_values,
+            sweep: cfg.sweep,
+            erasure_models: &cfg.erasure_models,
+            channels: &cfg.channels,
+            decodings: &cfg.decodings,
+        }
+    }
+
+    /// Every code with every decoder, skipping codes the secret does not fit. Reed–Solomon
+    /// codes ignore the decoder, so they run once, with the first one.
+    pub(super) fn code_decoder_pairs(&self) -> Vec<(CodeSource, DecoderImplementation)> {
+        let mut pairs = Vec::new();
+        for code in self.codes {
+            if let Some((k, _)) = code.dimensions().filter(|&(k, _)| self.secret.bit_len > k) {
+                log_warning!(
+                    "Skipping {}: secret bits ({}) exceed information length ({})",
+                    code.size_label(),
+                    self.secret.bit_len,
+                    k
+                );
+                continue;
+            }
+            let decoder_types = match code {
+                CodeSource::ReedSolomon(_) => &self.decoder_types[..1],
+                _ => self.decoder_types,
+            };
+            for &decoder_type in decoder_types {
+                pairs.push((code.clone(), decoder_type));
+            }
+        }
+        pairs
+    }
+
+    /// Participant removal only varies when shares are grouped into participants.
+    fn configurations(&self) -> Vec<BenchmarkParams> {
+        let participants_to_remove_values = match self.participants {
+            Some(_) if !self.participants_to_remove_values.is_empty() => {
+                self.participants_to_remove_values
+            }
+            _ => &[0],
+        };
+        let mut configs = Vec::new();
+        for (code, decoder_type) in self.code_decoder_pairs() {
+            for &participants_to_remove in participants_to_remove_values {
+                for &erasure_model in self.erasure_models() {
+                    for &channel in self.channels() {
+                        for &decoding in self.decodings() {
+                            if !self.decoding_applies(decoding, &code, decoder_type, channel) {
+                                continue;
+                            }
+                            for &shares_to_remove in self.shares_to_remove_values {
+                                for &implementation in self.implementations {
+                                    if implementation == Implementation::Bitsliced
+                                        && matches!(code, CodeSource::ReedSolomon(_))
+                                    {
+                                        continue;
+                                    }
+                                    configs.push(BenchmarkParams {
+                                        implementation,
+                                        decoder_type,
+                                        code: code.clone(),
+                                        shares_to_remove,
+                                        participants_to_remove,
+                                        erasure_model,
+                                        channel,
+                                        decoding,
+                                        secret: self.secret.clone(),
+                                    });
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        configs
+    }
+
+    /// The selected erasure models, uniform if none is.
+    pub(super) fn erasure_models(&self) -> &[ErasureModel] {
+        if self.erasure_models.is_empty() {
+            &[ErasureModel::Uniform]
+        } else {
+            self.erasure_models
+        }
+    }
+
+    /// The selected channels, clean if none is.
+    pub(super) fn channels(&self) -> &[ChannelModel] {
+        if self.channels.is_empty() {
+            &[ChannelModel::Clean]
+        } else {
+            self.channels
+        }
+    }
+
+    /// The selected decodings, belief propagation if none is.
+    pub(super) fn decodings(&self) -> &[Decoding] {
+        if self.decodings.is_empty() {
+            &[Decoding::BeliefPropagation]
+        } else {
+            self.decodings
+        }
+    }
+
+    /// Maximum-likelihood decoding needs a sparse `H` and hard shares, and does not
+    /// use the decoder, so it runs with the first one only.
+    pub(super) fn decoding_applies(
+        &self,
+        decoding: Decoding,
+        code: &CodeSource,
+        decoder_type: DecoderImplementation,
+        channel: ChannelModel,
+    ) -> bool {
+        decoding == Decoding::BeliefPropagation
+            || (!matches!(code, CodeSource::ReedSolomon(_))
+                && !channel.is_soft()
+                && self.decoder_types.first() == Some(&decoder_type))
+    }
+
+    fn code_params(&self, params: &BenchmarkParams) -> CodeInitParams {
+        params.code.init_params(
+            params.decoder_type,
+            self.max_iterations,
+            self.llr_value,
+            self.secret.bit_len,
+        )
+    }
+
+    fn metadata(&self, cancelled: bool, total_duration: Duration) -> BenchmarkMetadata {
+        BenchmarkMetadata {
+            timestamp: Local::now().to_rfc3339(),
+            runs_per_config: self.runs_per_config,
+            warmup_runs: self.warmup_runs,
+            cache_setup: self.cache_setup,
+            max_iterations: self.max_iterations,
+            llr_value: self.llr_value,
+            removal_seed: self.removal_seed,
+            participants: self.participants,
+            sweep: self.sweep,
+            thread_count: rayon::current_num_threads(),
+            cancelled,
+            total_duration,
+        }
+    }
+
+    fn removal_seed_for_run(&self, run_index: usize) -> Option<u64> {
+        self.removal_seed
+            .map(|seed| seed.wrapping_add(run_index as u64))
+    }
+}
+
+/// Public parameters of any code the benchmark can run.
+pub(super) enum BenchmarkSetup {
+    Ldpc(Box<SecretParams<LdpcCode>>),
+    ReedSolomon(SecretParams<ReedSolomonCode>),
+}
+
+pub(super) fn run_setup(
+    implementation: Implementation,
+    params: CodeInitParams,
+) -> Result<BenchmarkSetup, SchemeError> {
+    if let Some(CodeSource::ReedSolomon(_)) = params.code_source {
+        setup_with(implementation, params).map(BenchmarkSetup::ReedSolomon)
+    } else {
+        setup_with(implementation, params).map(|pp| BenchmarkSetup::Ldpc(Box::new(pp)))
+    }
+}
+
+fn setup_with<C: AdditiveCode>(
+    implementation: Implementation,
+    params: CodeInitParams,
+) -> Result<SecretParams<C>, SchemeError> {
+    match implementation {
+        Implementation::Sequential | Implementation::Bitsliced => aos::setup(params),
+        Implementation::Parallel => aos_parallel::setup(params),
+    }
+}
+
+/// A code the benchmark runs, with the backends only some codes support.
+pub(super) trait BenchmarkCode: AdditiveCode + Sync + Sized {
+    /// Deal through `aos_bitsliced`, which only encodes LDPC codes.
+    fn deal_bitsliced(
+        pp: &SecretParams<Self>,
+        secret: &F2PowElement,
+    ) -> Result<Shares, SchemeError>;
+}
+
+impl BenchmarkCode for LdpcCode {
+    fn deal_bitsliced(
+        pp: &SecretParams<Self>,
+        secret: &F2PowElement,
+    ) -> Result<Shares, SchemeError> {
+        aos_bitsliced::deal(pp, secret)
+    }
+}
+
+impl BenchmarkCode for ReedSolomonCode {
+    fn deal_bitsliced(
+        _pp: &SecretParams<Self>,
+        _secret: &F2PowElement,
+    ) -> Result<Shares, SchemeError> {
+        Err(SchemeError::InvalidCodeParameters(String::from(
+            "bitsliced encoding needs an LDPC code",
+        )))
+    }
+}
+
+pub(super) fn deal_with<C: BenchmarkCode>(
+    implementation: Implementation,
+    pp: &SecretParams<C>,
+    secret: &F2PowElement,
+) -> Result<Shares, SchemeError> {
+    match implementation {
+        Implementation::Sequential => aos::deal(pp, secret),
+        Implementation::Parallel => aos_parallel::deal(pp, secret),
+        Implementation::Bitsliced => C::deal_bitsliced(pp, secret),
+    }
+}
+
+struct RunOutcome {
+    run: BenchmarkRun,
+    deal_metrics: Option<DealMetrics>,
+    reconstruct_metrics: Option<ReconstructMetrics>,
+}
+
+/// One deal and reconstruct. With `participants` the shares are first grouped into
+/// that many interleaved participants, of which `params.participants_to_remove`
+/// drop out before `params.shares_to_remove` shares are removed from the rest by
+/// `params.erasure_model`. The remaining shares then pass through `params.channel`,
+/// outside the timed reconstruct.
+fn execute_run<C: BenchmarkCode>(
+    params: &BenchmarkParams,
+    pp: &SecretParams<C>,
+    participants: Option<usize>,
+    removal_seed: Option<u64>,
+) -> Result<RunOutcome, SchemeError> {
+    let implementation = params.implementation;
+    let secret = &params.secret;
+    let deal_start = Instant::now();
+    let mut shares = deal_with(implementation, pp, secret)?;
+    let deal_time = deal_start.elapsed();
+    let shares_dealt = shares.shares.len();
+    let deal_metrics = shares.metrics.take();
+
+    let mut participants_used = None;
+    if let Some(count) = participants {
+        let mut bundled = AccessLayout::numbered(count, pp.code.output_length).bundle(shares);
+        remove_random_participants(
+            &mut bundled.bundles,
+            params.participants_to_remove,
+            removal_seed,
+        );
+        participants_used = Some(bundled.bundles.len());
+        shares = bundled.to_shares();
+    }
+    params.erasure_model.erase(
+        &pp.code.code_impl,
+        &mut shares.shares,
+        params.shares_to_remove,
+        removal_seed,
+    );
+    let shares_used = shares.shares.len();
+
+    let received = params.channel.transmit(
+        shares,
+        pp.code.output_length as usize,
+        channel_seed(removal_seed),
+    );
+    let reconstruct_start = Instant::now();
+    let (reconstructed, reconstruct_metrics) =
+        reconstruct_received(implementation, params.decoding, pp, &received)?;
+    let reconstruct_time = reconstruct_start.elapsed();
+    let success = reconstructed.as_ref() == Some(secret);
+
+    Ok(RunOutcome {
+        run: BenchmarkRun {
+            setup_time: Duration::ZERO,
+            deal_time,
+            reconstruct_time,
+            total_time: deal_time + reconstruct_time,
+            shares_dealt,
+            shares_used,
+            participants_used,
+            success,
+            decoded: reconstructed.is_some(),
+            wrong_secret: reconstructed.is_some() && !success,
+            avg_iterations: reconstruct_metrics
+                .as_ref()
+                .and_then(|metrics| metrics.decoding_stats.as_ref())
+                .map_or(0.0, |decoding| decoding.avg_iterations),
+        },
+        deal_metrics,
+        reconstruct_metrics,
+    })
+}
+
+/// Reconstruct what came over the channel: shares with `decoding`, LLRs with
+/// `reconstruct_soft`. Bitsliced runs only encode differently and decode sequentially.
+pub(super) fn reconstruct_received<C: AdditiveCode + Sync>(
+    implementation: Implementation,
+    decoding: Decoding,
+    pp: &SecretParams<C>,
+    received: &Received,
+) -> Result<(Option<F2PowElement>, Option<ReconstructMetrics>), SchemeError> {
+    match (received, decoding) {
+        (Received::Hard(shares), Decoding::BeliefPropagation) => match implementation {
+            Implementation::Sequential | Implementation::Bitsliced => aos::reconstruct(pp, shares),
+            Implementation::Parallel => aos_parallel::reconstruct(pp, shares),
+        },
+        (Received::Hard(shares), Decoding::MaximumLikelihood) => match implementation {
+            Implementation::Sequential | Implementation::Bitsliced => {
+                aos::reconstruct_erasures(pp, shares)
+            }
+            Implementation::Parallel => aos_parallel::reconstruct_erasures(pp, shares),
+        },
+        (Received::Soft { z0, row_llrs }, Decoding::BeliefPropagation) => match implementation {
+            Implementation::Sequential | Implementation::Bitsliced => {
+                aos::reconstruct_soft(pp, z0, row_llrs)
+            }
+            Implementation::Parallel => aos_parallel::reconstruct_soft(pp, z0, row_llrs),
+        },
+        (Received::Soft { .. }, Decoding::MaximumLikelihood) => {
+            Err(SchemeError::InvalidCodeParameters(String::from(
+                "maximum-likelihood erasure decoding needs hard shares",
+            )))
+        }
+    }
+}
+
+fn config_label(params: &BenchmarkParams) -> String {
+    let mut label = format!(
+        "{} {} {} {:?} remove={}",
+        params.implementation,
+        params.code.rate_label(),
+        params.code.size_label(),
+        params.decoder_type,
+        params.shares_to_remove
+    );
+    if params.participants_to_remove > 0 {
+        label.push_str(&format!(
+            " remove_participants={}",
+            params.participants_to_remove
+        ));
+    }
+    if params.erasure_model != ErasureModel::Uniform {
+        label.push_str(&format!(" erasure={}", params.erasure_model));
+    }
+    if params.channel != ChannelModel::Clean {
+        label.push_str(&format!(" channel={}", params.channel));
+    }
+    if params.decoding != Decoding::BeliefPropagation {
+        label.push_str(&format!(" decoding={}", params.decoding));
+    }
+    label
+}
+
+/// Run the whole benchmark matrix.
+///
+/// `on_progress` receives `(completed_runs, total_runs, label)` after every
+/// run, warmups included. When `cancel` is set the current configuration is
+/// finished early and the partial summary is returned.
+fn run_plan(
+    plan: &BenchmarkPlan,
+    on_progress: &mut dyn FnMut(usize, usize, &str),
+    cancel: Option<&AtomicBool>,
+) -> BenchmarkSummary {
+    let start_time = Instant::now();
+    let is_cancelled = || cancel.is_some_and(|flag| flag.load(Ordering::SeqCst));
+
+    if let Some(settings) = &plan.sweep {
+        let (sweep, cancelled) = run_sweep(plan, settings, on_progress, cancel);
+        let summary = BenchmarkSummary {
+            metadata: plan.metadata(cancelled, start_time.elapsed()),
+            sweep,
+            ..Default::default()
+        };
+        log_success!(
+            "Erasure sweep finished in {:.2?} ({} curves)",
+            summary.metadata.total_duration,
+            summary.sweep.len()
+        );
+        return summary;
+    }
+
+    let configs = plan.configurations();
+
+    let runs_per_config = plan.warmup_runs + plan.runs_per_config;
+    let total_steps = configs.len() * runs_per_config;
+    let mut completed_steps = 0;
+    let mut setup_cache: HashMap<SetupKey, BenchmarkSetup> = HashMap::new();
+    let mut samples: Vec<(BenchmarkParams, ConfigSamples)> = Vec::new();
+    let mut cancelled = false;
+
+    log_info!(
+        "Benchmark started: {} configurations, {} warmup + {} measured runs each",
+        configs.len(),
+        plan.warmup_runs,
+        plan.runs_per_config
+    );
+
+    'configs: for params in configs {
+        let label = config_label(&params);
+        let key = (params.code.clone(), params.decoder_type);
+        let mut config_samples = ConfigSamples::default();
+
+        match params.code.dimensions() {
+            Some((k, n)) => log_info!("Benchmarking {} (k={}, n={})", label, k, n),
+            None => log_info!("Benchmarking {}", label),
+        }
+
+        for run_index in 0..runs_per_config {
+            if is_cancelled() {
+                cancelled = true;
+                if !config_samples.runs.is_empty() {
+                    samples.push((params, config_samples));
+                }
+                break 'configs;
+            }
+
+            let is_warmup = run_index < plan.warmup_runs;
+
+            let mut setup_time = Duration::ZERO;
+            if !plan.cache_setup || !setup_cache.contains_key(&key) {
+                let setup_start = Instant::now();
+                let pp = match run_setup(params.implementation, plan.code_params(&params)) {
+                    Ok(pp) => pp,
+                    Err(err) => {
+                        log_error!("Setup failed for {}: {}", label, err);
+                        completed_steps += runs_per_config - run_index;
+                        on_progress(completed_steps, total_steps, &label);
+                        continue 'configs;
+                    }
+                };
+                setup_time = setup_start.elapsed();
+                setup_cache.insert(key.clone(), pp);
+                if !is_warmup {
+                    config_samples.setup_times.push(setup_time);
+                }
+            }
+            let pp = &setup_cache[&key];
+
+            let measured_index = run_index.saturating_sub(plan.warmup_runs);
+            let removal_seed = plan.removal_seed_for_run(measured_index);
+            let run = match pp {
+                BenchmarkSetup::Ldpc(pp) => {
+                    execute_run(&params, pp, plan.participants, removal_seed)
+                }
+                BenchmarkSetup::ReedSolomon(pp) => {
+                    execute_run(&params, pp, plan.participants, removal_seed)
+                }
+            };
+            let mut outcome = match run {
+                Ok(outcome) => outcome,
+                Err(err) => {
+                    log_error!("Run failed for {}: {}", label, err);
+                    completed_steps += runs_per_config - run_index;
+                    on_progress(completed_steps, total_steps, &label);
+                    continue 'configs;
+                }
+            };
+
+            if !is_warmup {
+                outcome.run.setup_time = setup_time;
+                if let Some(metrics) = &outcome.deal_metrics {
+                    config_samples.record_deal_metrics(metrics);
+                }
+                if let Some(metrics) = &outcome.reconstruct_metrics {
+                    config_samples.record_reconstruct_metrics(metrics);
+                }
+                config_samples.runs.push(outcome.run);
+            }
+
+            completed_steps += 1;
+            on_progress(completed_steps, total_steps, &label);
+        }
+
+        if !plan.cache_setup {
+            setup_cache.remove(&key);
+        }
+        samples.push((params, config_samples));
+    }
+
+    if cancelled {
+        log_warning!(
+            "Benchmark cancelled after {}/{} runs",
+            completed_steps,
+            total_steps
+        );
+    }
+
+    let mut summary = BenchmarkSummary {
+        metadata: plan.metadata(cancelled, start_time.elapsed()),
+        ..Default::default()
+    };
+
+    for (params, config_samples) in samples {
+        if config_samples.runs.is_empty() {
+            continue;
+        }
+        let stats = build_operation_stats(&params, &config_samples);
+        if let Some(setup) = stats.setup {
+            summary.setup_stats.insert(params.clone(), setup);
+        }
+        summary.deal_stats.insert(params.clone(), stats.deal);
+        summary
+            .reconstruct_stats
+            .insert(params.clone(), stats.reconstruct);
+        summary.total_stats.insert(params.clone(), stats.total);
+        summary.runs.insert(params, config_samples.runs);
+    }
+
+    attach_parallel_metrics(&mut summary.total_stats, summary.metadata.thread_count);
+
+    log_success!(
+        "Benchmark finished in {:.2?} ({} configurations)",
+        summary.metadata.total_duration,
+        summary.total_stats.len()
+    );
+
+    summary
+}
+
+fn save_results(summary: &BenchmarkSummary, output_file: Option<&str>) {
+    if let Some(name) = output_file {
+        match export_to_json(summary, name) {
+            Ok(path) => log_success!("Results saved to {}", path.display()),
+            Err(err) => log_error!("Failed to save results: {}", err),
+        }
+    }
+}
+
+/// CLI entry point: runs the benchmark with a terminal progress bar and prints a report.
+pub fn run_comprehensive_benchmark(cfg: &CliConfig) {
+    let plan = BenchmarkPlan::new(cfg);
+
+    println!(
+        "Running benchmark: ell={}, secret=0x{}, runs={}, warmup={}",
+        cfg.secret.bit_len,
+        cfg.secret.to_hex(),
+        cfg.runs_per_config,
+        cfg.warmup_runs
+    );
+
+    let progress_bar = ProgressBar::new(0);
+    progress_bar.set_style(
+        ProgressStyle::with_template("[{elapsed_precise}] {bar:40.cyan/blue} {pos}/{len} {msg}")
+            .expect("valid progress template")
+            .progress_chars("=>-"),
+    );
+
+    let summary = run_plan(
+        &plan,
+        &mut |completed, total, label| {
+            progress_bar.set_length(total as u64);
+            progress_bar.set_position(completed as u64);
+            progress_bar.set_message(label.to_string());
+        },
+        None,
+    );
+    progress_bar.finish_and_clear();
+
+    for line in summary_lines(&summary) {
+        println!("{}", line);
+    }
+    for line in sweep_lines(&summary) {
+        println!("{}", line);
+    }
+    if cfg.show_detail {
+        for line in detail_lines(&summary) {
+            println!("{}", line);
+        }
+    }
+    println!(
+        "Total benchmark time: {}",
+        humantime::format_duration(Duration::from_millis(
+            summary.metadata.total_duration.as_millis() as u64
+        ))
+    );
+
+    if let Some(name) = &cfg.output_file {
+        match export_to_json(&summary, name) {
+            Ok(path) => println!("Results saved to {}", path.display()),
+            Err(err) => {
+                eprintln!("Failed to save results: {}", err);
+                log_error!("Failed to save results: {}", err);
+            }
+        }
+    }
+}
+
+/// GUI entry point: reports progress through `status` and honours `cancel_flag`.
+pub fn run_comprehensive_benchmark_for_ui<F>(
+    cfg: &CliConfig,
+    status: F,
+    cancel_flag: Arc<AtomicBool>,
+) -> BenchmarkSummary
+where
+    F: Fn(String),
+{
+    let plan = BenchmarkPlan::new(cfg);
+
+    let summary = run_plan(
+        &plan,
+        &mut |completed, total, label| {
+            status(format!("[{}/{}] {}", completed, total, label));
+        },
+        Some(cancel_flag.as_ref()),
+    );
+
+    for line in summary_lines(&summary) {
+        log_info!("{}", line);
+    }
+    for line in sweep_lines(&summary) {
+        log_info!("{}", line);
+    }
+    if cfg.show_detail {
+        for line in detail_lines(&summary) {
+            log_info!("{}", line);
+        }
+    }
+    save_results(&summary, cfg.output_file.as_deref());
+
+    summary
+}
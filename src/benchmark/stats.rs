@@ -0,0 +1,295 @@
+//! Aggregation of raw run measurements into [`BenchmarkStats`].
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use super::{BenchmarkParams, BenchmarkRun, BenchmarkStats, Implementation, PhaseStats};
+use crate::types::{
+    DealMetrics, DecodingStats, ParallelMetrics, PhaseMetrics, ReconstructMetrics,
+    ThroughputMetrics,
+};
+
+/// Everything collected for one [`BenchmarkParams`] entry while it runs.
+#[derive(Default)]
+pub(crate) struct ConfigSamples {
+    pub runs: Vec<BenchmarkRun>,
+    pub setup_times: Vec<Duration>,
+    pub deal_phases: Vec<Vec<PhaseMetrics>>,
+    pub reconstruct_phases: Vec<Vec<PhaseMetrics>>,
+    pub decoding: Vec<DecodingStats>,
+}
+
+impl ConfigSamples {
+    pub fn record_deal_metrics(&mut self, metrics: &DealMetrics) {
+        self.deal_phases.push(vec![
+            metrics.rand_vec_generation.clone(),
+            metrics.mask_xor.clone(),
+            metrics.matrix_creation.clone(),
+            metrics.encoding.clone(),
+            metrics.share_creation.clone(),
+        ]);
+    }
+
+    pub fn record_reconstruct_metrics(&mut self, metrics: &ReconstructMetrics) {
+        self.reconstruct_phases.push(vec![
+            metrics.matrix_setup.clone(),
+            metrics.row_decoding.clone(),
+            metrics.column_reconstruction.clone(),
+            metrics.final_computation.clone(),
+        ]);
+        if let Some(decoding) = &metrics.decoding_stats {
+            self.decoding.push(decoding.clone());
+        }
+    }
+}
+
+/// Order statistics, mean and population standard deviation of `samples`.
+pub(crate) fn duration_stats(samples: &[Duration]) -> BenchmarkStats {
+    if samples.is_empty() {
+        return BenchmarkStats::default();
+    }
+
+    let mut sorted = samples.to_vec();
+    sorted.sort();
+
+    let count = sorted.len();
+    let total: Duration = sorted.iter().sum();
+    let avg = total / count as u32;
+    let median = if count.is_multiple_of(2) {
+        (sorted[count / 2 - 1] + sorted[count / 2]) / 2
+    } else {
+        sorted[count / 2]
+    };
+
+    let mean_secs = avg.as_secs_f64();
+    let variance = sorted
+        .iter()
+        .map(|d| {
+            let diff = d.as_secs_f64() - mean_secs;
+            diff * diff
+        })
+        .sum::<f64>()
+        / count as f64;
+
+    BenchmarkStats {
+        avg,
+        min: sorted[0],
+        max: sorted[count - 1],
+        median,
+        std_dev: Duration::from_secs_f64(variance.sqrt()),
+        sample_count: count,
+        success_rate: 1.0,
+        ..Default::default()
+    }
+}
+
+/// Aggregate per-run phase breakdowns, keyed by phase name.
+pub(crate) fn aggregate_phases(runs: &[Vec<PhaseMetrics>]) -> Option<HashMap<String, PhaseStats>> {
+    if runs.is_empty() {
+        return None;
+    }
+
+    let mut grouped: HashMap<String, Vec<&PhaseMetrics>> = HashMap::new();
+    for phase in runs.iter().flatten() {
+        grouped.entry(phase.name.clone()).or_default().push(phase);
+    }
+
+    let phases = grouped
+        .into_iter()
+        .map(|(name, samples)| {
+            let count = samples.len();
+            let total: Duration = samples.iter().map(|p| p.duration).sum();
+            let stats = PhaseStats {
+                avg_duration: total / count as u32,
+                min_duration: samples.iter().map(|p| p.duration).min().unwrap_or_default(),
+                max_duration: samples.iter().map(|p| p.duration).max().unwrap_or_default(),
+                avg_percentage: samples.iter().map(|p| p.percentage).sum::<f64>() / count as f64,
+            };
+            (name, stats)
+        })
+        .collect();
+
+    Some(phases)
+}
+
+/// Sum row-level decoding counters over all runs.
+pub(crate) fn merge_decoding_stats(samples: &[DecodingStats]) -> Option<DecodingStats> {
+    if samples.is_empty() {
+        return None;
+    }
+
+    let sum = |get: fn(&DecodingStats) -> usize| samples.iter().map(get).sum::<usize>();
+    Some(DecodingStats::new(
+        sum(|s| s.total_rows),
+        sum(|s| s.successful_rows),
+        sum(|s| s.failed_rows),
+        sum(|s| s.total_iterations),
+        sum(|s| s.max_iterations_hit),
+    ))
+}
+
+fn throughput(shares: usize, bit_len: usize, avg: Duration) -> Option<ThroughputMetrics> {
+    let secs = avg.as_secs_f64();
+    if secs <= 0.0 {
+        return None;
+    }
+    Some(ThroughputMetrics {
+        shares_per_second: shares as f64 / secs,
+        bits_per_second: (shares * bit_len) as f64 / secs,
+    })
+}
+
+/// Per-operation statistics derived from the samples of one configuration.
+pub(crate) struct OperationStats {
+    pub setup: Option<BenchmarkStats>,
+    pub deal: BenchmarkStats,
+    pub reconstruct: BenchmarkStats,
+    pub total: BenchmarkStats,
+}
+
+pub(crate) fn build_operation_stats(
+    params: &BenchmarkParams,
+    samples: &ConfigSamples,
+) -> OperationStats {
+    let runs = &samples.runs;
+    let bit_len = params.secret.bit_len;
+    let rate = |counted: fn(&BenchmarkRun) -> bool| {
+        if runs.is_empty() {
+            0.0
+        } else {
+            runs.iter().filter(|&r| counted(r)).count() as f64 / runs.len() as f64
+        }
+    };
+    let success_rate = rate(|r| r.success);
+    let wrong_secret_rate = rate(|r| r.wrong_secret);
+
+    let setup = (!samples.setup_times.is_empty()).then(|| duration_stats(&samples.setup_times));
+
+    let deal_times: Vec<Duration> = runs.iter().map(|r| r.deal_time).collect();
+    let mut deal = duration_stats(&deal_times);
+    deal.phase_metrics = aggregate_phases(&samples.deal_phases);
+    let shares_dealt = runs.first().map_or(0, |r| r.shares_dealt);
+    deal.throughput = throughput(shares_dealt, bit_len, deal.avg);
+
+    let reconstruct_times: Vec<Duration> = runs.iter().map(|r| r.reconstruct_time).collect();
+    let mut reconstruct = duration_stats(&reconstruct_times);
+    reconstruct.success_rate = success_rate;
+    reconstruct.wrong_secret_rate = wrong_secret_rate;
+    reconstruct.phase_metrics = aggregate_phases(&samples.reconstruct_phases);
+    reconstruct.decoding_stats = merge_decoding_stats(&samples.decoding);
+    let shares_used = runs.first().map_or(0, |r| r.shares_used);
+    reconstruct.throughput = throughput(shares_used, bit_len, reconstruct.avg);
+
+    let total_times: Vec<Duration> = runs.iter().map(|r| r.total_time).collect();
+    let mut total = duration_stats(&total_times);
+    total.success_rate = success_rate;
+    total.wrong_secret_rate = wrong_secret_rate;
+    total.decoding_stats = reconstruct.decoding_stats.clone();
+
+    OperationStats {
+        setup,
+        deal,
+        reconstruct,
+        total,
+    }
+}
+
+/// Attach the speedup over the sequential entry to every parallel and bitsliced entry
+/// of `total_stats`; bitsliced runs count as one thread.
+pub(crate) fn attach_parallel_metrics(
+    total_stats: &mut HashMap<BenchmarkParams, BenchmarkStats>,
+    thread_count: usize,
+) {
+    let sequential_avg: HashMap<BenchmarkParams, Duration> = total_stats
+        .iter()
+        .filter(|(params, _)| params.implementation == Implementation::Sequential)
+        .map(|(params, stats)| (params.clone(), stats.avg))
+        .collect();
+
+    for (params, stats) in total_stats.iter_mut() {
+        let thread_count = match params.implementation {
+            Implementation::Sequential => continue,
+            Implementation::Parallel => thread_count,
+            Implementation::Bitsliced => 1,
+        };
+        let mut key = params.clone();
+        key.implementation = Implementation::Sequential;
+        let speedup = sequential_avg
+            .get(&key)
+            .filter(|_| stats.avg > Duration::ZERO)
+            .map(|seq| seq.as_secs_f64() / stats.avg.as_secs_f64());
+        stats.parallel_metrics = Some(ParallelMetrics {
+            thread_count,
+            speedup,
+            efficiency: speedup.map(|s| s / thread_count.max(1) as f64),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(value: u64) -> Duration {
+        Duration::from_millis(value)
+    }
+
+    #[test]
+    fn test_duration_stats_odd_count() {
+        let stats = duration_stats(&[ms(30), ms(10), ms(20)]);
+        assert_eq!(stats.avg, ms(20));
+        assert_eq!(stats.min, ms(10));
+        assert_eq!(stats.max, ms(30));
+        assert_eq!(stats.median, ms(20));
+        assert_eq!(stats.sample_count, 3);
+    }
+
+    #[test]
+    fn test_duration_stats_even_count_median() {
+        let stats = duration_stats(&[ms(10), ms(40), ms(20), ms(30)]);
+        assert_eq!(stats.median, ms(25));
+        assert_eq!(stats.std_dev.as_micros(), 11180);
+    }
+
+    #[test]
+    fn test_duration_stats_empty() {
+        let stats = duration_stats(&[]);
+        assert_eq!(stats.sample_count, 0);
+        assert_eq!(stats.avg, Duration::ZERO);
+    }
+
+    #[test]
+    fn test_aggregate_phases_groups_by_name() {
+        let total = ms(100);
+        let runs = vec![
+            vec![
+                PhaseMetrics::new("Encoding phase", ms(60), total),
+                PhaseMetrics::new("Mask XOR", ms(40), total),
+            ],
+            vec![
+                PhaseMetrics::new("Encoding phase", ms(80), total),
+                PhaseMetrics::new("Mask XOR", ms(20), total),
+            ],
+        ];
+        let phases = aggregate_phases(&runs).unwrap();
+        let encoding = &phases["Encoding phase"];
+        assert_eq!(encoding.avg_duration, ms(70));
+        assert_eq!(encoding.min_duration, ms(60));
+        assert_eq!(encoding.max_duration, ms(80));
+        assert!((encoding.avg_percentage - 70.0).abs() < 1e-9);
+        assert!(aggregate_phases(&[]).is_none());
+    }
+
+    #[test]
+    fn test_merge_decoding_stats_sums_counters() {
+        let merged = merge_decoding_stats(&[
+            DecodingStats::new(128, 128, 0, 256, 0),
+            DecodingStats::new(128, 126, 2, 300, 2),
+        ])
+        .unwrap();
+        assert_eq!(merged.total_rows, 256);
+        assert_eq!(merged.successful_rows, 254);
+        assert_eq!(merged.failed_rows, 2);
+        assert_eq!(merged.max_iterations_hit, 2);
+    }
+}
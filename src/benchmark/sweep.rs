@@ -0,0 +1,317 @@
+//! Monte Carlo erasure sweeps: reconstruction failure rate against the share of
+//! shares removed.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use super::runner::{
+    channel_seed, deal_with, reconstruct_received, run_setup, BenchmarkCode, BenchmarkPlan,
+    BenchmarkSetup,
+};
+use super::{Implementation, SweepCurve, SweepPoint, SweepSettings};
+use crate::error::SchemeError;
+use crate::types::SecretParams;
+use crate::{log_error, log_info};
+
+impl SweepSettings {
+    /// Removal percentages of the sweep, from 0 up to `max_percent` (at most 100).
+    pub fn percentages(&self) -> Vec<f64> {
+        let max_percent = self.max_percent.clamp(0.0, 100.0);
+        if self.step_percent <= 0.0 {
+            return vec![0.0];
+        }
+        // Tolerate the rounding of `max_percent / step_percent` just below an integer.
+        let steps = (max_percent / self.step_percent + 1e-9).floor() as usize;
+        (0..=steps)
+            .map(|step| step as f64 * self.step_percent)
+            .collect()
+    }
+}
+
+impl SweepCurve {
+    /// Largest swept removal percentage up to which the reconstruction success rate
+    /// stays at or above `success_rate`, or `None` if the first step is already below.
+    pub fn threshold(&self, success_rate: f64) -> Option<f64> {
+        self.points
+            .iter()
+            .take_while(|point| 1.0 - point.frame_error_rate >= success_rate)
+            .last()
+            .map(|point| point.removed_percent)
+    }
+
+    /// Percentage that still reconstructs in at least half of the trials.
+    pub fn threshold_50(&self) -> Option<f64> {
+        self.threshold(0.5)
+    }
+
+    /// Percentage that still reconstructs in at least 99% of the trials.
+    pub fn threshold_99(&self) -> Option<f64> {
+        self.threshold(0.99)
+    }
+}
+
+/// Progress of the sweep in trials, shared by all curves.
+struct SweepProgress<'a> {
+    completed: usize,
+    total: usize,
+    on_progress: &'a mut dyn FnMut(usize, usize, &str),
+}
+
+impl SweepProgress<'_> {
+    fn advance(&mut self, trials: usize, label: &str) {
+        self.completed += trials;
+        (self.on_progress)(self.completed, self.total, label);
+    }
+}
+
+/// Sweep every code, decoder, erasure model, channel and decoding of `plan`. Returns the curves and whether the sweep
+/// was cancelled; the curve being swept when `cancel` is set keeps its finished steps.
+pub(super) fn run_sweep(
+    plan: &BenchmarkPlan,
+    settings: &SweepSettings,
+    on_progress: &mut dyn FnMut(usize, usize, &str),
+    cancel: Option<&AtomicBool>,
+) -> (Vec<SweepCurve>, bool) {
+    // Correctness does not depend on the backend, so use the faster one if selected.
+    let implementation = if plan.implementations.contains(&Implementation::Parallel) {
+        Implementation::Parallel
+    } else {
+        Implementation::Sequential
+    };
+    let pairs: Vec<_> = plan
+        .code_decoder_pairs()
+        .into_iter()
+        .flat_map(|(code, decoder_type)| {
+            plan.erasure_models().iter().flat_map(move |&model| {
+                let code = code.clone();
+                plan.channels().iter().flat_map(move |&channel| {
+                    let code = code.clone();
+                    plan.decodings().iter().map(move |&decoding| {
+                        (code.clone(), decoder_type, model, channel, decoding)
+                    })
+                })
+            })
+        })
+        .filter(|(code, decoder_type, _, channel, decoding)| {
+            plan.decoding_applies(*decoding, code, *decoder_type, *channel)
+        })
+        .collect();
+    let percentages = settings.percentages();
+    let mut progress = SweepProgress {
+        completed: 0,
+        total: pairs.len() * percentages.len() * settings.trials,
+        on_progress,
+    };
+
+    log_info!(
+        "Erasure sweep started: {} curves, 0-{}% removed in {} steps of {}%, {} trials each",
+        pairs.len(),
+        percentages.last().copied().unwrap_or_default(),
+        percentages.len(),
+        settings.step_percent,
+        settings.trials
+    );
+
+    let mut curves = Vec::new();
+    for (code, decoder_type, erasure_model, channel, decoding) in pairs {
+        let label = format!(
+            "sweep {} {} {:?} erasure={} channel={} decoding={}",
+            code.rate_label(),
+            code.size_label(),
+            decoder_type,
+            erasure_model,
+            channel,
+            decoding
+        );
+        let mut curve = SweepCurve {
+            implementation,
+            decoder_type,
+            code,
+            erasure_model,
+            channel,
+            decoding,
+            secret_bits: plan.secret.bit_len,
+            points: Vec::new(),
+        };
+        let code_params = curve.code.init_params(
+            decoder_type,
+            plan.max_iterations,
+            plan.llr_value,
+            plan.secret.bit_len,
+        );
+        let swept = match run_setup(implementation, code_params) {
+            Ok(BenchmarkSetup::Ldpc(pp)) => sweep_curve(
+                plan,
+                settings,
+                &pp,
+                &mut curve,
+                &label,
+                &mut progress,
+                cancel,
+            ),
+            Ok(BenchmarkSetup::ReedSolomon(pp)) => sweep_curve(
+                plan,
+                settings,
+                &pp,
+                &mut curve,
+                &label,
+                &mut progress,
+                cancel,
+            ),
+            Err(err) => Err(err),
+        };
+        let cancelled = match swept {
+            Ok(cancelled) => cancelled,
+            Err(err) => {
+                log_error!("Sweep failed for {}: {}", label, err);
+                false
+            }
+        };
+        if !cancelled {
+            // Steps skipped after a failed setup or a step where every trial failed.
+            let swept_trials = curve.points.len() * settings.trials;
+            progress.advance(percentages.len() * settings.trials - swept_trials, &label);
+        }
+
+        if !curve.points.is_empty() {
+            curves.push(curve);
+        }
+        if cancelled {
+            return (curves, true);
+        }
+    }
+    (curves, false)
+}
+
+/// Deal once and reconstruct `settings.trials` removal patterns of the curve's
+/// erasure model per step, sent over its channel.
+/// The curve stops after the first step at which every trial fails. Returns
+/// whether `cancel` was set.
+fn sweep_curve<C: BenchmarkCode>(
+    plan: &BenchmarkPlan,
+    settings: &SweepSettings,
+    pp: &SecretParams<C>,
+    curve: &mut SweepCurve,
+    label: &str,
+    progress: &mut SweepProgress,
+    cancel: Option<&AtomicBool>,
+) -> Result<bool, SchemeError> {
+    let implementation = curve.implementation;
+    let mut shares = deal_with(implementation, pp, plan.secret)?;
+    shares.metrics = None;
+    let shares_dealt = shares.shares.len();
+
+    for (step, removed_percent) in settings.percentages().into_iter().enumerate() {
+        let removed = (removed_percent / 100.0 * shares_dealt as f64).round() as usize;
+        let step_label = format!("{} remove={:.1}%", label, removed_percent);
+        let mut point = SweepPoint {
+            removed_percent,
+            removed,
+            ..Default::default()
+        };
+        let (mut rows, mut failed_rows, mut iterations) = (0, 0, 0);
+
+        for trial in 0..settings.trials {
+            if cancel.is_some_and(|flag| flag.load(Ordering::SeqCst)) {
+                return Ok(true);
+            }
+            let mut kept = shares.clone();
+            let seed = plan
+                .removal_seed
+                .map(|seed| seed.wrapping_add((step * settings.trials + trial) as u64));
+            curve
+                .erasure_model
+                .erase(&pp.code.code_impl, &mut kept.shares, removed as isize, seed);
+
+            let received =
+                curve
+                    .channel
+                    .transmit(kept, pp.code.output_length as usize, channel_seed(seed));
+            let (reconstructed, metrics) =
+                reconstruct_received(implementation, curve.decoding, pp, &received)?;
+            point.trials += 1;
+            if reconstructed.as_ref() != Some(plan.secret) {
+                point.failures += 1;
+                if reconstructed.is_some() {
+                    point.wrong_secrets += 1;
+                }
+            }
+            if let Some(decoding) = metrics.and_then(|metrics| metrics.decoding_stats) {
+                rows += decoding.total_rows;
+                failed_rows += decoding.failed_rows;
+                iterations += decoding.total_iterations;
+            }
+            progress.advance(1, &step_label);
+        }
+
+        point.frame_error_rate = point.failures as f64 / point.trials.max(1) as f64;
+        point.wrong_secret_rate = point.wrong_secrets as f64 / point.trials.max(1) as f64;
+        point.row_failure_rate = failed_rows as f64 / rows.max(1) as f64;
+        point.avg_iterations = iterations as f64 / rows.max(1) as f64;
+        let all_failed = point.failures == point.trials;
+        curve.points.push(point);
+        if all_failed {
+            break;
+        }
+    }
+    Ok(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::channel_model::ChannelModel;
+    use crate::code::family::BuiltinCode;
+    use crate::erasure_model::ErasureModel;
+    use ldpc_toolbox::codes::ccsds::{AR4JAInfoSize, AR4JARate};
+    use ldpc_toolbox::decoder::factory::DecoderImplementation;
+
+    fn point(removed_percent: f64, frame_error_rate: f64) -> SweepPoint {
+        SweepPoint {
+            removed_percent,
+            frame_error_rate,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_percentages_include_both_ends() {
+        let settings = SweepSettings {
+            max_percent: 0.3,
+            step_percent: 0.1,
+            trials: 1,
+        };
+        assert_eq!(settings.percentages().len(), 4);
+        let settings = SweepSettings {
+            max_percent: 250.0,
+            step_percent: 50.0,
+            trials: 1,
+        };
+        assert_eq!(settings.percentages(), vec![0.0, 50.0, 100.0]);
+    }
+
+    #[test]
+    fn test_thresholds_stop_at_first_drop() {
+        let curve = SweepCurve {
+            implementation: Implementation::Parallel,
+            decoder_type: DecoderImplementation::Aminstarf32,
+            code: crate::types::CodeSource::Builtin(BuiltinCode::Ar4ja {
+                rate: AR4JARate::R4_5,
+                info_size: AR4JAInfoSize::K1024,
+            }),
+            erasure_model: ErasureModel::Uniform,
+            channel: ChannelModel::Clean,
+            decoding: crate::benchmark::Decoding::BeliefPropagation,
+            secret_bits: 128,
+            points: vec![
+                point(0.0, 0.0),
+                point(5.0, 0.0),
+                point(10.0, 0.2),
+                point(15.0, 0.6),
+                point(20.0, 0.4),
+            ],
+        };
+        assert_eq!(curve.threshold_99(), Some(5.0));
+        assert_eq!(curve.threshold_50(), Some(10.0));
+        assert_eq!(curve.threshold(1.1), None);
+    }
+}
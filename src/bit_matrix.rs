@@ -0,0 +1,322 @@
+//! Bit-packed GF(2) matrix used as the internal representation of deal and reconstruct.
+//!
+//! Rows are stored contiguously as `u64` words, least significant bit first, so a
+//! row of `ell` bits has the same layout as the bytes of an [`F2PowElement`].
+
+use ldpc_toolbox::gf2::GF2;
+use ndarray::Array1;
+use num_traits::{One, Zero};
+
+use crate::types::F2PowElement;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BitMatrix {
+    rows: usize,
+    cols: usize,
+    words_per_row: usize,
+    data: Vec<u64>,
+}
+
+/// Transpose a 64x64 bit block in place: bit `c` of `block[r]` moves to bit `r` of `block[c]`.
+fn transpose_block(block: &mut [u64; 64]) {
+    let mut width = 32;
+    let mut mask: u64 = 0x0000_0000_FFFF_FFFF;
+    while width != 0 {
+        let mut k = 0;
+        while k < 64 {
+            let swap = ((block[k] >> width) ^ block[k + width]) & mask;
+            block[k] ^= swap << width;
+            block[k + width] ^= swap;
+            k = (k + width + 1) & !width;
+        }
+        width >>= 1;
+        mask ^= mask << width;
+    }
+}
+
+/// `dst ^= src`, word by word.
+pub fn xor_into(dst: &mut [u64], src: &[u64]) {
+    for (d, s) in dst.iter_mut().zip(src) {
+        *d ^= *s;
+    }
+}
+
+/// Pack little-endian bytes into `words` little-endian `u64` words.
+pub fn bytes_to_words(bytes: &[u8], out: &mut [u64]) {
+    out.fill(0);
+    for (idx, &byte) in bytes.iter().enumerate().take(out.len() * 8) {
+        out[idx / 8] |= (byte as u64) << (8 * (idx % 8));
+    }
+}
+
+impl BitMatrix {
+    pub fn zeros(rows: usize, cols: usize) -> Self {
+        let words_per_row = cols.div_ceil(64);
+        BitMatrix {
+            rows,
+            cols,
+            words_per_row,
+            data: vec![0; rows * words_per_row],
+        }
+    }
+
+    /// One row per element; every element must have `cols` bits.
+    pub fn from_elements(elements: &[F2PowElement], cols: usize) -> Self {
+        let mut matrix = BitMatrix::zeros(elements.len(), cols);
+        for (row, element) in elements.iter().enumerate() {
+            debug_assert_eq!(element.bit_len, cols);
+            bytes_to_words(&element.bytes, matrix.row_mut(row));
+        }
+        matrix
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn words_per_row(&self) -> usize {
+        self.words_per_row
+    }
+
+    pub fn get(&self, row: usize, col: usize) -> bool {
+        debug_assert!(row < self.rows && col < self.cols);
+        (self.row(row)[col / 64] >> (col % 64)) & 1 == 1
+    }
+
+    pub fn set(&mut self, row: usize, col: usize, value: bool) {
+        debug_assert!(row < self.rows && col < self.cols);
+        let word = &mut self.row_mut(row)[col / 64];
+        let mask = 1u64 << (col % 64);
+        if value {
+            *word |= mask;
+        } else {
+            *word &= !mask;
+        }
+    }
+
+    pub fn row(&self, row: usize) -> &[u64] {
+        let start = row * self.words_per_row;
+        &self.data[start..start + self.words_per_row]
+    }
+
+    pub fn row_mut(&mut self, row: usize) -> &mut [u64] {
+        let start = row * self.words_per_row;
+        &mut self.data[start..start + self.words_per_row]
+    }
+
+    pub fn swap_rows(&mut self, a: usize, b: usize) {
+        if a == b {
+            return;
+        }
+        let (lo, hi) = (a.min(b), a.max(b));
+        let words = self.words_per_row;
+        let (head, tail) = self.data.split_at_mut(hi * words);
+        head[lo * words..(lo + 1) * words].swap_with_slice(&mut tail[..words]);
+    }
+
+    /// The first `cols` columns of every row.
+    pub fn leading_columns(&self, cols: usize) -> BitMatrix {
+        debug_assert!(cols <= self.cols);
+        let mut out = BitMatrix::zeros(self.rows, cols);
+        let words = out.words_per_row;
+        for row in 0..self.rows {
+            let dst = out.row_mut(row);
+            dst.copy_from_slice(&self.row(row)[..words]);
+            if !cols.is_multiple_of(64) {
+                dst[words - 1] &= (1u64 << (cols % 64)) - 1;
+            }
+        }
+        out
+    }
+
+    /// Rank over GF(2), by elimination on a copy.
+    pub fn rank(&self) -> usize {
+        let mut reduced = self.clone();
+        let mut rank = 0;
+        for col in 0..self.cols {
+            let Some(found) = (rank..self.rows).find(|&row| reduced.get(row, col)) else {
+                continue;
+            };
+            reduced.swap_rows(rank, found);
+            let pivot_row = reduced.row(rank).to_vec();
+            for row in rank + 1..self.rows {
+                if reduced.get(row, col) {
+                    xor_into(reduced.row_mut(row), &pivot_row);
+                }
+            }
+            rank += 1;
+            if rank == self.rows {
+                break;
+            }
+        }
+        rank
+    }
+
+    /// All rows as one slice, `words_per_row` words each (for chunked parallel writes).
+    pub fn data_mut(&mut self) -> &mut [u64] {
+        &mut self.data
+    }
+
+    /// Row `row` as an element of `F_{2^cols}`.
+    pub fn row_element(&self, row: usize) -> F2PowElement {
+        let bytes = self
+            .row(row)
+            .iter()
+            .flat_map(|word| word.to_le_bytes())
+            .take(self.cols.div_ceil(8))
+            .collect();
+        F2PowElement {
+            bytes,
+            bit_len: self.cols,
+        }
+    }
+
+    pub fn row_to_gf2(&self, row: usize) -> Array1<GF2> {
+        let words = self.row(row);
+        (0..self.cols)
+            .map(|col| {
+                if (words[col / 64] >> (col % 64)) & 1 == 1 {
+                    GF2::one()
+                } else {
+                    GF2::zero()
+                }
+            })
+            .collect()
+    }
+
+    /// Transpose in 64x64 blocks.
+    pub fn transpose(&self) -> BitMatrix {
+        let mut out = BitMatrix::zeros(self.cols, self.rows);
+        let mut block = [0u64; 64];
+
+        for block_row in 0..self.rows.div_ceil(64) {
+            let row_base = block_row * 64;
+            let row_count = (self.rows - row_base).min(64);
+            for block_col in 0..self.words_per_row {
+                let col_base = block_col * 64;
+                let col_count = (self.cols - col_base).min(64);
+
+                for (i, slot) in block.iter_mut().enumerate() {
+                    *slot = if i < row_count {
+                        self.row(row_base + i)[block_col]
+                    } else {
+                        0
+                    };
+                }
+                transpose_block(&mut block);
+                for (i, &word) in block.iter().enumerate().take(col_count) {
+                    out.row_mut(col_base + i)[block_row] = word;
+                }
+            }
+        }
+
+        out
+    }
+}
+
+/// Pack a GF(2) vector (e.g. an encoder output) into `out`.
+pub fn gf2_to_words(bits: &Array1<GF2>, out: &mut [u64]) {
+    out.fill(0);
+    for (idx, bit) in bits.iter().enumerate() {
+        if bit.is_one() {
+            out[idx / 64] |= 1 << (idx % 64);
+        }
+    }
+}
+
+/// Pack the first `len` hard decisions of a decoder codeword (one `u8` per bit) into `out`.
+pub fn codeword_to_words(codeword: &[u8], out: &mut [u64], len: usize) {
+    out.fill(0);
+    for (idx, &bit) in codeword.iter().take(len).enumerate() {
+        if bit == 1 {
+            out[idx / 64] |= 1 << (idx % 64);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::{RngExt, SeedableRng};
+
+    fn random_matrix(rows: usize, cols: usize, seed: u64) -> BitMatrix {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut matrix = BitMatrix::zeros(rows, cols);
+        for row in 0..rows {
+            for col in 0..cols {
+                matrix.set(row, col, rng.random());
+            }
+        }
+        matrix
+    }
+
+    #[test]
+    fn test_transpose_matches_naive() {
+        for (rows, cols) in [(1, 1), (64, 64), (70, 130), (128, 1), (3, 200)] {
+            let matrix = random_matrix(rows, cols, (rows * cols) as u64);
+            let transposed = matrix.transpose();
+            assert_eq!((transposed.rows(), transposed.cols()), (cols, rows));
+            for row in 0..rows {
+                for col in 0..cols {
+                    assert_eq!(matrix.get(row, col), transposed.get(col, row));
+                }
+            }
+            assert_eq!(transposed.transpose(), matrix);
+        }
+    }
+
+    #[test]
+    fn test_element_rows_roundtrip() {
+        let elements = vec![
+            F2PowElement::from_hex("1abcd", 70).unwrap(),
+            F2PowElement::from_hex("20000000000000001", 70).unwrap(),
+        ];
+        let matrix = BitMatrix::from_elements(&elements, 70);
+        assert_eq!(matrix.row_element(0), elements[0]);
+        assert_eq!(matrix.row_element(1), elements[1]);
+        assert!(matrix.get(1, 65));
+        assert!(matrix.get(1, 0));
+    }
+
+    #[test]
+    fn test_swap_rows_and_leading_columns() {
+        let mut matrix = random_matrix(3, 130, 4);
+        let original = matrix.clone();
+        matrix.swap_rows(0, 2);
+        assert_eq!(matrix.row(0), original.row(2));
+        assert_eq!(matrix.row(2), original.row(0));
+
+        let prefix = original.leading_columns(70);
+        assert_eq!(prefix.cols(), 70);
+        for col in 0..70 {
+            assert_eq!(prefix.get(1, col), original.get(1, col));
+        }
+        assert_eq!(prefix.row(1)[1] >> 6, 0);
+    }
+
+    #[test]
+    fn test_rank() {
+        let mut matrix = BitMatrix::zeros(3, 5);
+        for (row, col) in [(0, 0), (0, 3), (1, 1), (2, 0), (2, 1), (2, 3)] {
+            matrix.set(row, col, true);
+        }
+        assert_eq!(matrix.rank(), 2);
+        matrix.set(2, 4, true);
+        assert_eq!(matrix.rank(), 3);
+        assert_eq!(BitMatrix::zeros(4, 4).rank(), 0);
+    }
+
+    #[test]
+    fn test_gf2_row_roundtrip() {
+        let matrix = random_matrix(2, 100, 9);
+        let bits = matrix.row_to_gf2(1);
+        let mut words = vec![0; matrix.words_per_row()];
+        gf2_to_words(&bits, &mut words);
+        assert_eq!(words, matrix.row(1));
+    }
+}
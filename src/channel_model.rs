@@ -0,0 +1,245 @@
+//! Noise on the shares that do arrive.
+//!
+//! An [`crate::erasure_model::ErasureModel`] decides which shares are missing; a
+//! [`ChannelModel`] decides what happens to the bits of the others on the way to
+//! the reconstructor. Flipped bits are what `--llr` and the decoder variants have
+//! to cope with, and what can make a decode converge to the wrong secret.
+
+use std::f64::consts::TAU;
+use std::fmt::{Display, Formatter};
+use std::hash::{Hash, Hasher};
+
+use rand::rngs::StdRng;
+use rand::{Rng, RngExt, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+use crate::types::{F2PowElement, Share, Shares};
+
+/// Channel the present shares go through.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum ChannelModel {
+    /// Shares arrive intact.
+    #[default]
+    Clean,
+    /// Binary symmetric channel: every bit of every present share flips
+    /// independently with this probability. The decoder sees hard bits at the
+    /// code's configured LLR magnitude.
+    Bsc(f64),
+    /// BPSK over additive white Gaussian noise at this Es/N0 in dB. The decoder gets
+    /// the channel LLRs `2y / sigma^2` instead of hard bits.
+    Awgn(f64),
+}
+
+/// What the reconstructor receives from a [`ChannelModel`].
+#[allow(clippy::large_enum_variant)]
+pub enum Received {
+    /// Shares, possibly with flipped bits; reconstruct as usual.
+    Hard(Shares),
+    /// LLRs `row_llrs[row][col]`, 0 for missing shares; reconstruct with
+    /// `reconstruct_soft`.
+    Soft {
+        z0: F2PowElement,
+        row_llrs: Vec<Vec<f64>>,
+    },
+}
+
+impl ChannelModel {
+    /// The `--channel=` value naming this model.
+    pub fn label(&self) -> String {
+        match self {
+            ChannelModel::Clean => String::from("clean"),
+            ChannelModel::Bsc(flip_rate) => format!("bsc:{}", flip_rate),
+            ChannelModel::Awgn(snr_db) => format!("awgn:{}", snr_db),
+        }
+    }
+
+    /// Whether [`Self::transmit`] delivers LLRs rather than shares.
+    pub fn is_soft(&self) -> bool {
+        matches!(self, ChannelModel::Awgn(_))
+    }
+
+    /// Inverse of [`Self::label`]. Flip rates must lie in `[0, 1]` and SNRs be finite.
+    pub fn parse(s: &str) -> Result<Self, String> {
+        if s == "clean" {
+            return Ok(ChannelModel::Clean);
+        }
+        let parsed = match s.split_once(':') {
+            Some(("bsc", value)) => value
+                .parse::<f64>()
+                .ok()
+                .filter(|rate| (0.0..=1.0).contains(rate))
+                .map(ChannelModel::Bsc),
+            Some(("awgn", value)) => value
+                .parse::<f64>()
+                .ok()
+                .filter(|snr_db| snr_db.is_finite())
+                .map(ChannelModel::Awgn),
+            _ => None,
+        };
+        parsed.ok_or_else(|| format!("Unknown channel model: {}", s))
+    }
+
+    /// Send `shares` over the channel. `output_length` sizes the LLR rows of a soft
+    /// channel. The seed makes the noise reproducible, like the seed of
+    /// [`crate::erasure_model::ErasureModel::erase`].
+    pub fn transmit(
+        &self,
+        mut shares: Shares,
+        output_length: usize,
+        seed: Option<u64>,
+    ) -> Received {
+        let mut rng = match seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_rng(&mut rand::rng()),
+        };
+        match *self {
+            ChannelModel::Clean => Received::Hard(shares),
+            ChannelModel::Bsc(flip_rate) => {
+                flip_bits(&mut shares.shares, flip_rate, &mut rng);
+                Received::Hard(shares)
+            }
+            ChannelModel::Awgn(snr_db) => {
+                let sigma = (0.5 / 10f64.powf(snr_db / 10.0)).sqrt();
+                let mut row_llrs = vec![vec![0.0; output_length]; shares.z0.bit_len];
+                for share in &shares.shares {
+                    for (row, llrs) in row_llrs.iter_mut().enumerate() {
+                        let symbol = if share.y.bit(row) { -1.0 } else { 1.0 };
+                        let received = symbol + sigma * standard_normal(&mut rng);
+                        llrs[share.i as usize] = 2.0 * received / (sigma * sigma);
+                    }
+                }
+                Received::Soft {
+                    z0: shares.z0,
+                    row_llrs,
+                }
+            }
+        }
+    }
+}
+
+/// Flip every bit of `shares` independently with probability `flip_rate`.
+fn flip_bits<R: Rng + ?Sized>(shares: &mut [Share], flip_rate: f64, rng: &mut R) {
+    if flip_rate <= 0.0 {
+        return;
+    }
+    for share in shares {
+        for bit in 0..share.y.bit_len {
+            if rng.random_bool(flip_rate) {
+                let flipped = !share.y.bit(bit);
+                share.y.set_bit(bit, flipped);
+            }
+        }
+    }
+}
+
+/// A standard normal sample by the Box–Muller transform.
+fn standard_normal<R: Rng + ?Sized>(rng: &mut R) -> f64 {
+    let (u1, u2): (f64, f64) = (rng.random(), rng.random());
+    (-2.0 * (1.0 - u1).ln()).sqrt() * (TAU * u2).cos()
+}
+
+// Models are compared and hashed by label, so parameters that print alike are the
+// same benchmark configuration.
+impl PartialEq for ChannelModel {
+    fn eq(&self, other: &Self) -> bool {
+        self.label() == other.label()
+    }
+}
+
+impl Eq for ChannelModel {}
+
+impl Hash for ChannelModel {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.label().hash(state);
+    }
+}
+
+impl Display for ChannelModel {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.label())
+    }
+}
+
+impl From<ChannelModel> for String {
+    fn from(model: ChannelModel) -> Self {
+        model.label()
+    }
+}
+
+impl TryFrom<String> for ChannelModel {
+    type Error = String;
+
+    fn try_from(label: String) -> Result<Self, Self::Error> {
+        ChannelModel::parse(&label)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shares(count: u32, bit_len: usize) -> Shares {
+        Shares {
+            shares: (0..count)
+                .map(|i| Share {
+                    i,
+                    y: F2PowElement::zero(bit_len),
+                    proof: None,
+                })
+                .collect(),
+            z0: F2PowElement::zero(bit_len),
+            metrics: None,
+            commitment: None,
+        }
+    }
+
+    #[test]
+    fn test_labels_roundtrip() {
+        for model in [
+            ChannelModel::Clean,
+            ChannelModel::Bsc(0.01),
+            ChannelModel::Awgn(-1.5),
+        ] {
+            assert_eq!(ChannelModel::parse(&model.label()), Ok(model));
+        }
+        assert!(ChannelModel::parse("bsc:1.5").is_err());
+        assert!(ChannelModel::parse("awgn:inf").is_err());
+        assert!(ChannelModel::parse("noisy").is_err());
+    }
+
+    #[test]
+    fn test_bsc_flips_at_its_rate() {
+        let Received::Hard(sent) = ChannelModel::Bsc(0.1).transmit(shares(100, 100), 100, Some(1))
+        else {
+            panic!("a binary symmetric channel delivers hard bits");
+        };
+        let flipped: usize = sent
+            .shares
+            .iter()
+            .map(|share| (0..100).filter(|&bit| share.y.bit(bit)).count())
+            .sum();
+        assert!(
+            (800..1200).contains(&flipped),
+            "{} of 10000 flipped",
+            flipped
+        );
+    }
+
+    #[test]
+    fn test_awgn_leaves_missing_columns_erased() {
+        let mut sent = shares(10, 64);
+        sent.shares.retain(|share| share.i != 3);
+        let Received::Soft { row_llrs, .. } = ChannelModel::Awgn(10.0).transmit(sent, 12, Some(2))
+        else {
+            panic!("an AWGN channel delivers LLRs");
+        };
+        assert_eq!(row_llrs.len(), 64);
+        for llrs in &row_llrs {
+            assert_eq!(llrs[3], 0.0);
+            assert_eq!(llrs[10..], [0.0, 0.0]);
+            // At 10 dB a zero bit is received with a strongly positive LLR.
+            assert!(llrs[..3].iter().all(|&llr| llr > 0.0));
+        }
+    }
+}
@@ -0,0 +1,181 @@
+//! Parity-check matrices in MacKay's alist format.
+//!
+//! `SparseMatrix::from_alist` panics on out-of-range row indices, so files are
+//! parsed here with every count and index checked, and the row section (when
+//! present) is compared against the column section.
+
+use ldpc_toolbox::sparse::SparseMatrix;
+use std::path::Path;
+
+use crate::error::SchemeError;
+
+fn invalid(reason: impl Into<String>) -> SchemeError {
+    SchemeError::InvalidParityCheck(reason.into())
+}
+
+fn numbers(line: &str, what: &str) -> Result<Vec<usize>, SchemeError> {
+    line.split_whitespace()
+        .map(|token| {
+            token
+                .parse()
+                .map_err(|_| invalid(format!("{} contains non-numeric entry {:?}", what, token)))
+        })
+        .collect()
+}
+
+/// Non-padding entries of an adjacency line, converted to zero-based indices.
+fn adjacency(
+    line: &str,
+    what: &str,
+    bound: usize,
+    expected: usize,
+) -> Result<Vec<usize>, SchemeError> {
+    let mut entries: Vec<usize> = numbers(line, what)?
+        .into_iter()
+        .filter(|&entry| entry != 0)
+        .map(|entry| {
+            if entry > bound {
+                Err(invalid(format!(
+                    "{} references index {} (at most {})",
+                    what, entry, bound
+                )))
+            } else {
+                Ok(entry - 1)
+            }
+        })
+        .collect::<Result<_, _>>()?;
+    if entries.len() != expected {
+        return Err(invalid(format!(
+            "{} lists {} entries but its weight is {}",
+            what,
+            entries.len(),
+            expected
+        )));
+    }
+    entries.sort_unstable();
+    if entries.windows(2).any(|pair| pair[0] == pair[1]) {
+        return Err(invalid(format!("{} repeats an index", what)));
+    }
+    Ok(entries)
+}
+
+/// Parse alist text, with or without zero padding for irregular codes.
+pub fn parse_alist(text: &str) -> Result<SparseMatrix, SchemeError> {
+    let mut lines = text.lines().filter(|line| !line.trim().is_empty());
+    let mut next_line = |what: &str| {
+        lines
+            .next()
+            .ok_or_else(|| invalid(format!("missing {}", what)))
+    };
+
+    let sizes = numbers(next_line("size line")?, "size line")?;
+    let [ncols, nrows] = sizes[..] else {
+        return Err(invalid("size line must hold the column and row counts"));
+    };
+    if nrows == 0 || ncols <= nrows {
+        return Err(invalid(format!(
+            "a {} x {} matrix has no information positions",
+            nrows, ncols
+        )));
+    }
+    next_line("maximum weights")?;
+    let col_weights = numbers(next_line("column weights")?, "column weights")?;
+    let row_weights = numbers(next_line("row weights")?, "row weights")?;
+    if col_weights.len() != ncols || row_weights.len() != nrows {
+        return Err(invalid(format!(
+            "expected {} column and {} row weights, found {} and {}",
+            ncols,
+            nrows,
+            col_weights.len(),
+            row_weights.len()
+        )));
+    }
+
+    let mut h = SparseMatrix::new(nrows, ncols);
+    for (col, &weight) in col_weights.iter().enumerate() {
+        let what = format!("column {}", col + 1);
+        for row in adjacency(next_line(&what)?, &what, nrows, weight)? {
+            h.insert(row, col);
+        }
+    }
+
+    for (row, &weight) in row_weights.iter().enumerate() {
+        let what = format!("row {}", row + 1);
+        let Some(line) = lines.next() else {
+            if row == 0 {
+                break; // row section omitted
+            }
+            return Err(invalid(format!("missing {}", what)));
+        };
+        let cols = adjacency(line, &what, ncols, weight)?;
+        let mut expected: Vec<usize> = h.iter_row(row).copied().collect();
+        expected.sort_unstable();
+        if cols != expected {
+            return Err(invalid(format!(
+                "{} disagrees with the column section",
+                what
+            )));
+        }
+    }
+
+    Ok(h)
+}
+
+pub fn load_alist(path: &Path) -> Result<SparseMatrix, SchemeError> {
+    let text = std::fs::read_to_string(path)
+        .map_err(|err| invalid(format!("cannot read {}: {}", path.display(), err)))?;
+    parse_alist(&text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// [7,4] Hamming code, parity part last.
+    const HAMMING: &str = "7 3
+3 4
+2 2 2 3 1 1 1
+4 4 4
+1 2 0
+1 3 0
+2 3 0
+1 2 3
+1 0 0
+2 0 0
+3 0 0
+1 2 4 5
+1 3 4 6
+2 3 4 7
+";
+
+    #[test]
+    fn test_parse_matches_toolbox_writer() {
+        let h = parse_alist(HAMMING).unwrap();
+        assert_eq!((h.num_rows(), h.num_cols()), (3, 7));
+        assert!(h.contains(2, 3) && h.contains(0, 4) && !h.contains(0, 2));
+        assert_eq!(parse_alist(&h.alist()).unwrap(), h);
+        assert_eq!(parse_alist(&h.alist_no_padding()).unwrap(), h);
+    }
+
+    #[test]
+    fn test_row_section_is_optional() {
+        let columns_only: String = HAMMING.lines().take(11).collect::<Vec<_>>().join("\n");
+        assert_eq!(
+            parse_alist(&columns_only).unwrap(),
+            parse_alist(HAMMING).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_rejects_malformed_files() {
+        let out_of_range = HAMMING.replacen("1 2 0", "1 9 0", 1);
+        let wrong_weight = HAMMING.replacen("2 2 2 3", "3 2 2 3", 1);
+        let inconsistent_rows = HAMMING.replace("2 3 4 7", "2 3 5 7");
+        for text in ["", "7", &out_of_range, &wrong_weight, &inconsistent_rows] {
+            assert!(matches!(
+                parse_alist(text),
+                Err(SchemeError::InvalidParityCheck(_))
+            ));
+        }
+    }
+}
@@ -0,0 +1,354 @@
+//! Erasure decoding by peeling and Gaussian elimination over GF(2).
+//!
+//! Every row of a dealt matrix shares the same erasure pattern, so the system
+//! `H_E x_E = H_K y_K` only has to be solved once. The right-hand side carries all
+//! rows at once: each codeword position is a column of `ell` bits packed into `u64`
+//! words, and one row operation updates every row.
+
+use ldpc_toolbox::sparse::SparseMatrix;
+use rayon::prelude::*;
+
+use crate::bit_matrix::{xor_into, BitMatrix};
+
+/// Recovered erased positions of a batch of codewords.
+pub struct ErasureSolution {
+    /// Erased codeword positions, in increasing order.
+    pub erased: Vec<usize>,
+    /// Packed bits of each erased position, `None` when the known positions do not determine it.
+    pub recovered: Vec<Option<Vec<u64>>>,
+    /// Erased positions peeling left to Gaussian elimination: a stopping set of `h`.
+    pub eliminated: usize,
+    /// Packed mask of rows whose known bits violate a parity check.
+    pub inconsistent_rows: Vec<u64>,
+}
+
+impl ErasureSolution {
+    /// Bits of codeword position `col`, or `None` if it was erased and not recovered.
+    pub fn column<'a>(&'a self, col: usize, known: &'a BitMatrix) -> Option<&'a [u64]> {
+        match self.erased.binary_search(&col) {
+            Ok(idx) => self.recovered[idx].as_deref(),
+            Err(_) => Some(known.row(col)),
+        }
+    }
+}
+
+fn or_into(dst: &mut [u64], src: &[u64]) {
+    for (d, s) in dst.iter_mut().zip(src) {
+        *d |= *s;
+    }
+}
+
+fn bit_is_set(words: &[u64], idx: usize) -> bool {
+    (words[idx / 64] >> (idx % 64)) & 1 == 1
+}
+
+/// Solve for all erased positions of a batch of codewords of the code defined by `h`.
+///
+/// Row `j` of `columns` holds the bits of position `j` over all codewords (ignored
+/// where `present[j]` is false). Checks with a single unresolved position are
+/// peeled first, in time linear in the edges of `h`; only the stopping set that
+/// peeling leaves goes through Gaussian elimination, so below the belief-propagation
+/// threshold the solve costs about as much as one decoding pass.
+pub fn solve_erasures(h: &SparseMatrix, present: &[bool], columns: &BitMatrix) -> ErasureSolution {
+    solve(h, present, columns, false)
+}
+
+/// [`solve_erasures`] with the syndromes and the elimination steps spread over the
+/// Rayon pool; peeling stays sequential.
+pub fn solve_erasures_parallel(
+    h: &SparseMatrix,
+    present: &[bool],
+    columns: &BitMatrix,
+) -> ErasureSolution {
+    solve(h, present, columns, true)
+}
+
+fn solve(
+    h: &SparseMatrix,
+    present: &[bool],
+    columns: &BitMatrix,
+    parallel: bool,
+) -> ErasureSolution {
+    let words = columns.words_per_row();
+    let erased: Vec<usize> = (0..present.len()).filter(|&j| !present[j]).collect();
+    let mut values: Vec<Option<Vec<u64>>> = vec![None; present.len()];
+
+    // Per check: its unresolved positions, and the XOR of its resolved ones.
+    let mut syndromes = BitMatrix::zeros(h.num_rows(), columns.cols());
+    let check_syndrome = |row: usize, syndrome: &mut [u64]| {
+        for &col in h.iter_row(row).filter(|&&col| present[col]) {
+            xor_into(syndrome, columns.row(col));
+        }
+    };
+    if parallel {
+        syndromes
+            .data_mut()
+            .par_chunks_mut(words.max(1))
+            .enumerate()
+            .for_each(|(row, syndrome)| check_syndrome(row, syndrome));
+    } else {
+        for row in 0..h.num_rows() {
+            check_syndrome(row, syndromes.row_mut(row));
+        }
+    }
+    let mut unresolved: Vec<usize> = (0..h.num_rows())
+        .map(|row| h.iter_row(row).filter(|&&col| !present[col]).count())
+        .collect();
+    let mut queue: Vec<usize> = (0..h.num_rows())
+        .filter(|&row| unresolved[row] == 1)
+        .collect();
+
+    while let Some(row) = queue.pop() {
+        // Another check may have resolved the last position of this one meanwhile.
+        let Some(&col) = h
+            .iter_row(row)
+            .find(|&&col| !present[col] && values[col].is_none())
+        else {
+            continue;
+        };
+        let value = syndromes.row(row).to_vec();
+        for &check in h.iter_col(col) {
+            xor_into(syndromes.row_mut(check), &value);
+            unresolved[check] -= 1;
+            if unresolved[check] == 1 {
+                queue.push(check);
+            }
+        }
+        values[col] = Some(value);
+    }
+
+    // Resolved checks can only confirm that the known bits are consistent; the others
+    // form the system on the stopping set, each row being
+    // [coefficients over the stopping set | syndrome of the resolved positions].
+    let stopping_set: Vec<usize> = erased
+        .iter()
+        .copied()
+        .filter(|&col| values[col].is_none())
+        .collect();
+    let mut stopping_index = vec![usize::MAX; present.len()];
+    for (idx, &col) in stopping_set.iter().enumerate() {
+        stopping_index[col] = idx;
+    }
+    let unknown_words = stopping_set.len().div_ceil(64);
+    let mut system: Vec<Vec<u64>> = Vec::new();
+    let mut inconsistent_rows = vec![0u64; words];
+    for (row, &count) in unresolved.iter().enumerate() {
+        if count == 0 {
+            or_into(&mut inconsistent_rows, syndromes.row(row));
+            continue;
+        }
+        let mut equation = vec![0u64; unknown_words + words];
+        for &col in h.iter_row(row) {
+            let idx = stopping_index[col];
+            if idx != usize::MAX {
+                equation[idx / 64] ^= 1 << (idx % 64);
+            }
+        }
+        equation[unknown_words..].copy_from_slice(syndromes.row(row));
+        system.push(equation);
+    }
+
+    let mut pivots = vec![None; stopping_set.len()];
+    let mut rank = 0;
+    for (idx, pivot) in pivots.iter_mut().enumerate() {
+        let Some(found) = (rank..system.len()).find(|&r| bit_is_set(&system[r], idx)) else {
+            continue;
+        };
+        system.swap(rank, found);
+        let pivot_row = system[rank].clone();
+        let eliminate = |(r, equation): (usize, &mut Vec<u64>)| {
+            if r != rank && bit_is_set(equation, idx) {
+                xor_into(equation, &pivot_row);
+            }
+        };
+        if parallel {
+            system.par_iter_mut().enumerate().for_each(eliminate);
+        } else {
+            system.iter_mut().enumerate().for_each(eliminate);
+        }
+        *pivot = Some(rank);
+        rank += 1;
+    }
+
+    // A pivot determines its position only if no free unknown remains in its row.
+    for (idx, pivot) in pivots.iter().enumerate() {
+        let Some(pivot) = *pivot else {
+            continue;
+        };
+        let equation = &system[pivot];
+        let determined = equation[..unknown_words]
+            .iter()
+            .enumerate()
+            .all(|(w, &word)| word == if w == idx / 64 { 1 << (idx % 64) } else { 0 });
+        if determined {
+            values[stopping_set[idx]] = Some(equation[unknown_words..].to_vec());
+        }
+    }
+
+    for equation in &system[rank..] {
+        or_into(&mut inconsistent_rows, &equation[unknown_words..]);
+    }
+
+    ErasureSolution {
+        recovered: erased.iter().map(|&col| values[col].take()).collect(),
+        erased,
+        eliminated: stopping_set.len(),
+        inconsistent_rows,
+    }
+}
+
+/// Erased positions that peeling cannot resolve, and how long it ran.
+pub struct PeelingOutcome {
+    /// Erased positions left unresolved, in increasing order: a stopping set of `h`.
+    pub unresolved: Vec<usize>,
+    /// Flooding rounds until no check had a single unresolved position left, the
+    /// erasure-channel counterpart of belief-propagation iterations.
+    pub rounds: usize,
+}
+
+/// Peel the erasure pattern: every round, each check with exactly one unresolved
+/// position resolves it. This is what belief propagation does on the erasure
+/// channel, without touching any bit values.
+pub fn peel_erasures(h: &SparseMatrix, present: &[bool]) -> PeelingOutcome {
+    let mut resolved = present.to_vec();
+    let mut open_checks: Vec<usize> = (0..h.num_rows())
+        .filter(|&row| h.iter_row(row).any(|&col| !resolved[col]))
+        .collect();
+    let mut rounds = 0;
+
+    loop {
+        let peeled: Vec<usize> = open_checks
+            .iter()
+            .filter_map(|&row| {
+                let mut unresolved = h.iter_row(row).filter(|&&col| !resolved[col]);
+                match (unresolved.next(), unresolved.next()) {
+                    (Some(&col), None) => Some(col),
+                    _ => None,
+                }
+            })
+            .collect();
+        if peeled.is_empty() {
+            break;
+        }
+        for col in peeled {
+            resolved[col] = true;
+        }
+        open_checks.retain(|&row| h.iter_row(row).any(|&col| !resolved[col]));
+        rounds += 1;
+    }
+
+    PeelingOutcome {
+        unresolved: (0..resolved.len()).filter(|&col| !resolved[col]).collect(),
+        rounds,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Parity checks of the [7,4] Hamming code.
+    fn hamming_h() -> SparseMatrix {
+        let mut h = SparseMatrix::new(3, 7);
+        for (row, cols) in [[0, 1, 3, 4], [0, 2, 3, 5], [1, 2, 3, 6]]
+            .iter()
+            .enumerate()
+        {
+            for &col in cols {
+                h.insert(row, col);
+            }
+        }
+        h
+    }
+
+    /// Two codewords, one matrix row per position: 1101100 and 0111001.
+    fn codewords() -> BitMatrix {
+        let rows = [[1, 1, 0, 1, 1, 0, 0], [0, 1, 1, 1, 0, 0, 1]];
+        let mut columns = BitMatrix::zeros(7, 2);
+        for (codeword, bits) in rows.iter().enumerate() {
+            for (col, &bit) in bits.iter().enumerate() {
+                columns.set(col, codeword, bit == 1);
+            }
+        }
+        columns
+    }
+
+    #[test]
+    fn test_recovers_erasures_for_all_rows() {
+        let h = hamming_h();
+        let columns = codewords();
+        let mut present = vec![true; 7];
+        present[0] = false;
+        present[3] = false;
+
+        for solution in [
+            solve_erasures(&h, &present, &columns),
+            solve_erasures_parallel(&h, &present, &columns),
+        ] {
+            assert_eq!(solution.erased, vec![0, 3]);
+            assert_eq!(
+                solution.eliminated, 0,
+                "check 2 peels 3, then check 0 peels 0"
+            );
+            assert_eq!(solution.column(0, &columns), Some(columns.row(0)));
+            assert_eq!(solution.column(3, &columns), Some(columns.row(3)));
+            assert_eq!(solution.inconsistent_rows, vec![0]);
+        }
+    }
+
+    #[test]
+    fn test_reports_undetermined_positions() {
+        let h = hamming_h();
+        let columns = codewords();
+        let mut present = vec![true; 7];
+        for col in [0, 1, 3, 4] {
+            present[col] = false;
+        }
+
+        let solution = solve_erasures(&h, &present, &columns);
+        assert!(solution.recovered.iter().any(Option::is_none));
+    }
+
+    #[test]
+    fn test_flags_inconsistent_rows() {
+        let h = hamming_h();
+        let mut columns = codewords();
+        columns.set(6, 1, !columns.get(6, 1));
+        let mut present = vec![true; 7];
+        present[0] = false;
+
+        let solution = solve_erasures(&h, &present, &columns);
+        assert_eq!(solution.inconsistent_rows, vec![0b10]);
+    }
+
+    #[test]
+    fn test_peeling_stops_at_stopping_sets() {
+        let h = hamming_h();
+        let mut present = vec![true; 7];
+        present[4] = false;
+        present[0] = false;
+        // Check 1 peels position 0, after which check 0 peels position 4.
+        let outcome = peel_erasures(&h, &present);
+        assert!(outcome.unresolved.is_empty());
+        assert_eq!(outcome.rounds, 2);
+
+        // Every check meets {0, 1, 2} twice or more, so peeling is stuck, but the
+        // three checks are independent on it and elimination still solves it.
+        let mut h = SparseMatrix::new(3, 5);
+        for (row, cols) in [vec![0, 1, 2, 3], vec![0, 1, 4], vec![1, 2, 3, 4]]
+            .iter()
+            .enumerate()
+        {
+            for &col in cols {
+                h.insert(row, col);
+            }
+        }
+        let present = [false, false, false, true, true];
+        let outcome = peel_erasures(&h, &present);
+        assert_eq!(outcome.unresolved, vec![0, 1, 2]);
+        assert_eq!(outcome.rounds, 0);
+        let solution = solve_erasures(&h, &present, &BitMatrix::zeros(5, 1));
+        assert_eq!(solution.eliminated, 3);
+        assert!(solution.recovered.iter().all(Option::is_some));
+    }
+}
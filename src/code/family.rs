@@ -0,0 +1,314 @@
+//! LDPC code families shipped with ldpc-toolbox.
+//!
+//! Every family names its codes by a rate and a size label, and the size labels
+//! of different families never collide (`K…` for AR4JA, `N…` for DVB-S2, `Z…`
+//! for 5G NR), so a `(rate, size)` pair identifies a code on its own.
+
+use std::hash::{Hash, Hasher};
+use std::mem::discriminant;
+
+use ldpc_toolbox::codes::ccsds::{AR4JACode, AR4JAInfoSize, AR4JARate};
+use ldpc_toolbox::codes::dvbs2::Code as DvbS2Code;
+use ldpc_toolbox::codes::nr5g::{BaseGraph, LiftingSize};
+use ldpc_toolbox::sparse::SparseMatrix;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CodeFamily {
+    /// CCSDS AR4JA codes (TM Synchronization and Channel Coding).
+    Ar4ja,
+    /// DVB-S2 codes with normal (64800-bit) and short (16200-bit) frames.
+    DvbS2,
+    /// 5G NR base graphs 1 and 2 with a lifting size.
+    Nr5g,
+}
+
+const AR4JA_RATES: &[(&str, AR4JARate)] = &[
+    ("R1_2", AR4JARate::R1_2),
+    ("R2_3", AR4JARate::R2_3),
+    ("R4_5", AR4JARate::R4_5),
+];
+
+const AR4JA_SIZES: &[(&str, AR4JAInfoSize)] = &[
+    ("K1024", AR4JAInfoSize::K1024),
+    ("K4096", AR4JAInfoSize::K4096),
+    ("K16384", AR4JAInfoSize::K16384),
+];
+
+const DVBS2_NORMAL: &str = "N64800";
+const DVBS2_SHORT: &str = "N16200";
+
+/// DVB-S2 codes by rate label, as (normal frame, short frame). Rate 9/10 has no
+/// short frame.
+const DVBS2_CODES: &[(&str, DvbS2Code, Option<DvbS2Code>)] = &[
+    ("R1_4", DvbS2Code::R1_4, Some(DvbS2Code::R1_4short)),
+    ("R1_3", DvbS2Code::R1_3, Some(DvbS2Code::R1_3short)),
+    ("R2_5", DvbS2Code::R2_5, Some(DvbS2Code::R2_5short)),
+    ("R1_2", DvbS2Code::R1_2, Some(DvbS2Code::R1_2short)),
+    ("R3_5", DvbS2Code::R3_5, Some(DvbS2Code::R3_5short)),
+    ("R2_3", DvbS2Code::R2_3, Some(DvbS2Code::R2_3short)),
+    ("R3_4", DvbS2Code::R3_4, Some(DvbS2Code::R3_4short)),
+    ("R4_5", DvbS2Code::R4_5, Some(DvbS2Code::R4_5short)),
+    ("R5_6", DvbS2Code::R5_6, Some(DvbS2Code::R5_6short)),
+    ("R8_9", DvbS2Code::R8_9, Some(DvbS2Code::R8_9short)),
+    ("R9_10", DvbS2Code::R9_10, None),
+];
+
+const NR5G_BASE_GRAPHS: &[(&str, BaseGraph)] = &[("BG1", BaseGraph::BG1), ("BG2", BaseGraph::BG2)];
+
+/// Every 5G NR lifting size, in increasing order.
+const NR5G_LIFTING_SIZES: &[LiftingSize] = {
+    use LiftingSize::*;
+    &[
+        Z2, Z3, Z4, Z5, Z6, Z7, Z8, Z9, Z10, Z11, Z12, Z13, Z14, Z15, Z16, Z18, Z20, Z22, Z24, Z26,
+        Z28, Z30, Z32, Z36, Z40, Z44, Z48, Z52, Z56, Z60, Z64, Z72, Z80, Z88, Z96, Z104, Z112,
+        Z120, Z128, Z144, Z160, Z176, Z192, Z208, Z224, Z240, Z256, Z288, Z320, Z352, Z384,
+    ]
+};
+
+fn lifting_label(lifting_size: LiftingSize) -> String {
+    format!("Z{}", usize::from(lifting_size))
+}
+
+fn lookup<T: Copy>(table: &[(&str, T)], label: &str) -> Option<T> {
+    table
+        .iter()
+        .find(|(name, _)| *name == label)
+        .map(|(_, value)| *value)
+}
+
+impl CodeFamily {
+    pub const ALL: [CodeFamily; 3] = [CodeFamily::Ar4ja, CodeFamily::DvbS2, CodeFamily::Nr5g];
+
+    /// Name used by `--codes=` and in reports.
+    pub fn name(self) -> &'static str {
+        match self {
+            CodeFamily::Ar4ja => "AR4JA",
+            CodeFamily::DvbS2 => "DVBS2",
+            CodeFamily::Nr5g => "NR5G",
+        }
+    }
+
+    pub fn parse(s: &str) -> Result<Self, String> {
+        CodeFamily::ALL
+            .into_iter()
+            .find(|family| family.name().eq_ignore_ascii_case(s))
+            .ok_or_else(|| format!("Unknown code family: {}", s))
+    }
+
+    /// Rate labels, lowest rate first. For 5G NR these are the base graphs.
+    pub fn rate_labels(self) -> Vec<&'static str> {
+        match self {
+            CodeFamily::Ar4ja => AR4JA_RATES.iter().map(|(name, _)| *name).collect(),
+            CodeFamily::DvbS2 => DVBS2_CODES.iter().map(|(name, ..)| *name).collect(),
+            CodeFamily::Nr5g => NR5G_BASE_GRAPHS.iter().map(|(name, _)| *name).collect(),
+        }
+    }
+
+    /// Size labels, smallest first: information sizes, frame lengths or lifting sizes.
+    pub fn size_labels(self) -> Vec<String> {
+        match self {
+            CodeFamily::Ar4ja => AR4JA_SIZES
+                .iter()
+                .map(|(name, _)| name.to_string())
+                .collect(),
+            CodeFamily::DvbS2 => vec![DVBS2_SHORT.to_string(), DVBS2_NORMAL.to_string()],
+            CodeFamily::Nr5g => NR5G_LIFTING_SIZES
+                .iter()
+                .map(|&size| lifting_label(size))
+                .collect(),
+        }
+    }
+
+    /// Size used when a `--codes=` entry names no size.
+    pub fn default_size(self) -> &'static str {
+        match self {
+            CodeFamily::Ar4ja => "K1024",
+            CodeFamily::DvbS2 => DVBS2_SHORT,
+            CodeFamily::Nr5g => "Z64",
+        }
+    }
+
+    /// The code of this family with the given rate and size labels.
+    pub fn code(self, rate: &str, size: &str) -> Result<BuiltinCode, String> {
+        let unknown = || format!("{} has no code {} {}", self.name(), rate, size);
+        match self {
+            CodeFamily::Ar4ja => Ok(BuiltinCode::Ar4ja {
+                rate: lookup(AR4JA_RATES, rate).ok_or_else(unknown)?,
+                info_size: lookup(AR4JA_SIZES, size).ok_or_else(unknown)?,
+            }),
+            CodeFamily::DvbS2 => {
+                let (_, normal, short) = DVBS2_CODES
+                    .iter()
+                    .find(|(name, ..)| *name == rate)
+                    .ok_or_else(unknown)?;
+                let code = match size {
+                    DVBS2_NORMAL => Some(*normal),
+                    DVBS2_SHORT => *short,
+                    _ => None,
+                };
+                code.map(BuiltinCode::DvbS2).ok_or_else(unknown)
+            }
+            CodeFamily::Nr5g => Ok(BuiltinCode::Nr5g {
+                base_graph: lookup(NR5G_BASE_GRAPHS, rate).ok_or_else(unknown)?,
+                lifting_size: NR5G_LIFTING_SIZES
+                    .iter()
+                    .copied()
+                    .find(|&lifting| lifting_label(lifting) == size)
+                    .ok_or_else(unknown)?,
+            }),
+        }
+    }
+
+    /// Every code of this family at one size, lowest rate first; rates that do not
+    /// exist at that size are skipped.
+    pub fn codes_at_size(self, size: &str) -> Vec<BuiltinCode> {
+        self.rate_labels()
+            .into_iter()
+            .filter_map(|rate| self.code(rate, size).ok())
+            .collect()
+    }
+}
+
+/// A code built into ldpc-toolbox.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BuiltinCode {
+    Ar4ja {
+        rate: AR4JARate,
+        info_size: AR4JAInfoSize,
+    },
+    DvbS2(DvbS2Code),
+    Nr5g {
+        base_graph: BaseGraph,
+        lifting_size: LiftingSize,
+    },
+}
+
+// The DVB-S2 and 5G NR enums do not implement `Hash`.
+impl Hash for BuiltinCode {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        discriminant(self).hash(state);
+        match self {
+            BuiltinCode::Ar4ja { rate, info_size } => (rate, info_size).hash(state),
+            BuiltinCode::DvbS2(code) => discriminant(code).hash(state),
+            BuiltinCode::Nr5g {
+                base_graph,
+                lifting_size,
+            } => (discriminant(base_graph), usize::from(*lifting_size)).hash(state),
+        }
+    }
+}
+
+impl BuiltinCode {
+    /// Look a code up by its labels alone; the size label determines the family.
+    pub fn from_labels(rate: &str, size: &str) -> Result<Self, String> {
+        CodeFamily::ALL
+            .into_iter()
+            .find_map(|family| family.code(rate, size).ok())
+            .ok_or_else(|| format!("Unknown code: {} {}", rate, size))
+    }
+
+    pub fn family(&self) -> CodeFamily {
+        match self {
+            BuiltinCode::Ar4ja { .. } => CodeFamily::Ar4ja,
+            BuiltinCode::DvbS2(_) => CodeFamily::DvbS2,
+            BuiltinCode::Nr5g { .. } => CodeFamily::Nr5g,
+        }
+    }
+
+    pub fn parity_check(&self) -> SparseMatrix {
+        match *self {
+            BuiltinCode::Ar4ja { rate, info_size } => AR4JACode::new(rate, info_size).h(),
+            BuiltinCode::DvbS2(code) => code.h(),
+            BuiltinCode::Nr5g {
+                base_graph,
+                lifting_size,
+            } => base_graph.h(lifting_size),
+        }
+    }
+
+    /// `(k, n)`, read off the parity-check matrix (which for AR4JA includes the
+    /// punctured columns, as every column is a share).
+    pub fn dimensions(&self) -> (usize, usize) {
+        let h = self.parity_check();
+        (h.num_cols() - h.num_rows(), h.num_cols())
+    }
+
+    pub fn rate_label(&self) -> String {
+        match *self {
+            BuiltinCode::Ar4ja { rate, .. } => format!("{:?}", rate),
+            BuiltinCode::DvbS2(code) => DVBS2_CODES
+                .iter()
+                .find(|(_, normal, short)| *normal == code || *short == Some(code))
+                .map(|(name, ..)| name.to_string())
+                .expect("every DVB-S2 code is listed"),
+            BuiltinCode::Nr5g { base_graph, .. } => format!("{:?}", base_graph),
+        }
+    }
+
+    pub fn size_label(&self) -> String {
+        match *self {
+            BuiltinCode::Ar4ja { info_size, .. } => format!("{:?}", info_size),
+            BuiltinCode::DvbS2(code) => {
+                let normal = DVBS2_CODES.iter().any(|(_, normal, _)| *normal == code);
+                String::from(if normal { DVBS2_NORMAL } else { DVBS2_SHORT })
+            }
+            BuiltinCode::Nr5g { lifting_size, .. } => lifting_label(lifting_size),
+        }
+    }
+
+    /// `FAMILY:RATE:SIZE`, the form accepted by `--codes=`.
+    pub fn spec(&self) -> String {
+        format!(
+            "{}:{}:{}",
+            self.family().name(),
+            self.rate_label(),
+            self.size_label()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_labels_roundtrip() {
+        for family in CodeFamily::ALL {
+            for size in family.size_labels() {
+                for code in family.codes_at_size(&size) {
+                    assert_eq!(code.family(), family);
+                    assert_eq!(code.size_label(), size);
+                    assert_eq!(
+                        BuiltinCode::from_labels(&code.rate_label(), &size),
+                        Ok(code)
+                    );
+                }
+            }
+        }
+        assert!(CodeFamily::DvbS2.code("R9_10", DVBS2_SHORT).is_err());
+        assert_eq!(CodeFamily::parse("dvbs2"), Ok(CodeFamily::DvbS2));
+    }
+
+    #[test]
+    fn test_dimensions_match_parity_check() {
+        let codes = [
+            BuiltinCode::Ar4ja {
+                rate: AR4JARate::R2_3,
+                info_size: AR4JAInfoSize::K1024,
+            },
+            BuiltinCode::DvbS2(DvbS2Code::R1_2short),
+            BuiltinCode::Nr5g {
+                base_graph: BaseGraph::BG1,
+                lifting_size: LiftingSize::Z8,
+            },
+            BuiltinCode::Nr5g {
+                base_graph: BaseGraph::BG2,
+                lifting_size: LiftingSize::Z15,
+            },
+        ];
+        for code in codes {
+            let (k, n) = code.dimensions();
+            assert!(0 < k && k < n, "{:?}", code);
+        }
+    }
+}
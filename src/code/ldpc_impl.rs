@@ -1,14 +1,20 @@
+use crate::bit_matrix::{xor_into, BitMatrix};
+use crate::code::alist::load_alist;
+use crate::code::family::BuiltinCode;
 use crate::code::{AdditiveCode, DecodeResult};
-use crate::types::{info_bits, CodeInitParams};
-use ldpc_toolbox::codes::ccsds::{AR4JACode, AR4JAInfoSize, AR4JARate};
+use crate::error::SchemeError;
+use crate::types::{CodeInitParams, CodeSource};
+use ldpc_toolbox::codes::ccsds::{AR4JAInfoSize, AR4JARate};
 use ldpc_toolbox::decoder::factory::{DecoderFactory, DecoderImplementation};
 use ldpc_toolbox::encoder::Encoder;
 use ldpc_toolbox::gf2::GF2;
 use ldpc_toolbox::sparse::SparseMatrix;
 use ndarray::Array1;
 use num_traits::One;
+use std::sync::OnceLock;
 
 pub struct LdpcCode {
+    params: CodeInitParams,
     h: SparseMatrix,
     encoder: Encoder,
     arithmetic: DecoderImplementation,
@@ -16,23 +22,196 @@ pub struct LdpcCode {
     llr_value: f64,
     input_length: usize,
     output_length: usize,
+    generator: OnceLock<BitMatrix>,
+    schedule: EncodingSchedule,
 }
 
-impl AdditiveCode for LdpcCode {
-    fn setup(params: CodeInitParams) -> Self {
-        let rate = params.ldpc_rate.unwrap_or(AR4JARate::R4_5);
-        let info_size = params.ldpc_info_size.unwrap_or(AR4JAInfoSize::K1024);
+/// Dense parity generator `G0 = H1^{-1} H0` of `H = [H0 | H1]`, with `H1` the last
+/// `n - k` columns: bit `j` of row `p` is set when message bit `j` feeds parity bit `p`.
+/// This is the same systematic form that `Encoder::from_h` uses.
+fn parity_generator(h: &SparseMatrix) -> Result<BitMatrix, SchemeError> {
+    let parity_bits = h.num_rows();
+    let message_bits = h.num_cols() - parity_bits;
+
+    let mut reduced = dense(h);
+
+    for pivot in 0..parity_bits {
+        let col = message_bits + pivot;
+        let found = (pivot..parity_bits)
+            .find(|&row| reduced.get(row, col))
+            .ok_or_else(|| {
+                SchemeError::EncoderConstruction(String::from("parity part of H is singular"))
+            })?;
+        reduced.swap_rows(pivot, found);
+        let pivot_row = reduced.row(pivot).to_vec();
+        for row in 0..parity_bits {
+            if row != pivot && reduced.get(row, col) {
+                xor_into(reduced.row_mut(row), &pivot_row);
+            }
+        }
+    }
+
+    Ok(reduced.leading_columns(message_bits))
+}
+
+/// Sparse encoding of all parity positions of `H = [H0 | H1]` from the message.
+///
+/// With the message known, the checks with a single parity position resolve it one
+/// after another, as in erasure peeling; IRA staircases such as DVB-S2 resolve
+/// completely. The parity positions left over (a stopping set of `H1`) follow from
+/// the syndromes of the remaining checks through a small dense `solve` matrix
+/// computed once here.
+struct EncodingSchedule {
+    /// `(check, position)` in the order peeling resolves them.
+    peeled: Vec<(usize, usize)>,
+    /// Checks still open after peeling.
+    remaining_checks: Vec<usize>,
+    /// Parity positions peeling leaves, in increasing order.
+    stopping_set: Vec<usize>,
+    /// Row `i`: the remaining checks whose syndromes add up to `stopping_set[i]`.
+    solve: BitMatrix,
+}
+
+impl EncodingSchedule {
+    fn new(h: &SparseMatrix) -> Result<Self, SchemeError> {
+        let message_bits = h.num_cols() - h.num_rows();
+        let mut resolved: Vec<bool> = (0..h.num_cols()).map(|col| col < message_bits).collect();
+        let mut unresolved: Vec<usize> = (0..h.num_rows())
+            .map(|row| h.iter_row(row).filter(|&&col| !resolved[col]).count())
+            .collect();
+        let mut queue: Vec<usize> = (0..h.num_rows())
+            .filter(|&row| unresolved[row] == 1)
+            .collect();
+        let mut peeled = Vec::new();
+
+        while let Some(row) = queue.pop() {
+            let Some(&col) = h.iter_row(row).find(|&&col| !resolved[col]) else {
+                continue;
+            };
+            resolved[col] = true;
+            peeled.push((row, col));
+            for &check in h.iter_col(col) {
+                unresolved[check] -= 1;
+                if unresolved[check] == 1 {
+                    queue.push(check);
+                }
+            }
+        }
+
+        let stopping_set: Vec<usize> = (message_bits..h.num_cols())
+            .filter(|&col| !resolved[col])
+            .collect();
+        let remaining_checks: Vec<usize> = (0..h.num_rows())
+            .filter(|&row| unresolved[row] > 0)
+            .collect();
+
+        // [coefficients over the stopping set | the remaining checks combined so far]
+        let unknowns = stopping_set.len();
+        let mut system =
+            BitMatrix::zeros(remaining_checks.len(), unknowns + remaining_checks.len());
+        for (idx, &row) in remaining_checks.iter().enumerate() {
+            for &col in h.iter_row(row) {
+                if let Ok(unknown) = stopping_set.binary_search(&col) {
+                    system.set(idx, unknown, true);
+                }
+            }
+            system.set(idx, unknowns + idx, true);
+        }
+        for pivot in 0..unknowns {
+            let found = (pivot..system.rows())
+                .find(|&row| system.get(row, pivot))
+                .ok_or_else(|| {
+                    SchemeError::EncoderConstruction(String::from("parity part of H is singular"))
+                })?;
+            system.swap_rows(pivot, found);
+            let pivot_row = system.row(pivot).to_vec();
+            for row in 0..system.rows() {
+                if row != pivot && system.get(row, pivot) {
+                    xor_into(system.row_mut(row), &pivot_row);
+                }
+            }
+        }
+
+        let mut solve = BitMatrix::zeros(unknowns, remaining_checks.len());
+        for unknown in 0..unknowns {
+            for check in 0..remaining_checks.len() {
+                solve.set(unknown, check, system.get(unknown, unknowns + check));
+            }
+        }
+
+        Ok(EncodingSchedule {
+            peeled,
+            remaining_checks,
+            stopping_set,
+            solve,
+        })
+    }
+}
+
+fn dense(h: &SparseMatrix) -> BitMatrix {
+    let mut matrix = BitMatrix::zeros(h.num_rows(), h.num_cols());
+    for (row, col) in h.iter_all() {
+        matrix.set(row, col, true);
+    }
+    matrix
+}
+
+/// Check that an externally supplied `H` has full row rank and an invertible parity
+/// part (its last `rows` columns), and return the generator built along the way.
+fn validate_parity_check(h: &SparseMatrix) -> Result<BitMatrix, SchemeError> {
+    let rank = dense(h).rank();
+    if rank < h.num_rows() {
+        return Err(SchemeError::InvalidParityCheck(format!(
+            "rank {} is below the {} rows; remove the redundant checks",
+            rank,
+            h.num_rows()
+        )));
+    }
+    parity_generator(h).map_err(|_| {
+        SchemeError::InvalidParityCheck(format!(
+            "the last {} columns are singular, so the code is not systematic-encodable; \
+             permute the columns so that they are invertible",
+            h.num_rows()
+        ))
+    })
+}
+
+impl LdpcCode {
+    /// Parameters the code was built from, with every default filled in.
+    pub fn init_params(&self) -> &CodeInitParams {
+        &self.params
+    }
+
+    pub fn parity_check(&self) -> &SparseMatrix {
+        &self.h
+    }
+
+    /// Build the code for `h`, filling in decoder defaults. `generator` may already
+    /// hold `G0` when the caller validated `h`.
+    fn from_parity_check(
+        h: SparseMatrix,
+        params: CodeInitParams,
+        generator: OnceLock<BitMatrix>,
+    ) -> Result<Self, SchemeError> {
         let arithmetic = params
             .decoder_type
             .unwrap_or(DecoderImplementation::Aminstarf32);
         let max_iterations = params.max_iterations.unwrap_or(300);
         let llr_value = params.llr_value.unwrap_or(1.3863);
-        let h = AR4JACode::new(rate, info_size).h();
-        let input_length = info_bits(info_size);
+        let input_length = h.num_cols() - h.num_rows();
         let output_length = h.num_cols();
-        let encoder = Encoder::from_h(&h).unwrap();
+        let encoder =
+            Encoder::from_h(&h).map_err(|err| SchemeError::EncoderConstruction(err.to_string()))?;
+        let schedule = EncodingSchedule::new(&h)?;
+        let params = CodeInitParams {
+            decoder_type: Some(arithmetic),
+            max_iterations: Some(max_iterations),
+            llr_value: Some(llr_value),
+            ..params
+        };
 
-        LdpcCode {
+        Ok(LdpcCode {
+            params,
             h,
             encoder,
             arithmetic,
@@ -40,7 +219,117 @@ impl AdditiveCode for LdpcCode {
             llr_value,
             input_length,
             output_length,
+            generator,
+            schedule,
+        })
+    }
+
+    /// Code for a parity-check matrix given in memory, validated like an alist file.
+    /// `params.code_source` is kept as given (e.g. the path the matrix came from).
+    pub fn with_parity_check(h: SparseMatrix, params: CodeInitParams) -> Result<Self, SchemeError> {
+        let generator = OnceLock::from(validate_parity_check(&h)?);
+        let params = CodeInitParams {
+            ldpc_rate: None,
+            ldpc_info_size: None,
+            ..params
+        };
+        LdpcCode::from_parity_check(h, params, generator)
+    }
+
+    /// Dense parity generator `G0`: bit `j` of row `p` is set when message bit `j`
+    /// feeds codeword position `k + p`.
+    ///
+    /// Built on first use; `setup` already proved `H1` invertible via `Encoder::from_h`.
+    pub fn generator(&self) -> &BitMatrix {
+        self.generator.get_or_init(|| {
+            parity_generator(&self.h).expect("H1 is invertible once Encoder::from_h succeeded")
+        })
+    }
+
+    /// Encode every row of `message` (`rows x k`) into a `rows x n` matrix.
+    ///
+    /// The message is transposed so that each message bit position holds one word
+    /// per 64 rows; a single pass over the checks of `H` then produces 64 codewords,
+    /// with only the stopping set of the encoding schedule solved densely.
+    pub fn encode_bitsliced(&self, message: &BitMatrix) -> BitMatrix {
+        let message_columns = message.transpose();
+        let mut codeword_columns = BitMatrix::zeros(self.output_length, message.rows());
+        let schedule = &self.schedule;
+
+        for col in 0..self.input_length {
+            codeword_columns
+                .row_mut(col)
+                .copy_from_slice(message_columns.row(col));
+        }
+        let mut value = vec![0u64; codeword_columns.words_per_row()];
+        for &(check, col) in &schedule.peeled {
+            value.fill(0);
+            for &other in self.h.iter_row(check).filter(|&&other| other != col) {
+                xor_into(&mut value, codeword_columns.row(other));
+            }
+            codeword_columns.row_mut(col).copy_from_slice(&value);
+        }
+
+        if !schedule.stopping_set.is_empty() {
+            let mut syndromes = BitMatrix::zeros(schedule.remaining_checks.len(), message.rows());
+            for (idx, &check) in schedule.remaining_checks.iter().enumerate() {
+                for &col in self.h.iter_row(check) {
+                    if schedule.stopping_set.binary_search(&col).is_err() {
+                        xor_into(syndromes.row_mut(idx), codeword_columns.row(col));
+                    }
+                }
+            }
+            for (unknown, &col) in schedule.stopping_set.iter().enumerate() {
+                let out = codeword_columns.row_mut(col);
+                for (word_idx, &word) in schedule.solve.row(unknown).iter().enumerate() {
+                    let mut bits = word;
+                    while bits != 0 {
+                        let check = word_idx * 64 + bits.trailing_zeros() as usize;
+                        xor_into(out, syndromes.row(check));
+                        bits &= bits - 1;
+                    }
+                }
+            }
         }
+
+        codeword_columns.transpose()
+    }
+}
+
+impl AdditiveCode for LdpcCode {
+    fn setup(params: CodeInitParams) -> Result<Self, SchemeError> {
+        let code_source = params.code_source.clone().unwrap_or_else(|| {
+            CodeSource::Builtin(BuiltinCode::Ar4ja {
+                rate: params.ldpc_rate.unwrap_or(AR4JARate::R4_5),
+                info_size: params.ldpc_info_size.unwrap_or(AR4JAInfoSize::K1024),
+            })
+        });
+        let (h, ldpc_rate, ldpc_info_size, generator) = match &code_source {
+            CodeSource::Builtin(code) => {
+                let (rate, info_size) = match *code {
+                    BuiltinCode::Ar4ja { rate, info_size } => (Some(rate), Some(info_size)),
+                    _ => (None, None),
+                };
+                (code.parity_check(), rate, info_size, OnceLock::new())
+            }
+            CodeSource::Alist(path) => {
+                let h = load_alist(path)?;
+                let generator = OnceLock::from(validate_parity_check(&h)?);
+                (h, None, None, generator)
+            }
+            CodeSource::ReedSolomon(_) => {
+                return Err(SchemeError::InvalidCodeParameters(
+                    "a Reed-Solomon code source needs ReedSolomonCode, not LdpcCode".to_string(),
+                ))
+            }
+        };
+        let params = CodeInitParams {
+            code_source: Some(code_source),
+            ldpc_rate,
+            ldpc_info_size,
+            ..params
+        };
+        LdpcCode::from_parity_check(h, params, generator)
     }
 
     fn encode(&self, message: &Array1<GF2>) -> Array1<GF2> {
@@ -70,13 +359,30 @@ impl AdditiveCode for LdpcCode {
             })
             .collect();
 
+        self.decode_soft(&llr_values)
+    }
+
+    /// Belief propagation straight from `llrs`.
+    fn decode_soft(&self, llrs: &[f64]) -> DecodeResult {
         let mut decoder = self.arithmetic.build_decoder(self.h.clone());
-        match decoder.decode(llr_values.as_slice(), self.max_iterations) {
+        match decoder.decode(llrs, self.max_iterations) {
             Ok(output) => DecodeResult::from_decoder_output(output, true),
             Err(output) => DecodeResult::from_decoder_output(output, false),
         }
     }
 
+    /// Number of unsatisfied checks of `H`.
+    fn syndrome_weight(&self, codeword: &[u8]) -> usize {
+        (0..self.h.num_rows())
+            .filter(|&row| {
+                self.h
+                    .iter_row(row)
+                    .fold(0, |parity, &col| parity ^ codeword[col])
+                    == 1
+            })
+            .count()
+    }
+
     fn input_length(&self) -> u32 {
         self.input_length as u32
     }
@@ -88,6 +394,10 @@ impl AdditiveCode for LdpcCode {
     fn max_iterations(&self) -> usize {
         self.max_iterations
     }
+
+    fn sparse_parity_check(&self) -> Option<&SparseMatrix> {
+        Some(&self.h)
+    }
 }
 
 #[cfg(test)]
@@ -99,6 +409,7 @@ mod tests {
 
     fn create_test_code() -> LdpcCode {
         let params = CodeInitParams {
+            code_source: None,
             decoder_type: Some(DecoderImplementation::Aminstarf32),
             ldpc_rate: Some(AR4JARate::R4_5),
             ldpc_info_size: Some(AR4JAInfoSize::K1024),
@@ -106,7 +417,7 @@ mod tests {
             llr_value: Some(1.3863),
             secret_bits: Some(128),
         };
-        LdpcCode::setup(params)
+        LdpcCode::setup(params).unwrap()
     }
 
     #[test]
@@ -213,12 +524,181 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_soft_decode_corrects_flipped_bits() {
+        let code = create_test_code();
+        let message: Array1<GF2> = (0..code.input_length())
+            .map(|i| if i % 3 == 0 { GF2::one() } else { GF2::zero() })
+            .collect();
+        let encoded = code.encode(&message);
+        let sent: Vec<u8> = encoded.iter().map(|bit| bit.is_one() as u8).collect();
+        assert!(code.is_codeword(&sent));
+
+        let mut llrs: Vec<f64> = sent
+            .iter()
+            .map(|&bit| if bit == 1 { -4.0 } else { 4.0 })
+            .collect();
+        for col in [5, 300, 1100] {
+            llrs[col] = -llrs[col] / 8.0;
+        }
+        let mut received = sent.clone();
+        received[300] ^= 1;
+        assert!(!code.is_codeword(&received));
+        assert_eq!(
+            code.syndrome_weight(&received),
+            code.h.iter_col(300).count()
+        );
+
+        let decode_result = code.decode_soft(&llrs);
+        assert!(decode_result.success);
+        assert_eq!(decode_result.codeword, sent);
+    }
+
+    fn family_code(code: BuiltinCode) -> LdpcCode {
+        LdpcCode::setup(CodeInitParams {
+            code_source: Some(CodeSource::Builtin(code)),
+            decoder_type: None,
+            ldpc_rate: None,
+            ldpc_info_size: None,
+            max_iterations: Some(50),
+            llr_value: None,
+            secret_bits: None,
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_bitsliced_encoding_matches_row_encoder() {
+        use crate::bit_matrix::gf2_to_words;
+        use ldpc_toolbox::codes::dvbs2::Code as DvbS2Code;
+        use ldpc_toolbox::codes::nr5g::{BaseGraph, LiftingSize};
+        use rand::rngs::StdRng;
+        use rand::{RngExt, SeedableRng};
+
+        let codes = [
+            create_test_code(),
+            family_code(BuiltinCode::DvbS2(DvbS2Code::R8_9short)),
+            family_code(BuiltinCode::Nr5g {
+                base_graph: BaseGraph::BG2,
+                lifting_size: LiftingSize::Z8,
+            }),
+        ];
+        assert!(!codes[0].schedule.stopping_set.is_empty());
+        assert!(codes[1].schedule.stopping_set.is_empty(), "staircases peel");
+
+        let mut rng = StdRng::seed_from_u64(1);
+        let rows = 70;
+        for code in &codes {
+            let mut message = BitMatrix::zeros(rows, code.input_length() as usize);
+            for row in 0..rows {
+                for col in 0..code.input_length() as usize {
+                    message.set(row, col, rng.random());
+                }
+            }
+
+            let encoded = code.encode_bitsliced(&message);
+            assert_eq!(encoded.cols(), code.output_length() as usize);
+            let mut expected = vec![0; encoded.words_per_row()];
+            for row in 0..rows {
+                gf2_to_words(&code.encode(&message.row_to_gf2(row)), &mut expected);
+                assert_eq!(encoded.row(row), &expected[..], "row {}", row);
+            }
+        }
+    }
+
+    #[test]
+    fn test_family_code_records_its_source() {
+        use ldpc_toolbox::codes::dvbs2::Code as DvbS2Code;
+
+        let builtin = BuiltinCode::DvbS2(DvbS2Code::R8_9short);
+        let code = family_code(builtin);
+        assert_eq!(
+            code.init_params().code_source,
+            Some(CodeSource::Builtin(builtin))
+        );
+        assert_eq!(code.init_params().ldpc_rate, None);
+        assert_eq!(
+            (code.input_length() as usize, code.output_length() as usize),
+            builtin.dimensions()
+        );
+    }
+
+    fn write_alist(name: &str, h: &SparseMatrix) -> std::path::PathBuf {
+        let path = std::env::temp_dir().join(format!("{}_{}.alist", name, std::process::id()));
+        std::fs::write(&path, h.alist()).unwrap();
+        path
+    }
+
+    fn alist_params(path: std::path::PathBuf) -> CodeInitParams {
+        CodeInitParams {
+            code_source: Some(CodeSource::Alist(path)),
+            decoder_type: None,
+            ldpc_rate: None,
+            ldpc_info_size: None,
+            max_iterations: None,
+            llr_value: None,
+            secret_bits: None,
+        }
+    }
+
+    #[test]
+    fn test_alist_code_matches_builtin() {
+        let builtin = create_test_code();
+        let path = write_alist("ldpc_impl_ar4ja", builtin.parity_check());
+        let code = LdpcCode::setup(alist_params(path.clone())).unwrap();
+        std::fs::remove_file(path).unwrap();
+
+        assert_eq!(code.input_length(), builtin.input_length());
+        assert_eq!(code.output_length(), builtin.output_length());
+        assert_eq!(code.init_params().ldpc_rate, None);
+
+        let mut message = Array1::from(vec![GF2::zero(); code.input_length() as usize]);
+        message[3] = GF2::one();
+        message[700] = GF2::one();
+        assert_eq!(code.encode(&message), builtin.encode(&message));
+    }
+
+    #[test]
+    fn test_alist_rejects_unusable_matrices() {
+        // Third check is the sum of the first two: rank 2 of 3.
+        let mut redundant = SparseMatrix::new(3, 6);
+        for (row, col) in [
+            (0, 0),
+            (0, 3),
+            (1, 1),
+            (1, 4),
+            (2, 0),
+            (2, 1),
+            (2, 3),
+            (2, 4),
+        ] {
+            redundant.insert(row, col);
+        }
+        // Full rank, but the last two columns are equal.
+        let mut singular = SparseMatrix::new(2, 4);
+        for (row, col) in [(0, 0), (0, 2), (0, 3), (1, 1)] {
+            singular.insert(row, col);
+        }
+
+        for (name, h) in [("redundant", redundant), ("singular", singular)] {
+            let path = write_alist(name, &h);
+            let result = LdpcCode::setup(alist_params(path.clone()));
+            std::fs::remove_file(path).unwrap();
+            assert!(
+                matches!(result, Err(SchemeError::InvalidParityCheck(_))),
+                "{} matrix accepted",
+                name
+            );
+        }
+    }
+
     #[test]
     fn test_ldpc_different_rates() {
         let rates = [AR4JARate::R1_2, AR4JARate::R2_3, AR4JARate::R4_5];
 
         for rate in rates.iter() {
             let params = CodeInitParams {
+                code_source: None,
                 decoder_type: Some(DecoderImplementation::Aminstarf32),
                 ldpc_rate: Some(*rate),
                 ldpc_info_size: Some(AR4JAInfoSize::K1024),
@@ -227,7 +707,7 @@ mod tests {
                 secret_bits: Some(128),
             };
 
-            let code = LdpcCode::setup(params);
+            let code = LdpcCode::setup(params).unwrap();
 
             assert_eq!(code.input_length(), 1024);
             assert!(code.output_length() > code.input_length());
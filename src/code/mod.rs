@@ -1,8 +1,14 @@
+pub mod alist;
+pub mod erasure;
+pub mod family;
 pub mod ldpc_impl;
+pub mod reed_solomon;
 
 use ldpc_toolbox::decoder::DecoderOutput;
 use ldpc_toolbox::gf2::GF2;
+use ldpc_toolbox::sparse::SparseMatrix;
 use ndarray::Array1;
+use num_traits::{One, Zero};
 
 #[derive(Debug, Clone)]
 pub struct DecodeResult {
@@ -22,10 +28,48 @@ impl DecodeResult {
 }
 
 pub trait AdditiveCode {
-    fn setup(params: crate::types::CodeInitParams) -> Self;
+    fn setup(params: crate::types::CodeInitParams) -> Result<Self, crate::error::SchemeError>
+    where
+        Self: Sized;
     fn encode(&self, input: &Array1<GF2>) -> Array1<GF2>;
     fn decode(&self, input: &Array1<GF2>, present_positions: &[bool]) -> DecodeResult;
     fn input_length(&self) -> u32;
     fn output_length(&self) -> u32;
     fn max_iterations(&self) -> usize;
+
+    /// Decode from per-bit LLRs, positive favouring 0 and 0 marking an erasure.
+    ///
+    /// Without a soft decoder every non-zero LLR is taken as a present hard bit.
+    fn decode_soft(&self, llrs: &[f64]) -> DecodeResult {
+        let present: Vec<bool> = llrs.iter().map(|&llr| llr != 0.0).collect();
+        let input: Array1<GF2> = llrs
+            .iter()
+            .map(|&llr| if llr < 0.0 { GF2::one() } else { GF2::zero() })
+            .collect();
+        self.decode(&input, &present)
+    }
+
+    /// Residual syndrome weight of `codeword` (one `u8` per bit): 0 exactly for
+    /// codewords. The default re-encodes the leading `input_length` bits, which holds
+    /// for systematic codes, and counts the output bits that disagree.
+    fn syndrome_weight(&self, codeword: &[u8]) -> usize {
+        let message: Array1<GF2> = codeword[..self.input_length() as usize]
+            .iter()
+            .map(|&bit| if bit == 1 { GF2::one() } else { GF2::zero() })
+            .collect();
+        self.encode(&message)
+            .iter()
+            .zip(codeword)
+            .filter(|(bit, &received)| bit.is_one() != (received == 1))
+            .count()
+    }
+
+    fn is_codeword(&self, codeword: &[u8]) -> bool {
+        self.syndrome_weight(codeword) == 0
+    }
+
+    /// Sparse parity-check matrix of codes decoded on one, such as LDPC codes.
+    fn sparse_parity_check(&self) -> Option<&SparseMatrix> {
+        None
+    }
 }
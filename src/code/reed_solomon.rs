@@ -0,0 +1,457 @@
+//! Reed–Solomon codes over GF(2^m), the MDS baseline for the LDPC codes.
+//!
+//! The code is used through its binary image, so deal and reconstruct treat it
+//! exactly like an LDPC code: symbol `s` of a codeword occupies bits
+//! `s*m..(s+1)*m`, least significant bit first. A symbol with any missing bit is
+//! erased, and any `k` complete symbols determine the message.
+//!
+//! Codewords are systematic, message symbols first. Symbol `s < k` is the
+//! coefficient of `x^(n-k+s)` and parity symbol `s >= k` that of `x^(s-k)`, with
+//! generator `g(x) = (x - α)(x - α^2)…(x - α^(n-k))`.
+
+use ldpc_toolbox::gf2::GF2;
+use ndarray::Array1;
+use num_traits::{One, Zero};
+
+use crate::code::{AdditiveCode, DecodeResult};
+use crate::error::SchemeError;
+use crate::types::{CodeInitParams, CodeSource};
+
+/// Primitive polynomials for GF(2^m), indexed by `m`.
+const PRIMITIVE_POLYNOMIALS: [u32; 17] = [
+    0, 0, 0x7, 0xB, 0x13, 0x25, 0x43, 0x89, 0x11D, 0x211, 0x409, 0x805, 0x1053, 0x201B, 0x4443,
+    0x8003, 0x1100B,
+];
+
+/// Symbol size and length of a Reed–Solomon code, in symbols.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ReedSolomonParams {
+    /// `m`, between 2 and 16.
+    pub symbol_bits: usize,
+    /// Codeword length, at most `2^m - 1` (shorter codes are shortened).
+    pub n: usize,
+    /// Message length.
+    pub k: usize,
+}
+
+impl Default for ReedSolomonParams {
+    /// RS(255, 223) over GF(2^8).
+    fn default() -> Self {
+        ReedSolomonParams {
+            symbol_bits: 8,
+            n: 255,
+            k: 223,
+        }
+    }
+}
+
+impl ReedSolomonParams {
+    pub fn validate(&self) -> Result<(), SchemeError> {
+        let invalid = |reason: String| Err(SchemeError::InvalidCodeParameters(reason));
+        if !(2..=16).contains(&self.symbol_bits) {
+            return invalid(format!(
+                "Reed-Solomon symbols must have 2 to 16 bits, not {}",
+                self.symbol_bits
+            ));
+        }
+        let max_n = (1 << self.symbol_bits) - 1;
+        if self.n > max_n {
+            return invalid(format!(
+                "Reed-Solomon length {} exceeds {} over GF(2^{})",
+                self.n, max_n, self.symbol_bits
+            ));
+        }
+        if self.k == 0 || self.k >= self.n {
+            return invalid(format!(
+                "Reed-Solomon needs 0 < k < n, got k={} n={}",
+                self.k, self.n
+            ));
+        }
+        Ok(())
+    }
+
+    /// Rate column of reports, e.g. `N255_K223`.
+    pub fn rate_label(&self) -> String {
+        format!("N{}_K{}", self.n, self.k)
+    }
+
+    /// Size column of reports, e.g. `M8`.
+    pub fn size_label(&self) -> String {
+        format!("M{}", self.symbol_bits)
+    }
+
+    /// Inverse of [`rate_label`](Self::rate_label) and [`size_label`](Self::size_label).
+    pub fn from_labels(rate: &str, size: &str) -> Result<Self, String> {
+        let malformed = || format!("Unknown Reed-Solomon code: {} {}", rate, size);
+        let (n, k) = rate
+            .strip_prefix('N')
+            .and_then(|rest| rest.split_once("_K"))
+            .ok_or_else(malformed)?;
+        let params = ReedSolomonParams {
+            symbol_bits: size
+                .strip_prefix('M')
+                .and_then(|bits| bits.parse().ok())
+                .ok_or_else(malformed)?,
+            n: n.parse().map_err(|_| malformed())?,
+            k: k.parse().map_err(|_| malformed())?,
+        };
+        params.validate().map_err(|err| err.to_string())?;
+        Ok(params)
+    }
+
+    /// `(k, n)` of the binary image.
+    pub fn dimensions(&self) -> (usize, usize) {
+        (self.k * self.symbol_bits, self.n * self.symbol_bits)
+    }
+}
+
+/// GF(2^m) arithmetic through log and antilog tables.
+struct GaloisField {
+    order: usize,
+    exp: Vec<u16>,
+    log: Vec<u16>,
+}
+
+impl GaloisField {
+    fn new(bits: usize) -> Self {
+        let order = (1 << bits) - 1;
+        let mut exp = vec![0u16; 2 * order];
+        let mut log = vec![0u16; order + 1];
+        let mut value = 1u32;
+        for (power, slot) in exp[..order].iter_mut().enumerate() {
+            *slot = value as u16;
+            log[value as usize] = power as u16;
+            value <<= 1;
+            if value > order as u32 {
+                value ^= PRIMITIVE_POLYNOMIALS[bits];
+            }
+        }
+        let (low, high) = exp.split_at_mut(order);
+        high.copy_from_slice(low);
+        GaloisField { order, exp, log }
+    }
+
+    fn mul(&self, a: u16, b: u16) -> u16 {
+        if a == 0 || b == 0 {
+            0
+        } else {
+            self.exp[self.log[a as usize] as usize + self.log[b as usize] as usize]
+        }
+    }
+
+    fn div(&self, a: u16, b: u16) -> u16 {
+        debug_assert_ne!(b, 0, "division by zero in GF(2^m)");
+        if a == 0 {
+            0
+        } else {
+            self.exp[self.log[a as usize] as usize + self.order - self.log[b as usize] as usize]
+        }
+    }
+
+    /// `α^power`.
+    fn alpha(&self, power: usize) -> u16 {
+        self.exp[power % self.order]
+    }
+
+    /// Evaluate `poly` (lowest degree first) at `x`.
+    fn eval(&self, poly: &[u16], x: u16) -> u16 {
+        poly.iter()
+            .rev()
+            .fold(0, |acc, &coeff| self.mul(acc, x) ^ coeff)
+    }
+}
+
+pub struct ReedSolomonCode {
+    params: ReedSolomonParams,
+    field: GaloisField,
+    /// `g(x)` without its leading one, lowest degree first.
+    generator: Vec<u16>,
+}
+
+impl ReedSolomonCode {
+    pub fn new(params: ReedSolomonParams) -> Result<Self, SchemeError> {
+        params.validate()?;
+        let field = GaloisField::new(params.symbol_bits);
+        let parity = params.n - params.k;
+
+        let mut generator = vec![1u16];
+        for root in 1..=parity {
+            let alpha = field.alpha(root);
+            let mut next = vec![0u16; generator.len() + 1];
+            for (degree, &coeff) in generator.iter().enumerate() {
+                next[degree + 1] ^= coeff;
+                next[degree] ^= field.mul(coeff, alpha);
+            }
+            generator = next;
+        }
+        generator.pop();
+
+        Ok(ReedSolomonCode {
+            params,
+            field,
+            generator,
+        })
+    }
+
+    pub fn params(&self) -> ReedSolomonParams {
+        self.params
+    }
+
+    fn parity_symbols(&self) -> usize {
+        self.params.n - self.params.k
+    }
+
+    /// Polynomial degree of codeword symbol `symbol`.
+    fn degree(&self, symbol: usize) -> usize {
+        if symbol < self.params.k {
+            self.parity_symbols() + symbol
+        } else {
+            symbol - self.params.k
+        }
+    }
+
+    fn symbols_from_bits(&self, bits: impl Iterator<Item = bool>, count: usize) -> Vec<u16> {
+        let m = self.params.symbol_bits;
+        let mut symbols = vec![0u16; count];
+        for (idx, bit) in bits.enumerate() {
+            if bit {
+                symbols[idx / m] |= 1 << (idx % m);
+            }
+        }
+        symbols
+    }
+
+    fn symbols_to_bits<'a>(&self, symbols: &'a [u16]) -> impl Iterator<Item = bool> + 'a {
+        let m = self.params.symbol_bits;
+        (0..symbols.len() * m).map(move |idx| (symbols[idx / m] >> (idx % m)) & 1 == 1)
+    }
+
+    /// Systematic codeword symbols of `message`.
+    fn encode_symbols(&self, message: &[u16]) -> Vec<u16> {
+        let parity = self.parity_symbols();
+        let mut remainder = vec![0u16; parity];
+        for &symbol in message.iter().rev() {
+            let feedback = symbol ^ remainder[parity - 1];
+            for degree in (1..parity).rev() {
+                remainder[degree] =
+                    remainder[degree - 1] ^ self.field.mul(feedback, self.generator[degree]);
+            }
+            remainder[0] = self.field.mul(feedback, self.generator[0]);
+        }
+        let mut codeword = message.to_vec();
+        codeword.extend(remainder);
+        codeword
+    }
+
+    /// Fill in the erased symbols of `codeword` (zero on input) with Forney's formula.
+    fn fill_erasures(&self, codeword: &mut [u16], erased: &[usize]) {
+        let field = &self.field;
+        let parity = self.parity_symbols();
+
+        let mut poly = vec![0u16; self.params.n];
+        for (symbol, &value) in codeword.iter().enumerate() {
+            poly[self.degree(symbol)] = value;
+        }
+        let syndromes: Vec<u16> = (1..=parity)
+            .map(|root| field.eval(&poly, field.alpha(root)))
+            .collect();
+
+        // Erasure locator Λ(x) = ∏ (1 + X x) over the erased locations X = α^degree.
+        let mut locator = vec![1u16];
+        for &symbol in erased {
+            let location = field.alpha(self.degree(symbol));
+            let mut next = vec![0u16; locator.len() + 1];
+            for (degree, &coeff) in locator.iter().enumerate() {
+                next[degree] ^= coeff;
+                next[degree + 1] ^= field.mul(coeff, location);
+            }
+            locator = next;
+        }
+
+        // Ω(x) = S(x) Λ(x) mod x^(n-k).
+        let mut evaluator = vec![0u16; parity];
+        for (i, &syndrome) in syndromes.iter().enumerate() {
+            for (j, &coeff) in locator.iter().enumerate().take(parity - i) {
+                evaluator[i + j] ^= field.mul(syndrome, coeff);
+            }
+        }
+        // Formal derivative: only odd-degree terms survive in characteristic 2.
+        let derivative: Vec<u16> = locator
+            .iter()
+            .enumerate()
+            .skip(1)
+            .map(|(degree, &coeff)| if degree % 2 == 1 { coeff } else { 0 })
+            .collect();
+
+        for &symbol in erased {
+            let degree = self.degree(symbol);
+            let inverse = field.alpha(field.order - degree % field.order);
+            codeword[symbol] = field.div(
+                field.eval(&evaluator, inverse),
+                field.eval(&derivative, inverse),
+            );
+        }
+    }
+}
+
+impl AdditiveCode for ReedSolomonCode {
+    /// Requires `params.code_source` to be [`CodeSource::ReedSolomon`].
+    fn setup(params: CodeInitParams) -> Result<Self, SchemeError> {
+        match params.code_source {
+            Some(CodeSource::ReedSolomon(rs)) => ReedSolomonCode::new(rs),
+            other => Err(SchemeError::InvalidCodeParameters(format!(
+                "Reed-Solomon setup needs a Reed-Solomon code source, got {:?}",
+                other
+            ))),
+        }
+    }
+
+    fn encode(&self, input: &Array1<GF2>) -> Array1<GF2> {
+        let message = self.symbols_from_bits(input.iter().map(GF2::is_one), self.params.k);
+        self.symbols_to_bits(&self.encode_symbols(&message))
+            .map(|bit| if bit { GF2::one() } else { GF2::zero() })
+            .collect()
+    }
+
+    /// Algebraic erasure decoding; it succeeds exactly when at most `n - k` symbols
+    /// are erased. No iterations are reported.
+    fn decode(&self, input: &Array1<GF2>, present_positions: &[bool]) -> DecodeResult {
+        let m = self.params.symbol_bits;
+        let erased: Vec<usize> = (0..self.params.n)
+            .filter(|&symbol| {
+                !present_positions[symbol * m..(symbol + 1) * m]
+                    .iter()
+                    .all(|&p| p)
+            })
+            .collect();
+        let known = input
+            .iter()
+            .zip(present_positions)
+            .map(|(bit, &present)| present && bit.is_one());
+        let mut codeword = self.symbols_from_bits(known, self.params.n);
+
+        let success = erased.len() <= self.parity_symbols();
+        if success && !erased.is_empty() {
+            for &symbol in &erased {
+                codeword[symbol] = 0;
+            }
+            self.fill_erasures(&mut codeword, &erased);
+        }
+
+        DecodeResult {
+            codeword: self.symbols_to_bits(&codeword).map(u8::from).collect(),
+            iterations: 0,
+            success,
+        }
+    }
+
+    fn input_length(&self) -> u32 {
+        self.params.dimensions().0 as u32
+    }
+
+    fn output_length(&self) -> u32 {
+        self.params.dimensions().1 as u32
+    }
+
+    /// One algebraic pass, which the zero reported iterations never reach.
+    fn max_iterations(&self) -> usize {
+        1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::{RngExt, SeedableRng};
+
+    fn random_message(code: &ReedSolomonCode, rng: &mut StdRng) -> Array1<GF2> {
+        (0..code.input_length())
+            .map(|_| {
+                if rng.random() {
+                    GF2::one()
+                } else {
+                    GF2::zero()
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_field_tables_cover_every_element() {
+        for bits in 2..=16 {
+            let field = GaloisField::new(bits);
+            let mut seen = vec![false; field.order + 1];
+            for power in 0..field.order {
+                seen[field.exp[power] as usize] = true;
+            }
+            assert!(seen[1..].iter().all(|&s| s), "GF(2^{}) polynomial", bits);
+            assert_eq!(field.mul(field.div(3, 2), 2), 3);
+        }
+    }
+
+    #[test]
+    fn test_codewords_vanish_at_generator_roots() {
+        let code = ReedSolomonCode::new(ReedSolomonParams::default()).unwrap();
+        let message: Vec<u16> = (0..223).map(|i| (i * 37 % 256) as u16).collect();
+        let codeword = code.encode_symbols(&message);
+        assert_eq!(&codeword[..223], &message[..]);
+
+        let mut poly = vec![0u16; 255];
+        for (symbol, &value) in codeword.iter().enumerate() {
+            poly[code.degree(symbol)] = value;
+        }
+        for root in 1..=32 {
+            assert_eq!(code.field.eval(&poly, code.field.alpha(root)), 0);
+        }
+    }
+
+    #[test]
+    fn test_recovers_up_to_n_minus_k_erased_symbols() {
+        let params = ReedSolomonParams {
+            symbol_bits: 5,
+            n: 20,
+            k: 12,
+        };
+        let code = ReedSolomonCode::new(params).unwrap();
+        let mut rng = StdRng::seed_from_u64(3);
+
+        for erased_symbols in [1, 4, 8, 9] {
+            let message = random_message(&code, &mut rng);
+            let codeword = code.encode(&message);
+            let mut present = vec![true; codeword.len()];
+            // Erase one bit of each chosen symbol: the whole symbol counts as lost.
+            for symbol in 0..erased_symbols {
+                let symbol = (symbol * 7) % params.n;
+                present[symbol * params.symbol_bits + symbol % params.symbol_bits] = false;
+            }
+
+            let result = code.decode(&codeword, &present);
+            assert_eq!(result.success, erased_symbols <= 8);
+            if result.success {
+                let decoded: Vec<u8> = codeword.iter().map(|bit| bit.is_one() as u8).collect();
+                assert!(code.is_codeword(&decoded));
+                let mut corrupted = decoded.clone();
+                corrupted[0] ^= 1;
+                assert!(!code.is_codeword(&corrupted));
+                assert_eq!(result.codeword, decoded);
+            }
+        }
+    }
+
+    #[test]
+    fn test_rejects_invalid_parameters() {
+        for (symbol_bits, n, k) in [(1, 3, 1), (17, 10, 5), (4, 16, 8), (8, 10, 10), (8, 10, 0)] {
+            let params = ReedSolomonParams { symbol_bits, n, k };
+            assert!(matches!(
+                ReedSolomonCode::new(params),
+                Err(SchemeError::InvalidCodeParameters(_))
+            ));
+        }
+        let params = ReedSolomonParams::default();
+        assert_eq!(
+            ReedSolomonParams::from_labels(&params.rate_label(), &params.size_label()),
+            Ok(params)
+        );
+    }
+}
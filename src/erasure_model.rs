@@ -0,0 +1,323 @@
+//! Which shares a benchmark run loses.
+//!
+//! [`remove_random_shares`] models independent loss. Real outages are correlated,
+//! and an adversary picks the columns that hurt the decoder most; every
+//! [`ErasureModel`] removes the same number of shares, so the models compare
+//! directly against each other.
+
+use std::fmt::{Display, Formatter};
+
+use ldpc_toolbox::sparse::SparseMatrix;
+use rand::rngs::StdRng;
+use rand::seq::{IndexedRandom, SliceRandom};
+use rand::{Rng, RngExt, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+use crate::access::AccessLayout;
+use crate::code::AdditiveCode;
+use crate::types::Share;
+use crate::utils::remove_random_shares;
+
+/// Random starts tried for each stopping set of [`ErasureModel::Adversarial`].
+const ADVERSARIAL_TRIES: usize = 8;
+
+/// Pattern of the shares removed from a deal.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum ErasureModel {
+    /// Independent loss: a uniformly random subset.
+    #[default]
+    Uniform,
+    /// One contiguous, cyclic run of column indices from a random start, like a
+    /// site holding an index range going down.
+    Burst,
+    /// Random systematic (message) columns only.
+    SystematicOnly,
+    /// Random parity columns only.
+    ParityOnly,
+    /// Whole participants of an interleaved layout with this many participants, as
+    /// grouped by [`AccessLayout::numbered`], in random order.
+    Groups(usize),
+    /// Stopping sets of `H`, which belief propagation cannot recover however many
+    /// other shares are present. Codes without a sparse `H` lose uniform shares.
+    Adversarial,
+}
+
+impl ErasureModel {
+    /// The `--erasure=` value naming this model.
+    pub fn label(&self) -> String {
+        match self {
+            ErasureModel::Uniform => String::from("uniform"),
+            ErasureModel::Burst => String::from("burst"),
+            ErasureModel::SystematicOnly => String::from("systematic"),
+            ErasureModel::ParityOnly => String::from("parity"),
+            ErasureModel::Groups(count) => format!("groups:{}", count),
+            ErasureModel::Adversarial => String::from("adversarial"),
+        }
+    }
+
+    /// Inverse of [`Self::label`].
+    pub fn parse(s: &str) -> Result<Self, String> {
+        match s {
+            "uniform" => Ok(ErasureModel::Uniform),
+            "burst" => Ok(ErasureModel::Burst),
+            "systematic" => Ok(ErasureModel::SystematicOnly),
+            "parity" => Ok(ErasureModel::ParityOnly),
+            "adversarial" => Ok(ErasureModel::Adversarial),
+            _ => match s.strip_prefix("groups:").map(str::parse::<usize>) {
+                Some(Ok(count)) if count > 0 => Ok(ErasureModel::Groups(count)),
+                _ => Err(format!("Unknown erasure model: {}", s)),
+            },
+        }
+    }
+
+    /// Remove shares following this model, counted like [`remove_random_shares`]:
+    /// positive values are counts, negative values percentages of `shares`. Models
+    /// restricted to some columns remove all of them when there are fewer.
+    pub fn erase<C: AdditiveCode>(
+        &self,
+        code: &C,
+        shares: &mut Vec<Share>,
+        num_to_remove: isize,
+        seed: Option<u64>,
+    ) {
+        if *self == ErasureModel::Uniform {
+            remove_random_shares(shares, num_to_remove, seed);
+            return;
+        }
+        let count = if num_to_remove < 0 {
+            (shares.len() as f64 * (-num_to_remove) as f64 / 100.0).round() as usize
+        } else {
+            num_to_remove as usize
+        };
+        let mut rng = match seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_rng(&mut rand::rng()),
+        };
+
+        let output_length = code.output_length() as usize;
+        let mut present = vec![false; output_length];
+        for share in shares.iter() {
+            if let Some(slot) = present.get_mut(share.i as usize) {
+                *slot = true;
+            }
+        }
+        let mut erased = vec![false; output_length];
+        for col in self.erasure_order(code, &present, count, &mut rng) {
+            erased[col] = true;
+        }
+        shares.retain(|share| !erased.get(share.i as usize).copied().unwrap_or(false));
+    }
+
+    /// Up to `count` present columns to erase, most important first.
+    fn erasure_order<C: AdditiveCode, R: Rng + ?Sized>(
+        &self,
+        code: &C,
+        present: &[bool],
+        count: usize,
+        rng: &mut R,
+    ) -> Vec<usize> {
+        let output_length = present.len();
+        let input_length = code.input_length() as usize;
+        let shuffled = |range: std::ops::Range<usize>, rng: &mut R| {
+            let mut cols: Vec<usize> = range.filter(|&col| present[col]).collect();
+            cols.shuffle(rng);
+            cols
+        };
+
+        let mut order = match self {
+            ErasureModel::Uniform => shuffled(0..output_length, rng),
+            ErasureModel::Burst => {
+                let start = rng.random_range(0..output_length.max(1));
+                (start..output_length)
+                    .chain(0..start)
+                    .filter(|&col| present[col])
+                    .collect()
+            }
+            ErasureModel::SystematicOnly => shuffled(0..input_length, rng),
+            ErasureModel::ParityOnly => shuffled(input_length..output_length, rng),
+            ErasureModel::Groups(groups) => {
+                let layout = AccessLayout::numbered(*groups, output_length as u32);
+                let mut participants: Vec<_> = layout.participants().iter().collect();
+                participants.shuffle(rng);
+                participants
+                    .into_iter()
+                    .flat_map(|participant| &participant.columns)
+                    .map(|&col| col as usize)
+                    .filter(|&col| present[col])
+                    .collect()
+            }
+            ErasureModel::Adversarial => match code.sparse_parity_check() {
+                Some(h) => stopping_set_order(h, present, count, rng),
+                None => shuffled(0..output_length, rng),
+            },
+        };
+        order.truncate(count);
+        order
+    }
+}
+
+impl Display for ErasureModel {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.label())
+    }
+}
+
+impl From<ErasureModel> for String {
+    fn from(model: ErasureModel) -> Self {
+        model.label()
+    }
+}
+
+impl TryFrom<String> for ErasureModel {
+    type Error = String;
+
+    fn try_from(label: String) -> Result<Self, Self::Error> {
+        ErasureModel::parse(&label)
+    }
+}
+
+/// Present columns whose erasure, together with the columns already missing,
+/// leaves stopping sets of `h`: the smallest of a few greedily grown sets at a
+/// time, until `count` columns are chosen.
+fn stopping_set_order<R: Rng + ?Sized>(
+    h: &SparseMatrix,
+    present: &[bool],
+    count: usize,
+    rng: &mut R,
+) -> Vec<usize> {
+    let mut erased: Vec<bool> = present.iter().map(|&p| !p).collect();
+    let mut order = Vec::new();
+    while order.len() < count {
+        let starts: Vec<usize> = (0..present.len()).filter(|&col| !erased[col]).collect();
+        let mut smallest: Option<Vec<usize>> = None;
+        for _ in 0..ADVERSARIAL_TRIES {
+            let Some(&start) = starts.choose(rng) else {
+                break;
+            };
+            let set = grow_stopping_set(h, &erased, start, rng);
+            if smallest.as_ref().is_none_or(|best| set.len() < best.len()) {
+                smallest = Some(set);
+            }
+        }
+        let Some(smallest) = smallest else {
+            break;
+        };
+        for &col in &smallest {
+            erased[col] = true;
+        }
+        order.extend(smallest);
+    }
+    order
+}
+
+/// Grow a stopping set from `start`: while some checks hold exactly one column of
+/// the set, add the column that closes the most of them net of the checks it
+/// newly touches, preferring columns that are already erased. Returns the columns
+/// added that were not erased yet.
+///
+/// Every check touching the result holds at least two of its columns, so peeling,
+/// and belief propagation with it, never recovers any of them.
+fn grow_stopping_set<R: Rng + ?Sized>(
+    h: &SparseMatrix,
+    erased: &[bool],
+    start: usize,
+    rng: &mut R,
+) -> Vec<usize> {
+    let mut in_set = vec![false; erased.len()];
+    let mut set_columns_per_check = vec![0usize; h.num_rows()];
+    let mut open_checks: Vec<usize> = Vec::new();
+    let mut added = Vec::new();
+    let mut next = Some(start);
+
+    while let Some(col) = next {
+        in_set[col] = true;
+        if !erased[col] {
+            added.push(col);
+        }
+        for &row in h.iter_col(col) {
+            set_columns_per_check[row] += 1;
+            if set_columns_per_check[row] == 1 {
+                open_checks.push(row);
+            }
+        }
+        open_checks.retain(|&row| set_columns_per_check[row] == 1);
+
+        let mut candidates: Vec<usize> = open_checks
+            .iter()
+            .flat_map(|&row| h.iter_row(row).copied())
+            .filter(|&other| !in_set[other])
+            .collect();
+        candidates.sort_unstable();
+        candidates.dedup();
+        candidates.shuffle(rng);
+        next = candidates.into_iter().max_by_key(|&other| {
+            let (closed, opened) = h.iter_col(other).fold((0i64, 0i64), |(c, o), &row| {
+                match set_columns_per_check[row] {
+                    0 => (c, o + 1),
+                    1 => (c + 1, o),
+                    _ => (c, o),
+                }
+            });
+            (closed - opened, erased[other])
+        });
+    }
+    added
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::code::erasure::peel_erasures;
+
+    #[test]
+    fn test_labels_roundtrip() {
+        for model in [
+            ErasureModel::Uniform,
+            ErasureModel::Burst,
+            ErasureModel::SystematicOnly,
+            ErasureModel::ParityOnly,
+            ErasureModel::Groups(7),
+            ErasureModel::Adversarial,
+        ] {
+            assert_eq!(ErasureModel::parse(&model.label()), Ok(model));
+        }
+        assert!(ErasureModel::parse("groups:0").is_err());
+        assert!(ErasureModel::parse("bursty").is_err());
+    }
+
+    /// Parity checks of the [7,4] Hamming code.
+    fn hamming_h() -> SparseMatrix {
+        let mut h = SparseMatrix::new(3, 7);
+        for (row, cols) in [[0, 1, 3, 4], [0, 2, 3, 5], [1, 2, 3, 6]]
+            .iter()
+            .enumerate()
+        {
+            for &col in cols {
+                h.insert(row, col);
+            }
+        }
+        h
+    }
+
+    #[test]
+    fn test_grown_sets_defeat_peeling() {
+        let h = hamming_h();
+        let present = vec![true; 7];
+        let mut rng = StdRng::seed_from_u64(1);
+        for start in 0..7 {
+            let set = grow_stopping_set(&h, &[false; 7], start, &mut rng);
+            assert!(set.contains(&start));
+            for row in 0..h.num_rows() {
+                let inside = h.iter_row(row).filter(|col| set.contains(col)).count();
+                assert_ne!(inside, 1, "check {} of the set from {}", row, start);
+            }
+            let mut kept = present.clone();
+            for &col in &set {
+                kept[col] = false;
+            }
+            assert_eq!(peel_erasures(&h, &kept).unresolved.len(), set.len());
+        }
+        assert_eq!(stopping_set_order(&h, &present, 3, &mut rng).len(), 3);
+    }
+}
@@ -0,0 +1,103 @@
+//! Error type for the scheme API.
+
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SchemeError {
+    /// A length that must equal a setup parameter does not (e.g. secret bits vs `ell`).
+    ParameterMismatch {
+        parameter: &'static str,
+        expected: usize,
+        actual: usize,
+    },
+    /// The secret does not fit into the information length `k` of the code.
+    SecretTooLong { ell: usize, info_length: usize },
+    /// A share refers to a column outside the codeword.
+    ShareIndexOutOfRange { index: u32, output_length: u32 },
+    /// A share does not carry exactly `ell` bits.
+    ShareLengthMismatch {
+        index: u32,
+        expected: usize,
+        actual: usize,
+    },
+    /// The same column index appears more than once.
+    DuplicateShare { index: u32 },
+    /// Shares that must belong to the same column do not.
+    ShareIndexMismatch { expected: u32, actual: u32 },
+    /// Joint sharing was asked to combine an empty list of dealings.
+    NoDealings,
+    /// The systematic encoder could not be built from the parity-check matrix.
+    EncoderConstruction(String),
+    /// A user-supplied parity-check matrix is malformed or not usable for systematic encoding.
+    InvalidParityCheck(String),
+    /// The code source or its parameters do not describe a code this type can build.
+    InvalidCodeParameters(String),
+    /// Data that cannot be dropped as an erasure (such as `z0`) does not match the
+    /// dealer's commitment.
+    CommitmentMismatch(String),
+    /// Serialized data was written by an unknown format version.
+    UnsupportedVersion(u8),
+    /// Serialized data is truncated or malformed.
+    InvalidEncoding(String),
+}
+
+impl Display for SchemeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SchemeError::ParameterMismatch {
+                parameter,
+                expected,
+                actual,
+            } => write!(f, "{} must be {} but is {}", parameter, expected, actual),
+            SchemeError::SecretTooLong { ell, info_length } => write!(
+                f,
+                "Information length ({}) must be >= secret bits ({})",
+                info_length, ell
+            ),
+            SchemeError::ShareIndexOutOfRange {
+                index,
+                output_length,
+            } => write!(
+                f,
+                "share index {} is out of range for codeword length {}",
+                index, output_length
+            ),
+            SchemeError::ShareLengthMismatch {
+                index,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "share {} has {} bits, expected {}",
+                index, actual, expected
+            ),
+            SchemeError::DuplicateShare { index } => {
+                write!(f, "share index {} appears more than once", index)
+            }
+            SchemeError::ShareIndexMismatch { expected, actual } => write!(
+                f,
+                "share index {} does not match column {}",
+                actual, expected
+            ),
+            SchemeError::NoDealings => write!(f, "no dealings to combine"),
+            SchemeError::EncoderConstruction(reason) => {
+                write!(f, "failed to build LDPC encoder: {}", reason)
+            }
+            SchemeError::InvalidParityCheck(reason) => {
+                write!(f, "invalid parity-check matrix: {}", reason)
+            }
+            SchemeError::InvalidCodeParameters(reason) => {
+                write!(f, "invalid code parameters: {}", reason)
+            }
+            SchemeError::CommitmentMismatch(what) => {
+                write!(f, "{} does not match the share commitment", what)
+            }
+            SchemeError::UnsupportedVersion(version) => {
+                write!(f, "unsupported serialization format version {}", version)
+            }
+            SchemeError::InvalidEncoding(reason) => write!(f, "invalid encoding: {}", reason),
+        }
+    }
+}
+
+impl std::error::Error for SchemeError {}
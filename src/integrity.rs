@@ -0,0 +1,244 @@
+//! Dealer commitment over all shares and `z0`, used to detect tampered shares.
+//!
+//! The shares of a deal are the leaves of a SHA-256 Merkle tree in column order.
+//! Leaves, inner nodes and `z0` are hashed with distinct prefixes, and a node
+//! without a sibling is carried up unchanged, so the tree shape follows from the
+//! share count alone. The dealer publishes a [`ShareCommitment`] and hands every
+//! participant a [`MerkleProof`] with their share.
+
+use sha2::{Digest as _, Sha256};
+
+use crate::error::SchemeError;
+use crate::types::{F2PowElement, Share, Shares};
+
+pub type Digest = [u8; 32];
+
+const LEAF_PREFIX: u8 = 0;
+const NODE_PREFIX: u8 = 1;
+const Z0_PREFIX: u8 = 2;
+
+/// Published by the dealer; a share verifies only against the commitment of its own deal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShareCommitment {
+    /// Number of leaves, i.e. the codeword length `n`.
+    pub share_count: u32,
+    pub root: Digest,
+    pub z0_digest: Digest,
+}
+
+/// Sibling hashes from a share's leaf up to the root, lowest level first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MerkleProof {
+    pub siblings: Vec<Digest>,
+}
+
+fn element_hash(prefix: u8, index: u32, element: &F2PowElement) -> Digest {
+    let mut hasher = Sha256::new();
+    hasher.update([prefix]);
+    hasher.update(index.to_le_bytes());
+    hasher.update((element.bit_len as u32).to_le_bytes());
+    hasher.update(&element.bytes);
+    hasher.finalize().into()
+}
+
+fn leaf_hash(share: &Share) -> Digest {
+    element_hash(LEAF_PREFIX, share.i, &share.y)
+}
+
+fn node_hash(left: &Digest, right: &Digest) -> Digest {
+    let mut hasher = Sha256::new();
+    hasher.update([NODE_PREFIX]);
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize().into()
+}
+
+fn z0_hash(z0: &F2PowElement) -> Digest {
+    element_hash(Z0_PREFIX, 0, z0)
+}
+
+/// Every level of the tree, leaves first and the root last.
+fn tree_levels(leaves: Vec<Digest>) -> Vec<Vec<Digest>> {
+    let mut levels = vec![leaves];
+    while levels.last().unwrap().len() > 1 {
+        let next = levels
+            .last()
+            .unwrap()
+            .chunks(2)
+            .map(|pair| match pair {
+                [left, right] => node_hash(left, right),
+                [single] => *single,
+                _ => unreachable!(),
+            })
+            .collect();
+        levels.push(next);
+    }
+    levels
+}
+
+/// Commit to the shares of one deal, which must be all `n` columns in index order,
+/// and return the proof of each share.
+pub fn commit(shares: &[Share], z0: &F2PowElement) -> (ShareCommitment, Vec<MerkleProof>) {
+    debug_assert!(shares.iter().enumerate().all(|(i, s)| s.i as usize == i));
+    if shares.is_empty() {
+        let commitment = ShareCommitment {
+            share_count: 0,
+            root: [0; 32],
+            z0_digest: z0_hash(z0),
+        };
+        return (commitment, Vec::new());
+    }
+
+    let levels = tree_levels(shares.iter().map(leaf_hash).collect());
+    let proofs = (0..shares.len())
+        .map(|leaf| {
+            let mut index = leaf;
+            let siblings = levels[..levels.len() - 1]
+                .iter()
+                .filter_map(|level| {
+                    let sibling = level.get(index ^ 1).copied();
+                    index /= 2;
+                    sibling
+                })
+                .collect();
+            MerkleProof { siblings }
+        })
+        .collect();
+
+    let commitment = ShareCommitment {
+        share_count: shares.len() as u32,
+        root: levels.last().unwrap()[0],
+        z0_digest: z0_hash(z0),
+    };
+    (commitment, proofs)
+}
+
+impl Shares {
+    /// Commit to the shares of a whole deal: attach the proof of every share and keep
+    /// the commitment in `self.commitment`. The shares must be all `n` columns in
+    /// index order, as a deal returns them.
+    pub fn commit(&mut self) -> Result<&ShareCommitment, SchemeError> {
+        if let Some((position, share)) = self
+            .shares
+            .iter()
+            .enumerate()
+            .find(|(position, share)| share.i as usize != *position)
+        {
+            return Err(SchemeError::ShareIndexMismatch {
+                expected: position as u32,
+                actual: share.i,
+            });
+        }
+        let (commitment, proofs) = commit(&self.shares, &self.z0);
+        for (share, proof) in self.shares.iter_mut().zip(proofs) {
+            share.proof = Some(proof);
+        }
+        Ok(self.commitment.insert(commitment))
+    }
+}
+
+impl ShareCommitment {
+    /// Whether `share` carries a proof that places it, unmodified, at its index.
+    pub fn verify_share(&self, share: &Share) -> bool {
+        let Some(proof) = &share.proof else {
+            return false;
+        };
+        if share.i >= self.share_count {
+            return false;
+        }
+
+        let mut siblings = proof.siblings.iter();
+        let mut hash = leaf_hash(share);
+        let mut index = share.i as usize;
+        let mut width = self.share_count as usize;
+        while width > 1 {
+            if index % 2 == 1 {
+                let Some(left) = siblings.next() else {
+                    return false;
+                };
+                hash = node_hash(left, &hash);
+            } else if index + 1 < width {
+                let Some(right) = siblings.next() else {
+                    return false;
+                };
+                hash = node_hash(&hash, right);
+            }
+            index /= 2;
+            width = width.div_ceil(2);
+        }
+        siblings.next().is_none() && hash == self.root
+    }
+
+    pub fn verify_z0(&self, z0: &F2PowElement) -> bool {
+        z0_hash(z0) == self.z0_digest
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_shares(count: u32) -> Vec<Share> {
+        (0..count)
+            .map(|i| Share {
+                y: F2PowElement::from_u128(i as u128 * 0x9e37, 20),
+                i,
+                proof: None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_every_proof_verifies() {
+        let z0 = F2PowElement::from_u128(5, 20);
+        for count in [1, 2, 5, 8, 13] {
+            let mut shares = sample_shares(count);
+            let (commitment, proofs) = commit(&shares, &z0);
+            for (share, proof) in shares.iter_mut().zip(proofs) {
+                assert!(
+                    !commitment.verify_share(share),
+                    "a share without proof fails"
+                );
+                share.proof = Some(proof);
+                assert!(
+                    commitment.verify_share(share),
+                    "share {} of {}",
+                    share.i,
+                    count
+                );
+            }
+            assert!(commitment.verify_z0(&z0));
+            assert!(!commitment.verify_z0(&F2PowElement::from_u128(4, 20)));
+        }
+    }
+
+    #[test]
+    fn test_tampering_is_detected() {
+        let z0 = F2PowElement::zero(20);
+        let mut shares = sample_shares(7);
+        let (commitment, proofs) = commit(&shares, &z0);
+        for (share, proof) in shares.iter_mut().zip(proofs) {
+            share.proof = Some(proof);
+        }
+
+        let mut flipped = shares[3].clone();
+        flipped.y.set_bit(0, !flipped.y.bit(0));
+        assert!(!commitment.verify_share(&flipped));
+
+        let moved = Share {
+            i: 4,
+            ..shares[3].clone()
+        };
+        assert!(!commitment.verify_share(&moved));
+
+        let mut truncated = shares[6].clone();
+        truncated.proof.as_mut().unwrap().siblings.pop();
+        assert!(!commitment.verify_share(&truncated));
+
+        let out_of_range = Share {
+            i: 7,
+            ..shares[6].clone()
+        };
+        assert!(!commitment.verify_share(&out_of_range));
+    }
+}
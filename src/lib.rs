@@ -1,8 +1,17 @@
+pub mod access;
+pub mod analysis;
 pub mod aos;
+pub mod aos_bitsliced;
 pub mod aos_core;
 pub mod aos_parallel;
 pub mod benchmark;
+pub mod bit_matrix;
+pub mod channel_model;
 pub mod code;
+pub mod erasure_model;
+pub mod error;
+pub mod integrity;
+pub mod serialization;
 pub mod types;
 pub mod ui;
 pub mod utils;
@@ -1,21 +1,21 @@
+use ldpc_toolbox::decoder::factory::DecoderImplementation;
 use rand::rngs::StdRng;
 use rand::SeedableRng;
 use std::env;
 use std::process;
 
-mod aos;
-mod aos_core;
-mod aos_parallel;
-mod benchmark;
-mod code;
-mod types;
-mod ui;
-mod utils;
-
-use crate::types::{
-    all_decoder_types, parse_decoder_type, parse_ldpc_info_size, parse_ldpc_rate, F2PowElement,
+use schema_code::analysis::{analyze_privacy, estimate_privacy_threshold, sampled_leak_rate};
+use schema_code::aos;
+use schema_code::benchmark::{
+    run_comprehensive_benchmark, CliConfig, Decoding, Implementation, SweepSettings,
+};
+use schema_code::channel_model::ChannelModel;
+use schema_code::erasure_model::ErasureModel;
+use schema_code::types::{
+    all_decoder_types, parse_code_spec, parse_decoder_type, parse_ldpc_info_size, parse_ldpc_rate,
+    CodeSource, F2PowElement,
 };
-use benchmark::{run_comprehensive_benchmark, CliConfig, Implementation};
+use schema_code::ui;
 
 enum SecretSpec {
     Hex(String),
@@ -31,6 +31,10 @@ fn main() {
                 run_benchmarks(&args[2..]);
                 return;
             }
+            "privacy" => {
+                run_privacy(&args[2..]);
+                return;
+            }
             "help" | "--help" | "-h" => {
                 print_help();
                 return;
@@ -61,6 +65,7 @@ fn print_help() {
     println!("Usage: {} [COMMAND] [OPTIONS]", bin);
     println!("Commands:");
     println!("  benchmark [OPTIONS]  Run comprehensive benchmarks");
+    println!("  privacy [OPTIONS]    Check which share coalitions learn the secret");
     println!("  ui                   Run graphical user interface");
     println!("  help                 Print this help message");
     println!();
@@ -69,13 +74,40 @@ fn print_help() {
     println!("  --warmup=N           Number of warmup runs before measurement (default: 1)");
     println!("  --sequential         Run only sequential implementation");
     println!("  --parallel           Run only parallel implementation");
+    println!("  --implementations=I1,I2,...");
+    println!("                       Implementations to run: sequential, parallel, bitsliced");
+    println!("                       (bitsliced encoding, LDPC codes only)");
     println!("  --detail             Show detailed results for each phase");
     println!("  --rates=R1,R2,...    Comma-separated list of rates to test (1_2, 2_3, etc.)");
     println!("  --sizes=S1,S2,...    Comma-separated list of info sizes to test (K1024, etc.)");
+    println!("  --codes=C1,C2,...    Code families to test instead of the AR4JA rate/size grid;");
+    println!("                       each entry is FAMILY[:RATE[:SIZE]] with FAMILY one of");
+    println!("                       AR4JA, DVBS2, NR5G (e.g. DVBS2:R1_2:N16200, NR5G:BG2:Z64);");
+    println!("                       without a rate every rate of the family is tested;");
+    println!("                       RS[:N<n>_K<k>[:M<m>]] adds a Reed-Solomon baseline over");
+    println!("                       GF(2^m), RS:N255_K223:M8 by default");
+    println!("  --alist=F1,F2,...    Benchmark parity-check matrices from alist files instead");
+    println!("                       of (or, with --codes, in addition to) the AR4JA grid");
     println!("  --decoders=D1,D2,... Comma-separated list of decoder types to test");
     println!("  --shares=N1,N2,...   Comma-separated list of shares_to_remove values");
     println!("                       (positive = absolute count, negative = percentage)");
+    println!("  --participants=M     Group the shares into M interleaved participants");
+    println!("  --remove-participants=K1,K2,...");
+    println!("                       Participants dropped before --shares are removed");
+    println!("                       (requires --participants, default: 0)");
     println!("  --seed=N             Seed for deterministic share removal");
+    println!("  --erasure=M1,M2,...  Pattern of the removed shares: uniform (default), burst,");
+    println!("                       systematic, parity, groups:G (whole participants of G)");
+    println!("                       or adversarial (stopping sets of H)");
+    println!("  --channel=C1,C2,...  Noise on the remaining shares: clean (default), bsc:P");
+    println!("                       (bits flip with probability P, decoded at --llr) or");
+    println!("                       awgn:SNR (BPSK at Es/N0 SNR dB, decoded from channel LLRs)");
+    println!("  --decoding=D1,D2,... Reconstruction of hard shares: bp (belief propagation,");
+    println!("                       default) or ml (peeling and elimination on H, LDPC only)");
+    println!("  --sweep=MAX          Erasure sweep instead of --shares: remove 0 to MAX percent");
+    println!("                       of the shares and report failure rates and thresholds");
+    println!("  --sweep-step=P       Step of the sweep in percent (default: 1)");
+    println!("  --sweep-trials=N     Random removal patterns per step (default: 100)");
     println!("  --secret-bits=ELL    Secret length in bits (default: 128)");
     println!("  --secret=HEX         Secret as hex string (accepts optional 0x prefix)");
     println!("  --secret=random      Generate a random secret");
@@ -85,11 +117,25 @@ fn print_help() {
     println!("  --no-cache           Disable setup caching");
     println!("  --terminal-log       Print log messages to terminal");
     println!();
+    println!("Privacy Options:");
+    println!("  --rates=R1,R2,...    AR4JA rates to analyse (default: 1_2,2_3,4_5)");
+    println!("  --sizes=S1,S2,...    AR4JA info sizes to analyse (default: K1024)");
+    println!("  --indices=I1,I2,...  Check the coalition holding exactly these shares");
+    println!("  --coalition=T        Check random coalitions of T shares");
+    println!("                       (without --indices or --coalition the privacy");
+    println!("                       threshold of each configuration is estimated)");
+    println!("  --samples=N          Random coalitions drawn per size (default: 20)");
+    println!("  --seed=N             Seed for the setup mask and the sampling");
+    println!();
     println!("Example:");
     println!(
         "  {} benchmark --runs=5 --warmup=1 --rates=4_5 --sizes=K1024 --secret-bits=128 --secret=0x2a --detail --output",
         bin
     );
+    println!(
+        "  {} privacy --rates=4_5 --coalition=1000 --samples=50",
+        bin
+    );
 }
 
 fn parse_secret(spec: &SecretSpec, secret_bits: usize) -> Result<F2PowElement, String> {
@@ -110,10 +156,12 @@ fn parse_benchmark_args(args: &[String]) -> CliConfig {
     let mut shares_to_remove_values: Vec<isize> = vec![100];
     let mut decoder_types = all_decoder_types();
     let mut ldpc_rates = vec![
-        crate::types::parse_ldpc_rate("1_2").unwrap(),
-        crate::types::parse_ldpc_rate("4_5").unwrap(),
+        parse_ldpc_rate("1_2").unwrap(),
+        parse_ldpc_rate("4_5").unwrap(),
     ];
-    let mut ldpc_info_sizes = vec![crate::types::parse_ldpc_info_size("K1024").unwrap()];
+    let mut ldpc_info_sizes = vec![parse_ldpc_info_size("K1024").unwrap()];
+    let mut code_specs: Vec<String> = Vec::new();
+    let mut alist_files: Vec<String> = Vec::new();
     let mut implementations = vec![Implementation::Sequential, Implementation::Parallel];
     let mut runs_per_config = 3;
     let mut warmup_runs = 1;
@@ -125,6 +173,12 @@ fn parse_benchmark_args(args: &[String]) -> CliConfig {
     let mut max_iterations: usize = 500;
     let mut llr_value: f64 = 10.0;
     let mut removal_seed: Option<u64> = None;
+    let mut participants: Option<usize> = None;
+    let mut participants_to_remove_values: Vec<usize> = vec![0];
+    let mut sweep: Option<SweepSettings> = None;
+    let mut erasure_models = vec![ErasureModel::Uniform];
+    let mut channels = vec![ChannelModel::Clean];
+    let mut decodings = vec![Decoding::BeliefPropagation];
 
     for arg in args {
         if let Some(val) = arg.strip_prefix("--runs=") {
@@ -171,6 +225,19 @@ fn parse_benchmark_args(args: &[String]) -> CliConfig {
             implementations = vec![Implementation::Sequential];
         } else if arg == "--parallel" {
             implementations = vec![Implementation::Parallel];
+        } else if let Some(val) = arg.strip_prefix("--implementations=") {
+            match val
+                .split(',')
+                .map(|s| Implementation::parse(s.trim()))
+                .collect::<Result<Vec<_>, _>>()
+            {
+                Ok(parsed) if !parsed.is_empty() => implementations = parsed,
+                Ok(_) => {}
+                Err(err) => {
+                    eprintln!("Invalid --implementations value: {}", err);
+                    process::exit(1);
+                }
+            }
         } else if arg == "--output" {
             output_file = Some(String::new());
         } else if let Some(val) = arg.strip_prefix("--output=") {
@@ -191,6 +258,20 @@ fn parse_benchmark_args(args: &[String]) -> CliConfig {
             if !parsed.is_empty() {
                 ldpc_info_sizes = parsed;
             }
+        } else if let Some(val) = arg.strip_prefix("--codes=") {
+            code_specs = val
+                .split(',')
+                .map(str::trim)
+                .filter(|spec| !spec.is_empty())
+                .map(String::from)
+                .collect();
+        } else if let Some(val) = arg.strip_prefix("--alist=") {
+            alist_files = val
+                .split(',')
+                .map(str::trim)
+                .filter(|path| !path.is_empty())
+                .map(String::from)
+                .collect();
         } else if let Some(val) = arg.strip_prefix("--shares=") {
             let parsed: Vec<isize> = val
                 .split(',')
@@ -200,7 +281,7 @@ fn parse_benchmark_args(args: &[String]) -> CliConfig {
                 shares_to_remove_values = parsed;
             }
         } else if arg == "--terminal-log" {
-            crate::ui::logging::set_terminal_log(true);
+            ui::logging::set_terminal_log(true);
         } else if arg == "--no-cache" {
             cache_setup = false;
         } else if let Some(val) = arg.strip_prefix("--decoders=") {
@@ -213,10 +294,91 @@ fn parse_benchmark_args(args: &[String]) -> CliConfig {
                     decoder_types = specified;
                 }
             }
+        } else if let Some(val) = arg.strip_prefix("--participants=") {
+            participants = val.trim().parse().ok().filter(|&count| count > 0);
+        } else if let Some(val) = arg.strip_prefix("--remove-participants=") {
+            let parsed: Vec<usize> = val
+                .split(',')
+                .filter_map(|s| s.trim().parse().ok())
+                .collect();
+            if !parsed.is_empty() {
+                participants_to_remove_values = parsed;
+            }
         } else if let Some(val) = arg.strip_prefix("--seed=") {
             if let Ok(seed) = val.parse::<u64>() {
                 removal_seed = Some(seed);
             }
+        } else if let Some(val) = arg.strip_prefix("--erasure=") {
+            match val
+                .split(',')
+                .map(|s| ErasureModel::parse(s.trim()))
+                .collect::<Result<Vec<_>, _>>()
+            {
+                Ok(parsed) if !parsed.is_empty() => erasure_models = parsed,
+                Ok(_) => {}
+                Err(err) => {
+                    eprintln!("Invalid --erasure value: {}", err);
+                    process::exit(1);
+                }
+            }
+        } else if let Some(val) = arg.strip_prefix("--channel=") {
+            match val
+                .split(',')
+                .map(|s| ChannelModel::parse(s.trim()))
+                .collect::<Result<Vec<_>, _>>()
+            {
+                Ok(parsed) if !parsed.is_empty() => channels = parsed,
+                Ok(_) => {}
+                Err(err) => {
+                    eprintln!("Invalid --channel value: {}", err);
+                    process::exit(1);
+                }
+            }
+        } else if let Some(val) = arg.strip_prefix("--decoding=") {
+            match val
+                .split(',')
+                .map(|s| Decoding::parse(s.trim()))
+                .collect::<Result<Vec<_>, _>>()
+            {
+                Ok(parsed) if !parsed.is_empty() => decodings = parsed,
+                Ok(_) => {}
+                Err(err) => {
+                    eprintln!("Invalid --decoding value: {}", err);
+                    process::exit(1);
+                }
+            }
+        } else if let Some(val) = arg.strip_prefix("--sweep=") {
+            match val.parse::<f64>() {
+                Ok(max_percent) if max_percent > 0.0 => {
+                    sweep.get_or_insert_with(SweepSettings::default).max_percent = max_percent
+                }
+                _ => {
+                    eprintln!("Invalid --sweep value: {}", val);
+                    process::exit(1);
+                }
+            }
+        } else if let Some(val) = arg.strip_prefix("--sweep-step=") {
+            match val.parse::<f64>() {
+                Ok(step_percent) if step_percent > 0.0 => {
+                    sweep
+                        .get_or_insert_with(SweepSettings::default)
+                        .step_percent = step_percent
+                }
+                _ => {
+                    eprintln!("Invalid --sweep-step value: {}", val);
+                    process::exit(1);
+                }
+            }
+        } else if let Some(val) = arg.strip_prefix("--sweep-trials=") {
+            match val.parse::<usize>() {
+                Ok(trials) if trials > 0 => {
+                    sweep.get_or_insert_with(SweepSettings::default).trials = trials
+                }
+                _ => {
+                    eprintln!("Invalid --sweep-trials value: {}", val);
+                    process::exit(1);
+                }
+            }
         }
     }
 
@@ -228,11 +390,29 @@ fn parse_benchmark_args(args: &[String]) -> CliConfig {
         }
     };
 
+    let mut codes = Vec::new();
+    for spec in &code_specs {
+        match parse_code_spec(spec) {
+            Ok(parsed) => codes.extend(parsed),
+            Err(err) => {
+                eprintln!("Invalid code: {}", err);
+                process::exit(1);
+            }
+        }
+    }
+    if code_specs.is_empty() && alist_files.is_empty() {
+        codes = CodeSource::ar4ja_grid(&ldpc_rates, &ldpc_info_sizes);
+    }
+    codes.extend(
+        alist_files
+            .into_iter()
+            .map(|path| CodeSource::Alist(path.into())),
+    );
+
     CliConfig {
         shares_to_remove_values,
         decoder_types,
-        ldpc_rates,
-        ldpc_info_sizes,
+        codes,
         implementations,
         runs_per_config,
         warmup_runs,
@@ -243,26 +423,145 @@ fn parse_benchmark_args(args: &[String]) -> CliConfig {
         max_iterations,
         llr_value,
         removal_seed,
+        participants,
+        participants_to_remove_values,
+        sweep,
+        erasure_models,
+        channels,
+        decodings,
     }
 }
 
 fn run_benchmarks(args: &[String]) {
     let cfg = parse_benchmark_args(args);
+    run_comprehensive_benchmark(&cfg);
+}
 
-    run_comprehensive_benchmark(
-        &cfg.shares_to_remove_values,
-        &cfg.decoder_types,
-        &cfg.ldpc_rates,
-        &cfg.ldpc_info_sizes,
-        &cfg.implementations,
-        cfg.runs_per_config,
-        cfg.warmup_runs,
-        cfg.cache_setup,
-        cfg.show_detail,
-        cfg.output_file.as_deref(),
-        &cfg.secret,
-        cfg.max_iterations,
-        cfg.llr_value,
-        cfg.removal_seed,
-    );
+fn run_privacy(args: &[String]) {
+    let mut ldpc_rates = vec![
+        parse_ldpc_rate("1_2").unwrap(),
+        parse_ldpc_rate("2_3").unwrap(),
+        parse_ldpc_rate("4_5").unwrap(),
+    ];
+    let mut ldpc_info_sizes = vec![parse_ldpc_info_size("K1024").unwrap()];
+    let mut indices: Option<Vec<u32>> = None;
+    let mut coalition: Option<usize> = None;
+    let mut samples: usize = 20;
+    let mut seed: Option<u64> = None;
+
+    for arg in args {
+        if let Some(val) = arg.strip_prefix("--rates=") {
+            let parsed: Vec<_> = val
+                .split(',')
+                .filter_map(|s| parse_ldpc_rate(s.trim()).ok())
+                .collect();
+            if !parsed.is_empty() {
+                ldpc_rates = parsed;
+            }
+        } else if let Some(val) = arg.strip_prefix("--sizes=") {
+            let parsed: Vec<_> = val
+                .split(',')
+                .filter_map(|s| parse_ldpc_info_size(s.trim()).ok())
+                .collect();
+            if !parsed.is_empty() {
+                ldpc_info_sizes = parsed;
+            }
+        } else if let Some(val) = arg.strip_prefix("--indices=") {
+            match val.split(',').map(|s| s.trim().parse::<u32>()).collect() {
+                Ok(parsed) => indices = Some(parsed),
+                Err(_) => {
+                    eprintln!("Invalid --indices value: {}", val);
+                    process::exit(1);
+                }
+            }
+        } else if let Some(val) = arg.strip_prefix("--coalition=") {
+            match val.parse::<usize>() {
+                Ok(size) => coalition = Some(size),
+                Err(_) => {
+                    eprintln!("Invalid --coalition value: {}", val);
+                    process::exit(1);
+                }
+            }
+        } else if let Some(val) = arg.strip_prefix("--samples=") {
+            match val.parse::<usize>() {
+                Ok(count) if count > 0 => samples = count,
+                _ => {
+                    eprintln!("Invalid --samples value: {}", val);
+                    process::exit(1);
+                }
+            }
+        } else if let Some(val) = arg.strip_prefix("--seed=") {
+            if let Ok(parsed) = val.parse::<u64>() {
+                seed = Some(parsed);
+            }
+        } else if arg == "--terminal-log" {
+            ui::logging::set_terminal_log(true);
+        }
+    }
+
+    let mut rng = match seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_rng(&mut rand::rng()),
+    };
+    for code in CodeSource::ar4ja_grid(&ldpc_rates, &ldpc_info_sizes) {
+        let params = code.init_params(DecoderImplementation::Aminstarf32, 1, 10.0, 128);
+        let pp = match aos::setup_with_rng(params, &mut rng) {
+            Ok(pp) => pp,
+            Err(err) => {
+                eprintln!(
+                    "Setup failed for {} {}: {}",
+                    code.rate_label(),
+                    code.size_label(),
+                    err
+                );
+                process::exit(1);
+            }
+        };
+        let (k, n) = (pp.code.input_length, pp.code.output_length);
+        print!(
+            "AR4JA {} {} (k={}, n={}): ",
+            code.rate_label(),
+            code.size_label(),
+            k,
+            n
+        );
+
+        if let Some(indices) = &indices {
+            match analyze_privacy(&pp, indices) {
+                Ok(analysis) => println!(
+                    "{} shares, rank {}, {}",
+                    analysis.coalition_size,
+                    analysis.rank,
+                    if analysis.leaks {
+                        "learns the secret"
+                    } else {
+                        "learns nothing"
+                    }
+                ),
+                Err(err) => {
+                    println!();
+                    eprintln!("Invalid indices: {}", err);
+                    process::exit(1);
+                }
+            }
+        } else if let Some(size) = coalition {
+            let rate = sampled_leak_rate(&pp, size, samples, &mut rng);
+            println!(
+                "{:.1}% of {} random coalitions of {} shares learn the secret",
+                rate * 100.0,
+                samples,
+                size.min(n as usize)
+            );
+        } else {
+            let threshold = estimate_privacy_threshold(&pp, samples, &mut rng);
+            println!(
+                "random coalitions of up to {} shares learn nothing, {} shares can leak \
+                 ({} samples per size); {} chosen systematic shares always leak",
+                threshold.leak_free_size,
+                threshold.first_leak_size,
+                threshold.samples,
+                threshold.mask_weight
+            );
+        }
+    }
 }
@@ -0,0 +1,819 @@
+//! Versioned wire format for shares and public parameters.
+//!
+//! Binary blobs start with a four-byte magic and the format version. Integers
+//! are little-endian and bit vectors are packed LSB-first, the same layout as
+//! [`F2PowElement::bytes`]:
+//!
+//! - `Share`: `"SCSH" | version | i: u32 | bits: u32 | y | proof`
+//! - `Shares`: `"SCSS" | version | ell: u32 | z0 | count: u32 | count x (i: u32 | y | proof)`
+//! - `ShareCommitment`: `"SCCM" | version | share_count: u32 | root | z0_digest`
+//! - `SecretParams`: `"SCPP" | version | rate | info size | decoder | max_iterations: u32 |
+//!   llr_value: f64 | ell: u32 | k: u32 | a_bits`, where names are length-prefixed strings
+//!   and the rate and info size are the labels of a [`BuiltinCode`] (e.g. `R4_5`/`K1024`,
+//!   `R1_2`/`N16200`, `BG2`/`Z64`).
+//!   Codes loaded from an alist file use the rate `"Alist"` and an empty info size, and
+//!   append `path | alist`, both as strings with a `u32` length prefix.
+//!
+//! A `proof` is one byte holding the number of sibling digests plus one (zero when
+//! the share has no proof), followed by the 32-byte digests. Version 1 predates
+//! proofs and is still read, without them.
+//!
+//! The JSON form carries the same fields, with bit vectors as big-endian hex
+//! (see [`F2PowElement::to_hex`]) and digests as hex in byte order. Deal metrics
+//! are never serialized, and the commitment of a share set travels separately.
+
+use serde::{Deserialize, Serialize};
+
+use crate::code::alist::parse_alist;
+use crate::code::family::BuiltinCode;
+use crate::code::ldpc_impl::LdpcCode;
+use crate::code::AdditiveCode;
+use crate::error::SchemeError;
+use crate::integrity::{Digest, MerkleProof, ShareCommitment};
+use crate::types::{
+    parse_decoder_type, CodeInitParams, CodeParams, CodeSource, F2PowElement, SecretParams, Share,
+    Shares,
+};
+
+pub const FORMAT_VERSION: u8 = 2;
+
+/// First version whose shares carry Merkle proofs.
+const PROOF_VERSION: u8 = 2;
+
+const SHARE_MAGIC: &[u8; 4] = b"SCSH";
+const SHARES_MAGIC: &[u8; 4] = b"SCSS";
+const PARAMS_MAGIC: &[u8; 4] = b"SCPP";
+const COMMITMENT_MAGIC: &[u8; 4] = b"SCCM";
+
+/// Rate name written for codes loaded from an alist file.
+const ALIST_RATE: &str = "Alist";
+
+fn invalid(reason: impl Into<String>) -> SchemeError {
+    SchemeError::InvalidEncoding(reason.into())
+}
+
+fn check_version(version: u8) -> Result<(), SchemeError> {
+    if (1..=FORMAT_VERSION).contains(&version) {
+        Ok(())
+    } else {
+        Err(SchemeError::UnsupportedVersion(version))
+    }
+}
+
+fn bits_to_element(bits: &[bool]) -> F2PowElement {
+    let mut element = F2PowElement::zero(bits.len());
+    for (idx, &bit) in bits.iter().enumerate() {
+        if bit {
+            element.set_bit(idx, true);
+        }
+    }
+    element
+}
+
+fn element_to_bits(element: &F2PowElement) -> Vec<bool> {
+    (0..element.bit_len).map(|idx| element.bit(idx)).collect()
+}
+
+fn hex_to_element(hex: &str, bit_len: usize) -> Result<F2PowElement, SchemeError> {
+    F2PowElement::from_hex(hex, bit_len).map_err(invalid)
+}
+
+fn digest_to_hex(digest: &Digest) -> String {
+    digest.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+fn hex_to_digest(hex: &str) -> Result<Digest, SchemeError> {
+    let malformed = || invalid(format!("malformed digest {:?}", hex));
+    if hex.len() != 64 || !hex.is_ascii() {
+        return Err(malformed());
+    }
+    let mut digest = [0u8; 32];
+    for (idx, byte) in digest.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hex[2 * idx..2 * idx + 2], 16).map_err(|_| malformed())?;
+    }
+    Ok(digest)
+}
+
+fn proof_to_hex(proof: &Option<MerkleProof>) -> Option<Vec<String>> {
+    proof
+        .as_ref()
+        .map(|proof| proof.siblings.iter().map(digest_to_hex).collect())
+}
+
+fn hex_to_proof(hex: Option<&[String]>) -> Result<Option<MerkleProof>, SchemeError> {
+    hex.map(|siblings| {
+        Ok(MerkleProof {
+            siblings: siblings
+                .iter()
+                .map(|sibling| hex_to_digest(sibling))
+                .collect::<Result<_, _>>()?,
+        })
+    })
+    .transpose()
+}
+
+fn put_u32(out: &mut Vec<u8>, value: usize) {
+    out.extend_from_slice(&(value as u32).to_le_bytes());
+}
+
+fn put_str(out: &mut Vec<u8>, value: &str) {
+    out.push(value.len() as u8);
+    out.extend_from_slice(value.as_bytes());
+}
+
+fn put_long_str(out: &mut Vec<u8>, value: &str) {
+    put_u32(out, value.len());
+    out.extend_from_slice(value.as_bytes());
+}
+
+fn put_proof(out: &mut Vec<u8>, proof: &Option<MerkleProof>) {
+    match proof {
+        Some(proof) => {
+            out.push(proof.siblings.len() as u8 + 1);
+            for sibling in &proof.siblings {
+                out.extend_from_slice(sibling);
+            }
+        }
+        None => out.push(0),
+    }
+}
+
+fn header(magic: &[u8; 4]) -> Vec<u8> {
+    let mut out = magic.to_vec();
+    out.push(FORMAT_VERSION);
+    out
+}
+
+/// Cursor over a binary blob that reports truncation as [`SchemeError::InvalidEncoding`].
+struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+    version: u8,
+}
+
+impl<'a> Reader<'a> {
+    fn new(data: &'a [u8], magic: &[u8; 4]) -> Result<Self, SchemeError> {
+        let mut reader = Reader {
+            data,
+            pos: 0,
+            version: FORMAT_VERSION,
+        };
+        if reader.take(4)? != magic {
+            return Err(invalid(format!(
+                "expected {} header",
+                String::from_utf8_lossy(magic)
+            )));
+        }
+        reader.version = reader.u8()?;
+        check_version(reader.version)?;
+        Ok(reader)
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], SchemeError> {
+        let end = self
+            .pos
+            .checked_add(len)
+            .filter(|&end| end <= self.data.len())
+            .ok_or_else(|| invalid("unexpected end of data"))?;
+        let bytes = &self.data[self.pos..end];
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    fn u8(&mut self) -> Result<u8, SchemeError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u32(&mut self) -> Result<usize, SchemeError> {
+        let bytes = self.take(4)?;
+        Ok(u32::from_le_bytes(bytes.try_into().unwrap()) as usize)
+    }
+
+    fn f64(&mut self) -> Result<f64, SchemeError> {
+        let bytes = self.take(8)?;
+        Ok(f64::from_le_bytes(bytes.try_into().unwrap()))
+    }
+
+    fn str(&mut self) -> Result<&'a str, SchemeError> {
+        let len = self.u8()? as usize;
+        std::str::from_utf8(self.take(len)?).map_err(|err| invalid(err.to_string()))
+    }
+
+    fn long_str(&mut self) -> Result<&'a str, SchemeError> {
+        let len = self.u32()?;
+        std::str::from_utf8(self.take(len)?).map_err(|err| invalid(err.to_string()))
+    }
+
+    fn element(&mut self, bit_len: usize) -> Result<F2PowElement, SchemeError> {
+        let bytes = self.take(bit_len.div_ceil(8))?.to_vec();
+        let used_bits = bit_len % 8;
+        if used_bits != 0 && bytes.last().is_some_and(|last| last >> used_bits != 0) {
+            return Err(invalid(format!("bits set beyond bit length {}", bit_len)));
+        }
+        Ok(F2PowElement { bytes, bit_len })
+    }
+
+    fn digest(&mut self) -> Result<Digest, SchemeError> {
+        Ok(self.take(32)?.try_into().unwrap())
+    }
+
+    /// A share's proof; absent before [`PROOF_VERSION`].
+    fn proof(&mut self) -> Result<Option<MerkleProof>, SchemeError> {
+        if self.version < PROOF_VERSION {
+            return Ok(None);
+        }
+        match self.u8()? {
+            0 => Ok(None),
+            count => Ok(Some(MerkleProof {
+                siblings: (1..count)
+                    .map(|_| self.digest())
+                    .collect::<Result<_, _>>()?,
+            })),
+        }
+    }
+
+    fn finish(self) -> Result<(), SchemeError> {
+        if self.pos == self.data.len() {
+            Ok(())
+        } else {
+            Err(invalid(format!(
+                "{} trailing bytes",
+                self.data.len() - self.pos
+            )))
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct ShareJson {
+    version: u8,
+    i: u32,
+    bits: usize,
+    y: String,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    proof: Option<Vec<String>>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ShareEntryJson {
+    i: u32,
+    y: String,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    proof: Option<Vec<String>>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CommitmentJson {
+    version: u8,
+    share_count: u32,
+    root: String,
+    z0_digest: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SharesJson {
+    version: u8,
+    ell: usize,
+    z0: String,
+    shares: Vec<ShareEntryJson>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct PublicParamsJson {
+    version: u8,
+    ldpc_rate: String,
+    ldpc_info_size: String,
+    decoder_type: String,
+    max_iterations: usize,
+    llr_value: f64,
+    ell: usize,
+    input_length: usize,
+    a_bits: String,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    alist_path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    alist: Option<String>,
+}
+
+fn to_json<T: Serialize>(value: &T) -> String {
+    serde_json::to_string_pretty(value).expect("wire format structs always serialize")
+}
+
+fn from_json<'de, T: Deserialize<'de>>(json: &'de str) -> Result<T, SchemeError> {
+    serde_json::from_str(json).map_err(|err| invalid(err.to_string()))
+}
+
+impl Share {
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = header(SHARE_MAGIC);
+        put_u32(&mut out, self.i as usize);
+        put_u32(&mut out, self.y.bit_len);
+        out.extend_from_slice(&self.y.bytes);
+        put_proof(&mut out, &self.proof);
+        out
+    }
+
+    pub fn from_bytes(data: &[u8]) -> Result<Self, SchemeError> {
+        let mut reader = Reader::new(data, SHARE_MAGIC)?;
+        let i = reader.u32()? as u32;
+        let bits = reader.u32()?;
+        let y = reader.element(bits)?;
+        let proof = reader.proof()?;
+        reader.finish()?;
+        Ok(Share { y, i, proof })
+    }
+
+    pub fn to_json(&self) -> String {
+        to_json(&ShareJson {
+            version: FORMAT_VERSION,
+            i: self.i,
+            bits: self.y.bit_len,
+            y: self.y.to_hex(),
+            proof: proof_to_hex(&self.proof),
+        })
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, SchemeError> {
+        let parsed: ShareJson = from_json(json)?;
+        check_version(parsed.version)?;
+        Ok(Share {
+            y: hex_to_element(&parsed.y, parsed.bits)?,
+            i: parsed.i,
+            proof: hex_to_proof(parsed.proof.as_deref())?,
+        })
+    }
+}
+
+impl Shares {
+    /// Every share is written with `z0.bit_len` bits, so all of them must have that
+    /// length; otherwise [`SchemeError::ShareLengthMismatch`].
+    fn check_share_lengths(&self) -> Result<(), SchemeError> {
+        let ell = self.z0.bit_len;
+        match self.shares.iter().find(|share| share.y.bit_len != ell) {
+            Some(share) => Err(SchemeError::ShareLengthMismatch {
+                index: share.i,
+                expected: ell,
+                actual: share.y.bit_len,
+            }),
+            None => Ok(()),
+        }
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>, SchemeError> {
+        self.check_share_lengths()?;
+        let ell = self.z0.bit_len;
+        let mut out = header(SHARES_MAGIC);
+        put_u32(&mut out, ell);
+        out.extend_from_slice(&self.z0.bytes);
+        put_u32(&mut out, self.shares.len());
+        for share in &self.shares {
+            put_u32(&mut out, share.i as usize);
+            out.extend_from_slice(&share.y.bytes);
+            put_proof(&mut out, &share.proof);
+        }
+        Ok(out)
+    }
+
+    pub fn from_bytes(data: &[u8]) -> Result<Self, SchemeError> {
+        let mut reader = Reader::new(data, SHARES_MAGIC)?;
+        let ell = reader.u32()?;
+        let z0 = reader.element(ell)?;
+        let count = reader.u32()?;
+        let mut shares = Vec::new();
+        for _ in 0..count {
+            let i = reader.u32()? as u32;
+            let y = reader.element(ell)?;
+            let proof = reader.proof()?;
+            shares.push(Share { y, i, proof });
+        }
+        reader.finish()?;
+        Ok(Shares {
+            shares,
+            z0,
+            metrics: None,
+            commitment: None,
+        })
+    }
+
+    pub fn to_json(&self) -> Result<String, SchemeError> {
+        self.check_share_lengths()?;
+        Ok(to_json(&SharesJson {
+            version: FORMAT_VERSION,
+            ell: self.z0.bit_len,
+            z0: self.z0.to_hex(),
+            shares: self
+                .shares
+                .iter()
+                .map(|share| ShareEntryJson {
+                    i: share.i,
+                    y: share.y.to_hex(),
+                    proof: proof_to_hex(&share.proof),
+                })
+                .collect(),
+        }))
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, SchemeError> {
+        let parsed: SharesJson = from_json(json)?;
+        check_version(parsed.version)?;
+        let shares = parsed
+            .shares
+            .iter()
+            .map(|entry| {
+                Ok(Share {
+                    y: hex_to_element(&entry.y, parsed.ell)?,
+                    i: entry.i,
+                    proof: hex_to_proof(entry.proof.as_deref())?,
+                })
+            })
+            .collect::<Result<Vec<_>, SchemeError>>()?;
+        Ok(Shares {
+            shares,
+            z0: hex_to_element(&parsed.z0, parsed.ell)?,
+            metrics: None,
+            commitment: None,
+        })
+    }
+}
+
+impl ShareCommitment {
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = header(COMMITMENT_MAGIC);
+        put_u32(&mut out, self.share_count as usize);
+        out.extend_from_slice(&self.root);
+        out.extend_from_slice(&self.z0_digest);
+        out
+    }
+
+    pub fn from_bytes(data: &[u8]) -> Result<Self, SchemeError> {
+        let mut reader = Reader::new(data, COMMITMENT_MAGIC)?;
+        let share_count = reader.u32()? as u32;
+        let root = reader.digest()?;
+        let z0_digest = reader.digest()?;
+        reader.finish()?;
+        Ok(ShareCommitment {
+            share_count,
+            root,
+            z0_digest,
+        })
+    }
+
+    pub fn to_json(&self) -> String {
+        to_json(&CommitmentJson {
+            version: FORMAT_VERSION,
+            share_count: self.share_count,
+            root: digest_to_hex(&self.root),
+            z0_digest: digest_to_hex(&self.z0_digest),
+        })
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, SchemeError> {
+        let parsed: CommitmentJson = from_json(json)?;
+        check_version(parsed.version)?;
+        Ok(ShareCommitment {
+            share_count: parsed.share_count,
+            root: hex_to_digest(&parsed.root)?,
+            z0_digest: hex_to_digest(&parsed.z0_digest)?,
+        })
+    }
+}
+
+/// Rebuild the code from its public description and attach the published mask.
+///
+/// Alist codes are rebuilt from the embedded matrix, so the file need not exist here.
+fn rebuild_secret_params(
+    params: CodeInitParams,
+    a_bits: &F2PowElement,
+    alist: Option<&str>,
+) -> Result<SecretParams<LdpcCode>, SchemeError> {
+    let ell = params.secret_bits.unwrap_or(128);
+    let code_impl = match (&params.code_source, alist) {
+        (Some(CodeSource::Alist(_)), Some(alist)) => {
+            LdpcCode::with_parity_check(parse_alist(alist)?, params)?
+        }
+        (Some(CodeSource::Alist(_)), None) => return Err(invalid("alist code without matrix")),
+        _ => LdpcCode::setup(params)?,
+    };
+    let input_length = code_impl.input_length();
+    let output_length = code_impl.output_length();
+
+    if a_bits.bit_len != input_length as usize {
+        return Err(SchemeError::ParameterMismatch {
+            parameter: "a_bits length",
+            expected: input_length as usize,
+            actual: a_bits.bit_len,
+        });
+    }
+    if ell > input_length as usize {
+        return Err(SchemeError::SecretTooLong {
+            ell,
+            info_length: input_length as usize,
+        });
+    }
+
+    Ok(SecretParams {
+        code: CodeParams {
+            output_length,
+            input_length,
+            code_impl,
+        },
+        ell,
+        a_bits: element_to_bits(a_bits),
+    })
+}
+
+fn parse_init_params(
+    rate: &str,
+    info_size: &str,
+    decoder: &str,
+    max_iterations: usize,
+    llr_value: f64,
+    ell: usize,
+    alist_path: Option<&str>,
+) -> Result<CodeInitParams, SchemeError> {
+    let code_source = if rate == ALIST_RATE {
+        let path = alist_path.ok_or_else(|| invalid("alist code without path"))?;
+        CodeSource::Alist(path.into())
+    } else {
+        CodeSource::Builtin(BuiltinCode::from_labels(rate, info_size).map_err(invalid)?)
+    };
+    Ok(code_source.init_params(
+        parse_decoder_type(decoder).map_err(invalid)?,
+        max_iterations,
+        llr_value,
+        ell,
+    ))
+}
+
+/// Names of the code parameters as written on the wire.
+fn code_names(code: &LdpcCode) -> (String, String, String) {
+    let params = code.init_params();
+    let (rate, info_size) = match &params.code_source {
+        Some(CodeSource::Builtin(builtin)) => (builtin.rate_label(), builtin.size_label()),
+        _ => (ALIST_RATE.to_string(), String::new()),
+    };
+    (
+        rate,
+        info_size,
+        format!("{:?}", params.decoder_type.unwrap()),
+    )
+}
+
+/// Source path and matrix of a code loaded from an alist file.
+fn alist_payload(code: &LdpcCode) -> Option<(String, String)> {
+    match &code.init_params().code_source {
+        Some(CodeSource::Alist(path)) => {
+            Some((path.display().to_string(), code.parity_check().alist()))
+        }
+        _ => None,
+    }
+}
+
+/// Only the public part of the setup is serialized; the code is rebuilt on load.
+impl SecretParams<LdpcCode> {
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let code = &self.code.code_impl;
+        let (rate, info_size, decoder) = code_names(code);
+        let mut out = header(PARAMS_MAGIC);
+        put_str(&mut out, &rate);
+        put_str(&mut out, &info_size);
+        put_str(&mut out, &decoder);
+        put_u32(&mut out, code.max_iterations());
+        out.extend_from_slice(&code.init_params().llr_value.unwrap().to_le_bytes());
+        put_u32(&mut out, self.ell);
+        put_u32(&mut out, self.a_bits.len());
+        out.extend_from_slice(&bits_to_element(&self.a_bits).bytes);
+        if let Some((path, alist)) = alist_payload(code) {
+            put_long_str(&mut out, &path);
+            put_long_str(&mut out, &alist);
+        }
+        out
+    }
+
+    pub fn from_bytes(data: &[u8]) -> Result<Self, SchemeError> {
+        let mut reader = Reader::new(data, PARAMS_MAGIC)?;
+        let rate = reader.str()?;
+        let info_size = reader.str()?;
+        let decoder = reader.str()?;
+        let max_iterations = reader.u32()?;
+        let llr_value = reader.f64()?;
+        let ell = reader.u32()?;
+        let input_length = reader.u32()?;
+        let a_bits = reader.element(input_length)?;
+        let (alist_path, alist) = if rate == ALIST_RATE {
+            (Some(reader.long_str()?), Some(reader.long_str()?))
+        } else {
+            (None, None)
+        };
+        reader.finish()?;
+
+        let params = parse_init_params(
+            rate,
+            info_size,
+            decoder,
+            max_iterations,
+            llr_value,
+            ell,
+            alist_path,
+        )?;
+        rebuild_secret_params(params, &a_bits, alist)
+    }
+
+    pub fn to_json(&self) -> String {
+        let code = &self.code.code_impl;
+        let (ldpc_rate, ldpc_info_size, decoder_type) = code_names(code);
+        let (alist_path, alist) = alist_payload(code).unzip();
+        to_json(&PublicParamsJson {
+            version: FORMAT_VERSION,
+            ldpc_rate,
+            ldpc_info_size,
+            decoder_type,
+            max_iterations: code.max_iterations(),
+            llr_value: code.init_params().llr_value.unwrap(),
+            ell: self.ell,
+            input_length: self.a_bits.len(),
+            a_bits: bits_to_element(&self.a_bits).to_hex(),
+            alist_path,
+            alist,
+        })
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, SchemeError> {
+        let parsed: PublicParamsJson = from_json(json)?;
+        check_version(parsed.version)?;
+        let a_bits = hex_to_element(&parsed.a_bits, parsed.input_length)?;
+        let params = parse_init_params(
+            &parsed.ldpc_rate,
+            &parsed.ldpc_info_size,
+            &parsed.decoder_type,
+            parsed.max_iterations,
+            parsed.llr_value,
+            parsed.ell,
+            parsed.alist_path.as_deref(),
+        )?;
+        rebuild_secret_params(params, &a_bits, parsed.alist.as_deref())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_share() -> Share {
+        let bits = [
+            true, false, true, true, false, false, false, false, true, true,
+        ];
+        Share {
+            y: bits_to_element(&bits),
+            i: 7,
+            proof: None,
+        }
+    }
+
+    fn proven_share() -> Share {
+        Share {
+            proof: Some(MerkleProof {
+                siblings: vec![[0xab; 32], [0x01; 32]],
+            }),
+            ..sample_share()
+        }
+    }
+
+    #[test]
+    fn test_share_binary_roundtrip() {
+        let share = sample_share();
+        let bytes = share.to_bytes();
+        assert_eq!(&bytes[..5], b"SCSH\x02");
+        assert_eq!(bytes.len(), 5 + 4 + 4 + 2 + 1);
+
+        let decoded = Share::from_bytes(&bytes).unwrap();
+        assert_eq!(decoded.i, 7);
+        assert_eq!(decoded.y, share.y);
+        assert_eq!(decoded.proof, None);
+
+        let share = proven_share();
+        let bytes = share.to_bytes();
+        assert_eq!(bytes.len(), 5 + 4 + 4 + 2 + 1 + 2 * 32);
+        assert_eq!(Share::from_bytes(&bytes).unwrap().proof, share.proof);
+    }
+
+    #[test]
+    fn test_reads_version_1_share_without_proof() {
+        let mut bytes = sample_share().to_bytes();
+        bytes[4] = 1;
+        bytes.pop();
+        let decoded = Share::from_bytes(&bytes).unwrap();
+        assert_eq!((decoded.i, decoded.proof), (7, None));
+    }
+
+    #[test]
+    fn test_share_json_roundtrip() {
+        let share = sample_share();
+        let json = share.to_json();
+        assert!(json.contains("\"y\": \"030d\""));
+
+        let decoded = Share::from_json(&json).unwrap();
+        assert_eq!(decoded.i, share.i);
+        assert_eq!(decoded.y, share.y);
+        assert!(!json.contains("proof"));
+
+        let share = proven_share();
+        let decoded = Share::from_json(&share.to_json()).unwrap();
+        assert_eq!(decoded.proof, share.proof);
+    }
+
+    #[test]
+    fn test_commitment_roundtrip() {
+        let commitment = ShareCommitment {
+            share_count: 1280,
+            root: [7; 32],
+            z0_digest: [0xf0; 32],
+        };
+        let bytes = commitment.to_bytes();
+        assert_eq!(&bytes[..5], b"SCCM\x02");
+        assert_eq!(ShareCommitment::from_bytes(&bytes).unwrap(), commitment);
+        assert_eq!(
+            ShareCommitment::from_json(&commitment.to_json()).unwrap(),
+            commitment
+        );
+        let json = commitment.to_json().replace("f0f0", "zz");
+        assert!(matches!(
+            ShareCommitment::from_json(&json),
+            Err(SchemeError::InvalidEncoding(_))
+        ));
+    }
+
+    #[test]
+    fn test_shares_binary_and_json_roundtrip() {
+        let shares = Shares {
+            shares: vec![
+                sample_share(),
+                Share {
+                    i: 9,
+                    ..proven_share()
+                },
+            ],
+            z0: F2PowElement::from_hex("2a1", 10).unwrap(),
+            metrics: None,
+            commitment: None,
+        };
+
+        for decoded in [
+            Shares::from_bytes(&shares.to_bytes().unwrap()).unwrap(),
+            Shares::from_json(&shares.to_json().unwrap()).unwrap(),
+        ] {
+            assert_eq!(decoded.z0, shares.z0);
+            assert_eq!(decoded.shares.len(), 2);
+            assert_eq!(decoded.shares[1].i, 9);
+            assert_eq!(decoded.shares[1].y, shares.shares[1].y);
+            assert_eq!(decoded.shares[0].proof, None);
+            assert_eq!(decoded.shares[1].proof, shares.shares[1].proof);
+        }
+
+        let mixed = Shares {
+            z0: F2PowElement::from_hex("2a", 8).unwrap(),
+            ..shares
+        };
+        let mismatch = Some(SchemeError::ShareLengthMismatch {
+            index: 7,
+            expected: 8,
+            actual: 10,
+        });
+        assert_eq!(mixed.to_bytes().err(), mismatch);
+        assert_eq!(mixed.to_json().err(), mismatch);
+    }
+
+    #[test]
+    fn test_rejects_malformed_input() {
+        let mut bytes = sample_share().to_bytes();
+        assert!(matches!(
+            Share::from_bytes(&bytes[..bytes.len() - 1]),
+            Err(SchemeError::InvalidEncoding(_))
+        ));
+        assert!(matches!(
+            Shares::from_bytes(&bytes),
+            Err(SchemeError::InvalidEncoding(_))
+        ));
+
+        let last_y_byte = bytes.len() - 2;
+        bytes[last_y_byte] |= 0x80;
+        assert!(matches!(
+            Share::from_bytes(&bytes),
+            Err(SchemeError::InvalidEncoding(_))
+        ));
+
+        bytes[4] = 3;
+        assert_eq!(
+            Share::from_bytes(&bytes).err(),
+            Some(SchemeError::UnsupportedVersion(3))
+        );
+
+        let json = sample_share()
+            .to_json()
+            .replace("\"version\": 2", "\"version\": 9");
+        assert_eq!(
+            Share::from_json(&json).err(),
+            Some(SchemeError::UnsupportedVersion(9))
+        );
+    }
+}
@@ -1,11 +1,15 @@
+use crate::aos_core::present_columns;
+use crate::code::family::{BuiltinCode, CodeFamily};
+use crate::code::reed_solomon::ReedSolomonParams;
 use crate::code::AdditiveCode;
+use crate::error::SchemeError;
+use crate::integrity::{MerkleProof, ShareCommitment};
 use ldpc_toolbox::codes::ccsds::{AR4JAInfoSize, AR4JARate};
 use ldpc_toolbox::decoder::factory::DecoderImplementation;
-use ldpc_toolbox::gf2::GF2;
-use ndarray::Array1;
 use rand::RngExt;
 use serde::{Deserialize, Serialize, Serializer};
 use std::fmt::{Debug, Formatter};
+use std::path::PathBuf;
 use std::time::Duration;
 
 /// Serde module for `Duration` fields serialized as milliseconds.
@@ -79,6 +83,45 @@ impl F2PowElement {
         Self::from_bytes_le(value.to_le_bytes().to_vec(), bit_len)
     }
 
+    /// Element of `8 * bytes.len()` bits read big-endian, like [`Self::from_hex`].
+    pub fn from_bytes(bytes: &[u8]) -> Self {
+        Self::from_bytes_le(bytes.iter().rev().copied().collect(), bytes.len() * 8)
+    }
+
+    /// Big-endian bytes, the inverse of [`Self::from_bytes`]; a partial leading byte
+    /// holds the top `bit_len % 8` bits.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.bytes.iter().rev().copied().collect()
+    }
+
+    /// Bits `start..start + len` as an element of `len` bits.
+    pub fn bits(&self, start: usize, len: usize) -> Self {
+        assert!(
+            start + len <= self.bit_len,
+            "bit range {}..{} out of range",
+            start,
+            start + len
+        );
+        let mut element = Self::zero(len);
+        for index in 0..len {
+            element.set_bit(index, self.bit(start + index));
+        }
+        element
+    }
+
+    /// The bits of `parts` one after another, the first part at the lowest bits.
+    pub fn concat(parts: &[Self]) -> Self {
+        let mut element = Self::zero(parts.iter().map(|part| part.bit_len).sum());
+        let mut offset = 0;
+        for part in parts {
+            for index in 0..part.bit_len {
+                element.set_bit(offset + index, part.bit(index));
+            }
+            offset += part.bit_len;
+        }
+        element
+    }
+
     pub fn random<R: rand::Rng + ?Sized>(bit_len: usize, rng: &mut R) -> Self {
         let mut bytes = vec![0; bit_len.div_ceil(8)];
         rng.fill(&mut bytes[..]);
@@ -325,25 +368,160 @@ pub fn parse_ldpc_info_size(s: &str) -> Result<AR4JAInfoSize, String> {
     }
 }
 
-pub fn info_bits(info_size: AR4JAInfoSize) -> usize {
-    match info_size {
-        AR4JAInfoSize::K1024 => 1024,
-        AR4JAInfoSize::K4096 => 4096,
-        AR4JAInfoSize::K16384 => 16384,
+/// Codes named by one `--codes=` entry, `FAMILY[:RATE[:SIZE]]`. A missing or empty
+/// rate stands for every rate of the family, a missing size for its default size.
+/// `RS[:N{n}_K{k}[:M{m}]]` names one Reed–Solomon code, RS(255, 223) over GF(2^8)
+/// by default.
+pub fn parse_code_spec(s: &str) -> Result<Vec<CodeSource>, String> {
+    let mut parts = s.split(':').map(str::trim);
+    let name = parts.next().unwrap_or_default();
+    let rate = parts.next().filter(|rate| !rate.is_empty());
+    let size = parts.next();
+    if parts.next().is_some() {
+        return Err(format!("Expected FAMILY[:RATE[:SIZE]], got {}", s));
+    }
+    if name.eq_ignore_ascii_case("RS") {
+        let default = ReedSolomonParams::default();
+        let params = ReedSolomonParams::from_labels(
+            rate.unwrap_or(&default.rate_label()),
+            size.unwrap_or(&default.size_label()),
+        )?;
+        return Ok(vec![CodeSource::ReedSolomon(params)]);
     }
+    let family = CodeFamily::parse(name)?;
+    let size = size.unwrap_or(family.default_size());
+    let codes = match rate {
+        Some(rate) => vec![family.code(rate, size)?],
+        None => family.codes_at_size(size),
+    };
+    if codes.is_empty() {
+        return Err(format!("{} has no codes of size {}", family.name(), size));
+    }
+    Ok(codes.into_iter().map(CodeSource::Builtin).collect())
 }
 
-pub fn codeword_bits(rate: AR4JARate, info_size: AR4JAInfoSize) -> usize {
-    let k = info_bits(info_size);
-    match rate {
-        AR4JARate::R1_2 => k * 2,
-        AR4JARate::R2_3 => (k * 3) / 2,
-        AR4JARate::R4_5 => (k * 5) / 4,
+/// Where `LdpcCode::setup` takes its parity-check matrix from, or the Reed–Solomon
+/// code built by `ReedSolomonCode::setup`; also the codes iterated over by the
+/// benchmark matrix.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum CodeSource {
+    /// A code of one of the families built into ldpc-toolbox.
+    Builtin(BuiltinCode),
+    /// Parity-check matrix read from an alist file.
+    Alist(PathBuf),
+    /// Reed–Solomon code over GF(2^m), the MDS baseline.
+    ReedSolomon(ReedSolomonParams),
+}
+
+impl CodeSource {
+    /// Every AR4JA rate/size combination, rates outermost.
+    pub fn ar4ja_grid(rates: &[AR4JARate], info_sizes: &[AR4JAInfoSize]) -> Vec<CodeSource> {
+        rates
+            .iter()
+            .flat_map(|&rate| {
+                info_sizes.iter().map(move |&info_size| {
+                    CodeSource::Builtin(BuiltinCode::Ar4ja { rate, info_size })
+                })
+            })
+            .collect()
+    }
+
+    /// Inverse of the plain rate and size labels written by the benchmark export:
+    /// `N{n}_K{k}` with `M{m}` for Reed–Solomon, the builtin labels otherwise.
+    pub fn from_labels(rate: &str, size: &str) -> Result<CodeSource, String> {
+        if size.starts_with('M') {
+            ReedSolomonParams::from_labels(rate, size).map(CodeSource::ReedSolomon)
+        } else {
+            BuiltinCode::from_labels(rate, size).map(CodeSource::Builtin)
+        }
+    }
+
+    /// The `--codes=` entry naming exactly this code; alist files have none.
+    pub fn spec(&self) -> Option<String> {
+        match self {
+            CodeSource::Builtin(code) => Some(code.spec()),
+            CodeSource::Alist(_) => None,
+            CodeSource::ReedSolomon(params) => Some(format!(
+                "RS:{}:{}",
+                params.rate_label(),
+                params.size_label()
+            )),
+        }
+    }
+
+    /// `(k, n)` when known without reading a file.
+    pub fn dimensions(&self) -> Option<(usize, usize)> {
+        match self {
+            CodeSource::Builtin(code) => Some(code.dimensions()),
+            CodeSource::Alist(_) => None,
+            CodeSource::ReedSolomon(params) => Some(params.dimensions()),
+        }
+    }
+
+    /// Family column of reports: the family name, `RS` or `Alist` for files.
+    pub fn family_label(&self) -> String {
+        match self {
+            CodeSource::Builtin(code) => code.family().name().to_string(),
+            CodeSource::Alist(_) => String::from("Alist"),
+            CodeSource::ReedSolomon(_) => String::from("RS"),
+        }
+    }
+
+    /// Rate column of reports: the code rate, prefixed by the family outside AR4JA
+    /// (whose labels predate the other families), or `Alist` for files.
+    pub fn rate_label(&self) -> String {
+        match self {
+            CodeSource::Builtin(code @ BuiltinCode::Ar4ja { .. }) => code.rate_label(),
+            CodeSource::Builtin(code) => format!("{} {}", code.family().name(), code.rate_label()),
+            CodeSource::Alist(_) => String::from("Alist"),
+            CodeSource::ReedSolomon(params) => format!("RS {}", params.rate_label()),
+        }
+    }
+
+    /// Size column of reports: the information or frame size, or the file name.
+    pub fn size_label(&self) -> String {
+        match self {
+            CodeSource::Builtin(code) => code.size_label(),
+            CodeSource::Alist(path) => path.file_name().map_or_else(
+                || path.display().to_string(),
+                |name| name.to_string_lossy().into_owned(),
+            ),
+            CodeSource::ReedSolomon(params) => params.size_label(),
+        }
+    }
+
+    /// Setup parameters for this code; the remaining fields are left to the caller.
+    pub fn init_params(
+        &self,
+        decoder_type: DecoderImplementation,
+        max_iterations: usize,
+        llr_value: f64,
+        secret_bits: usize,
+    ) -> CodeInitParams {
+        let (ldpc_rate, ldpc_info_size) = match self {
+            CodeSource::Builtin(BuiltinCode::Ar4ja { rate, info_size }) => {
+                (Some(*rate), Some(*info_size))
+            }
+            _ => (None, None),
+        };
+        CodeInitParams {
+            code_source: Some(self.clone()),
+            decoder_type: Some(decoder_type),
+            ldpc_rate,
+            ldpc_info_size,
+            max_iterations: Some(max_iterations),
+            llr_value: Some(llr_value),
+            secret_bits: Some(secret_bits),
+        }
     }
 }
 
 #[derive(Clone, Serialize)]
 pub struct CodeInitParams {
+    /// Defaults to the AR4JA code selected by `ldpc_rate` and `ldpc_info_size`;
+    /// required by `ReedSolomonCode`.
+    #[serde(serialize_with = "serialize_option_debug")]
+    pub code_source: Option<CodeSource>,
     #[serde(serialize_with = "serialize_option_debug")]
     pub decoder_type: Option<DecoderImplementation>,
     #[serde(serialize_with = "serialize_option_debug")]
@@ -412,6 +590,21 @@ impl DecodingStats {
         }
     }
 
+    /// Aggregate per-row reports; rows at or above `max_iterations` count as hitting
+    /// the limit.
+    pub fn from_rows(rows: &[RowReport], max_iterations: usize) -> Self {
+        let successful_rows = rows.iter().filter(|row| row.success).count();
+        Self::new(
+            rows.len(),
+            successful_rows,
+            rows.len() - successful_rows,
+            rows.iter().map(|row| row.iterations).sum(),
+            rows.iter()
+                .filter(|row| row.iterations >= max_iterations)
+                .count(),
+        )
+    }
+
     pub fn success_rate(&self) -> f64 {
         if self.total_rows > 0 {
             self.successful_rows as f64 / self.total_rows as f64
@@ -421,6 +614,15 @@ impl DecodingStats {
     }
 }
 
+/// Decoding result of one row of a reconstruct.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RowReport {
+    pub success: bool,
+    pub iterations: usize,
+    /// Unsatisfied checks left in the decoder's final estimate; 0 for decoded rows.
+    pub residual_syndrome_weight: usize,
+}
+
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ReconstructMetrics {
     pub matrix_setup: PhaseMetrics,
@@ -432,6 +634,46 @@ pub struct ReconstructMetrics {
     pub total_time: Duration,
     #[serde(skip_serializing_if = "Option::is_none", default)]
     pub decoding_stats: Option<DecodingStats>,
+    /// Indices of shares that failed verification and were treated as erasures.
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub rejected_shares: Vec<u32>,
+    /// Indices of present shares whose bits the error-tolerant decoder changed.
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub corrected_shares: Vec<u32>,
+    /// Participants whose bundles supplied shares, for bundled reconstructs.
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub participants: Vec<String>,
+}
+
+/// Everything a reconstruct found out, including why rows failed.
+#[derive(Debug, Clone)]
+pub struct ReconstructOutcome {
+    pub secret: Option<F2PowElement>,
+    pub metrics: ReconstructMetrics,
+    /// Codeword positions without a share.
+    pub erasures: usize,
+    /// One report per row, in row order.
+    pub rows: Vec<RowReport>,
+    /// Rough number of further shares that would probably let every row decode;
+    /// 0 on success.
+    pub recommended_extra_shares: usize,
+}
+
+impl ReconstructOutcome {
+    /// Indices of the rows that did not decode.
+    pub fn failed_rows(&self) -> Vec<usize> {
+        self.rows
+            .iter()
+            .enumerate()
+            .filter(|(_, row)| !row.success)
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    /// The `(secret, metrics)` pair returned by `reconstruct`.
+    pub fn into_parts(self) -> (Option<F2PowElement>, Option<ReconstructMetrics>) {
+        (self.secret, Some(self.metrics))
+    }
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -481,17 +723,143 @@ pub struct Shares {
     pub shares: Vec<Share>,
     pub z0: F2PowElement,
     pub metrics: Option<DealMetrics>,
+    /// Set by deal; the dealer publishes it for `reconstruct_verified`.
+    pub commitment: Option<ShareCommitment>,
 }
 
+/// One column of the encoded matrix: `ell` bits, packed like any other `F2PowElement`.
 #[derive(Clone)]
 pub struct Share {
-    pub y: Array1<GF2>,
+    pub y: F2PowElement,
     pub i: u32,
+    /// Inclusion proof against the dealer's [`ShareCommitment`].
+    pub proof: Option<MerkleProof>,
+}
+
+impl Shares {
+    /// Shares of `s1 XOR s2` from shares of `s1` (`self`) and `s2` dealt under the
+    /// same `pp`: the scheme is linear, so the columns and the `z0` values are
+    /// XORed pairwise. Shares are paired by column, in any order, and both deals
+    /// must hold the same columns.
+    ///
+    /// Both sides are checked against `pp` (`z0` and share lengths, column range,
+    /// and the codeword length of a commitment). Shares themselves do not record
+    /// the mask `a`, so combining deals made under different parameters of the same
+    /// shape is not detected and gives shares that do not reconstruct.
+    ///
+    /// The result carries no commitment or proofs, since it was never dealt.
+    pub fn combine<C: AdditiveCode>(
+        &self,
+        pp: &SecretParams<C>,
+        other: &Shares,
+    ) -> Result<Shares, SchemeError> {
+        for shares in [self, other] {
+            present_columns(pp, shares)?;
+            if let Some(commitment) = &shares.commitment {
+                if commitment.share_count != pp.code.output_length {
+                    return Err(SchemeError::ParameterMismatch {
+                        parameter: "codeword length",
+                        expected: pp.code.output_length as usize,
+                        actual: commitment.share_count as usize,
+                    });
+                }
+            }
+        }
+        let shares = xor_by_column(&self.shares, &other.shares)?;
+        let mut z0 = self.z0.clone();
+        z0.xor_assign(&other.z0);
+
+        Ok(Shares {
+            shares,
+            z0,
+            metrics: None,
+            commitment: None,
+        })
+    }
+}
+
+/// Pairwise XOR of two share sets over the same columns, paired by index in any
+/// order.
+pub(crate) fn xor_by_column(lhs: &[Share], rhs: &[Share]) -> Result<Vec<Share>, SchemeError> {
+    if lhs.len() != rhs.len() {
+        return Err(SchemeError::ParameterMismatch {
+            parameter: "share count",
+            expected: lhs.len(),
+            actual: rhs.len(),
+        });
+    }
+    sorted_by_column(lhs)
+        .into_iter()
+        .zip(sorted_by_column(rhs))
+        .map(|(lhs, rhs)| lhs.xor(rhs))
+        .collect()
+}
+
+fn sorted_by_column(shares: &[Share]) -> Vec<&Share> {
+    let mut sorted: Vec<&Share> = shares.iter().collect();
+    sorted.sort_by_key(|share| share.i);
+    sorted
+}
+
+impl Share {
+    /// XOR with the share of another deal for the same column.
+    pub fn xor(&self, other: &Share) -> Result<Share, SchemeError> {
+        if self.i != other.i {
+            return Err(SchemeError::ShareIndexMismatch {
+                expected: self.i,
+                actual: other.i,
+            });
+        }
+        if self.y.bit_len != other.y.bit_len {
+            return Err(SchemeError::ShareLengthMismatch {
+                index: other.i,
+                expected: self.y.bit_len,
+                actual: other.y.bit_len,
+            });
+        }
+        let mut y = self.y.clone();
+        y.xor_assign(&other.y);
+        Ok(Share {
+            y,
+            i: self.i,
+            proof: None,
+        })
+    }
+}
+
+/// Whether the blocks of a long secret get their own `z0` or share one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BlockZ0 {
+    #[default]
+    PerBlock,
+    /// One `ell`-bit `z0` for every block, at the cost of fixing one random column
+    /// per block.
+    Shared,
+}
+
+/// Shares of a byte string dealt as `ell`-bit blocks under one parameter set.
+#[derive(Clone)]
+pub struct BlockShares {
+    /// Share `i` bundles column `i` of every block: block `b` is in bits
+    /// `b * ell..(b + 1) * ell` of `y`.
+    pub shares: Vec<Share>,
+    /// One `z0` per block, or a single one for [`BlockZ0::Shared`].
+    pub z0: Vec<F2PowElement>,
+    /// Secret length in bytes; the last block is zero-padded.
+    pub secret_len: usize,
+}
+
+impl BlockShares {
+    /// Number of `ell`-bit blocks a secret of `secret_len` bytes is split into.
+    pub fn block_count(secret_len: usize, ell: usize) -> usize {
+        (secret_len * 8).div_ceil(ell).max(1)
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::F2PowElement;
+    use super::{F2PowElement, Share};
+    use crate::error::SchemeError;
 
     #[test]
     fn test_from_hex_pads_to_bit_length() {
@@ -525,4 +893,50 @@ mod tests {
         assert!(element.bit(9));
         assert_eq!(element.to_hex(), "0201");
     }
+
+    #[test]
+    fn test_bytes_roundtrip_big_endian() {
+        let element = F2PowElement::from_bytes(&[0x01, 0x02, 0xff]);
+        assert_eq!(element, F2PowElement::from_hex("0102ff", 24).unwrap());
+        assert_eq!(element.to_bytes(), vec![0x01, 0x02, 0xff]);
+        assert!(F2PowElement::from_bytes(&[]).to_bytes().is_empty());
+        assert_eq!(
+            F2PowElement::from_u128(0x1ab, 9).to_bytes(),
+            vec![0x01, 0xab]
+        );
+    }
+
+    #[test]
+    fn test_bits_and_concat_are_inverse() {
+        let element = F2PowElement::from_hex("1234567", 27).unwrap();
+        let parts = [element.bits(0, 5), element.bits(5, 13), element.bits(18, 9)];
+        assert_eq!(parts[0], F2PowElement::from_u128(0x07, 5));
+        assert_eq!(F2PowElement::concat(&parts), element);
+    }
+
+    #[test]
+    fn test_share_xor_checks_column_and_length() {
+        let share = |i: u32, hex: &str, bits: usize| Share {
+            y: F2PowElement::from_hex(hex, bits).unwrap(),
+            i,
+            proof: None,
+        };
+        let combined = share(3, "0f", 8).xor(&share(3, "3c", 8)).unwrap();
+        assert_eq!((combined.i, combined.y.to_hex()), (3, "33".to_string()));
+        assert_eq!(
+            share(3, "0f", 8).xor(&share(4, "0f", 8)).err(),
+            Some(SchemeError::ShareIndexMismatch {
+                expected: 3,
+                actual: 4
+            })
+        );
+        assert_eq!(
+            share(3, "0f", 8).xor(&share(3, "0f", 12)).err(),
+            Some(SchemeError::ShareLengthMismatch {
+                index: 3,
+                expected: 8,
+                actual: 12
+            })
+        );
+    }
 }
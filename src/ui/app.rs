@@ -98,12 +98,11 @@ impl BenchmarkApp {
         self.tab = Tab::Console;
 
         log_info!(
-            "Starting benchmark: ell={}, impl={:?}, decoder={:?}, rate={:?}, size={:?}, runs={}",
+            "Starting benchmark: ell={}, impl={:?}, decoder={:?}, codes={:?}, runs={}",
             config.secret_bits,
             config.implementations,
             config.decoder_types,
-            config.ldpc_rates,
-            config.ldpc_info_sizes,
+            config.codes(),
             config.runs_per_config
         );
 
@@ -118,25 +117,11 @@ impl BenchmarkApp {
             *status.lock().expect("Failed to lock status mutex") = Some(preparing_msg);
 
             let summary = run_comprehensive_benchmark_for_ui(
-                &config.shares_to_remove,
-                &config.decoder_types,
-                &config.ldpc_rates,
-                &config.ldpc_info_sizes,
-                &config.implementations,
-                config.runs_per_config,
-                config.cache_setup,
-                config.show_detail,
-                config
-                    .save_results
-                    .then_some(config.output_filename.as_str()),
+                &config.cli_config(secret),
                 |status_message| {
                     *status.lock().expect("Failed to lock status mutex") = Some(status_message);
                 },
-                &secret,
-                config.max_iterations,
-                config.llr_value,
                 cancel_flag,
-                config.removal_seed,
             );
 
             *result.lock().expect("Failed to lock result mutex") = Some(summary);
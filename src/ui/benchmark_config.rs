@@ -0,0 +1,265 @@
+use ldpc_toolbox::codes::ccsds::{AR4JAInfoSize, AR4JARate};
+use ldpc_toolbox::decoder::factory::DecoderImplementation;
+
+use crate::benchmark::{CliConfig, Decoding, Implementation, SweepSettings};
+use crate::channel_model::ChannelModel;
+use crate::code::family::{BuiltinCode, CodeFamily};
+use crate::code::reed_solomon::ReedSolomonParams;
+use crate::erasure_model::ErasureModel;
+use crate::types::{CodeSource, F2PowElement};
+
+/// Benchmark settings edited on the Configure tab.
+#[derive(Clone)]
+pub struct BenchmarkConfig {
+    pub shares_to_remove: Vec<isize>,
+    pub decoder_types: Vec<DecoderImplementation>,
+    pub ldpc_rates: Vec<AR4JARate>,
+    pub ldpc_info_sizes: Vec<AR4JAInfoSize>,
+    /// Families of the benchmark matrix; AR4JA codes come from `ldpc_rates` x `ldpc_info_sizes`.
+    pub code_families: Vec<CodeFamily>,
+    /// Selected codes of the other families.
+    pub family_codes: Vec<BuiltinCode>,
+    /// Reed–Solomon baseline benchmarked next to the families, if any.
+    pub reed_solomon: Option<ReedSolomonParams>,
+    /// Benchmark the code in `alist_path` instead of the code families.
+    pub use_alist: bool,
+    pub alist_path: String,
+    pub implementations: Vec<Implementation>,
+    pub runs_per_config: usize,
+    pub warmup_runs: usize,
+    pub cache_setup: bool,
+    pub show_detail: bool,
+    pub verbose: bool,
+    pub save_results: bool,
+    pub output_filename: String,
+    pub secret_bits: usize,
+    pub secret_hex: String,
+    pub secret_random: bool,
+    pub secret_seed: Option<u64>,
+    pub max_iterations: usize,
+    pub llr_value: f64,
+    pub removal_seed_enabled: bool,
+    pub removal_seed: Option<u64>,
+    /// Group the shares into this many interleaved participants.
+    pub participants: Option<usize>,
+    pub participants_to_remove: Vec<usize>,
+    /// Pattern of the removed shares.
+    pub erasure_model: ErasureModel,
+    /// Noise on the shares left after removal.
+    pub channel: ChannelModel,
+    /// How the remaining shares are reconstructed.
+    pub decoding: Decoding,
+    /// Run an erasure sweep instead of removing `shares_to_remove`.
+    pub sweep: Option<SweepSettings>,
+}
+
+impl Default for BenchmarkConfig {
+    fn default() -> Self {
+        Self {
+            shares_to_remove: vec![100],
+            decoder_types: vec![DecoderImplementation::Aminstarf32],
+            ldpc_rates: vec![AR4JARate::R4_5],
+            ldpc_info_sizes: vec![AR4JAInfoSize::K1024],
+            code_families: vec![CodeFamily::Ar4ja],
+            family_codes: Vec::new(),
+            reed_solomon: None,
+            use_alist: false,
+            alist_path: String::new(),
+            implementations: vec![Implementation::Sequential, Implementation::Parallel],
+            runs_per_config: 3,
+            warmup_runs: 1,
+            cache_setup: true,
+            show_detail: false,
+            verbose: false,
+            save_results: false,
+            output_filename: String::new(),
+            secret_bits: 128,
+            secret_hex: String::from("2a"),
+            secret_random: false,
+            secret_seed: None,
+            max_iterations: 500,
+            llr_value: 10.0,
+            removal_seed_enabled: false,
+            removal_seed: None,
+            participants: None,
+            participants_to_remove: vec![0],
+            erasure_model: ErasureModel::Uniform,
+            channel: ChannelModel::Clean,
+            decoding: Decoding::BeliefPropagation,
+            sweep: None,
+        }
+    }
+}
+
+impl BenchmarkConfig {
+    /// Codes of the benchmark matrix.
+    pub fn codes(&self) -> Vec<CodeSource> {
+        if self.use_alist {
+            vec![CodeSource::Alist(self.alist_path.trim().into())]
+        } else {
+            self.code_families
+                .iter()
+                .flat_map(|&family| match family {
+                    CodeFamily::Ar4ja => {
+                        CodeSource::ar4ja_grid(&self.ldpc_rates, &self.ldpc_info_sizes)
+                    }
+                    _ => self
+                        .family_codes
+                        .iter()
+                        .filter(|code| code.family() == family)
+                        .map(|&code| CodeSource::Builtin(code))
+                        .collect(),
+                })
+                .chain(self.reed_solomon.map(CodeSource::ReedSolomon))
+                .collect()
+        }
+    }
+
+    /// Settings of a run of these selections with `secret`.
+    pub fn cli_config(&self, secret: F2PowElement) -> CliConfig {
+        CliConfig {
+            shares_to_remove_values: self.shares_to_remove.clone(),
+            decoder_types: self.decoder_types.clone(),
+            codes: self.codes(),
+            implementations: self.implementations.clone(),
+            runs_per_config: self.runs_per_config,
+            warmup_runs: self.warmup_runs,
+            cache_setup: self.cache_setup,
+            show_detail: self.show_detail,
+            output_file: self.save_results.then(|| self.output_filename.clone()),
+            secret,
+            max_iterations: self.max_iterations,
+            llr_value: self.llr_value,
+            removal_seed: self.removal_seed,
+            participants: self.participants,
+            participants_to_remove_values: self.participants_to_remove.clone(),
+            sweep: self.sweep,
+            erasure_models: vec![self.erasure_model],
+            channels: vec![self.channel],
+            decodings: vec![self.decoding],
+        }
+    }
+
+    /// Equivalent `benchmark` CLI arguments, used by "Show command line".
+    pub fn to_arg_strings(&self) -> Vec<String> {
+        let join = |items: Vec<String>| items.join(",");
+        let mut args = vec![
+            format!("--runs={}", self.runs_per_config),
+            format!("--warmup={}", self.warmup_runs),
+            format!("--secret-bits={}", self.secret_bits),
+        ];
+
+        args.push(match (self.secret_random, self.secret_seed) {
+            (true, Some(seed)) => format!("--secret=random:{}", seed),
+            (true, None) => String::from("--secret=random"),
+            (false, _) => format!("--secret=0x{}", self.secret_hex),
+        });
+
+        match self.implementations.as_slice() {
+            [Implementation::Sequential] => args.push(String::from("--sequential")),
+            [Implementation::Parallel] => args.push(String::from("--parallel")),
+            [Implementation::Sequential, Implementation::Parallel] => {}
+            implementations => args.push(format!(
+                "--implementations={}",
+                implementations
+                    .iter()
+                    .map(|implementation| implementation.to_string().to_lowercase())
+                    .collect::<Vec<_>>()
+                    .join(",")
+            )),
+        }
+
+        if self.use_alist {
+            args.push(format!("--alist={}", self.alist_path.trim()));
+        } else if self.code_families != [CodeFamily::Ar4ja] || self.reed_solomon.is_some() {
+            args.push(format!(
+                "--codes={}",
+                join(self.codes().iter().filter_map(CodeSource::spec).collect())
+            ));
+        } else {
+            args.push(format!(
+                "--rates={}",
+                join(
+                    self.ldpc_rates
+                        .iter()
+                        .map(|rate| format!("{:?}", rate))
+                        .collect()
+                )
+            ));
+            args.push(format!(
+                "--sizes={}",
+                join(
+                    self.ldpc_info_sizes
+                        .iter()
+                        .map(|size| format!("{:?}", size))
+                        .collect()
+                )
+            ));
+        }
+        args.push(format!(
+            "--decoders={}",
+            join(
+                self.decoder_types
+                    .iter()
+                    .map(|decoder| format!("{:?}", decoder))
+                    .collect()
+            )
+        ));
+        args.push(format!(
+            "--shares={}",
+            join(
+                self.shares_to_remove
+                    .iter()
+                    .map(|value| value.to_string())
+                    .collect()
+            )
+        ));
+        args.push(format!("--max-iterations={}", self.max_iterations));
+        args.push(format!("--llr={}", self.llr_value));
+
+        if let Some(participants) = self.participants {
+            args.push(format!("--participants={}", participants));
+            args.push(format!(
+                "--remove-participants={}",
+                join(
+                    self.participants_to_remove
+                        .iter()
+                        .map(|value| value.to_string())
+                        .collect()
+                )
+            ));
+        }
+        if self.erasure_model != ErasureModel::Uniform {
+            args.push(format!("--erasure={}", self.erasure_model));
+        }
+        if self.channel != ChannelModel::Clean {
+            args.push(format!("--channel={}", self.channel));
+        }
+        if self.decoding != Decoding::BeliefPropagation {
+            args.push(format!("--decoding={}", self.decoding));
+        }
+        if let Some(sweep) = self.sweep {
+            args.push(format!("--sweep={}", sweep.max_percent));
+            args.push(format!("--sweep-step={}", sweep.step_percent));
+            args.push(format!("--sweep-trials={}", sweep.trials));
+        }
+        if let Some(seed) = self.removal_seed.filter(|_| self.removal_seed_enabled) {
+            args.push(format!("--seed={}", seed));
+        }
+        if self.show_detail {
+            args.push(String::from("--detail"));
+        }
+        if !self.cache_setup {
+            args.push(String::from("--no-cache"));
+        }
+        if self.save_results {
+            if self.output_filename.trim().is_empty() {
+                args.push(String::from("--output"));
+            } else {
+                args.push(format!("--output={}", self.output_filename.trim()));
+            }
+        }
+
+        args
+    }
+}
@@ -115,6 +115,25 @@ pub fn parallel_color() -> Color32 {
     Color32::from_rgb(200, 120, 70)
 }
 
+pub fn bitsliced_color() -> Color32 {
+    Color32::from_rgb(90, 170, 100)
+}
+
+/// Distinct colors for the curves of one chart, cycling after eight.
+pub fn series_color(index: usize) -> Color32 {
+    const COLORS: [Color32; 8] = [
+        Color32::from_rgb(70, 130, 200),
+        Color32::from_rgb(200, 120, 70),
+        Color32::from_rgb(80, 170, 90),
+        Color32::from_rgb(190, 70, 90),
+        Color32::from_rgb(140, 90, 190),
+        Color32::from_rgb(200, 170, 60),
+        Color32::from_rgb(60, 170, 170),
+        Color32::from_rgb(120, 120, 120),
+    ];
+    COLORS[index % COLORS.len()]
+}
+
 // --- Data bar styling ---
 
 pub const DATA_BAR_HEIGHT: f32 = 18.0;
@@ -38,6 +38,7 @@ const TRANSLATIONS: &[(&str, &str, &str)] = &[
     ("implementation_both", "Both", "Обидві"),
     ("implementation_sequential", "Sequential", "Послідовна"),
     ("implementation_parallel", "Parallel", "Паралельна"),
+    ("implementation_bitsliced", "Sequential + bitsliced", "Послідовна + побітова"),
 
     // Secret parameters
     ("secret_hex", "Secret (hex):", "Секрет (hex):"),
@@ -52,13 +53,58 @@ const TRANSLATIONS: &[(&str, &str, &str)] = &[
     ("removal_seed",         "Seed:",              "Seed:"),
     ("removal_seed_hint",    "Deterministic share removal", "Детерміноване видалення шарів"),
 
+    // Participants
+    ("participants_enabled", "Group shares into participants", "Згрупувати шари за учасниками"),
+    ("participants_count",   "Participants:",      "Учасників:"),
+    ("participants_remove",  "Remove participants:", "Видалити учасників:"),
+
+    // Erasure models
+    ("erasure_model",       "Removal pattern:",       "Шаблон видалення:"),
+    ("erasure_uniform",     "Uniform",                "Рівномірний"),
+    ("erasure_burst",       "Burst (contiguous)",     "Пакетний (суцільний)"),
+    ("erasure_systematic",  "Systematic columns only", "Лише систематичні стовпці"),
+    ("erasure_parity",      "Parity columns only",    "Лише стовпці парності"),
+    ("erasure_groups",      "Whole participants of",  "Цілі учасники з"),
+    ("erasure_adversarial", "Adversarial (stopping sets)", "Зловмисний (зупиняючі множини)"),
+
+    // Channel models
+    ("channel_model",     "Channel:",                   "Канал:"),
+    ("channel_clean",     "Clean",                      "Без шуму"),
+    ("channel_bsc",       "Binary symmetric (bit flips)", "Двійковий симетричний (інверсії бітів)"),
+    ("channel_awgn",      "AWGN (soft LLRs)",           "AWGN (м'які LLR)"),
+    ("channel_flip_rate", "Flip rate:",                 "Імовірність інверсії:"),
+    ("channel_snr_db",    "Es/N0 (dB):",                "Es/N0 (дБ):"),
+    ("decoding",          "Decoding:",                  "Декодування:"),
+    ("decoding_bp",       "Belief propagation",         "Поширення довіри"),
+    ("decoding_ml",       "Maximum likelihood (LDPC)",  "Максимальна правдоподібність (LDPC)"),
+
+    // Erasure sweep
+    ("sweep_enabled",      "Erasure sweep instead of fixed removal", "Розгортка стирань замість фіксованого видалення"),
+    ("sweep_max_percent",  "Up to (%):",         "До (%):"),
+    ("sweep_step_percent", "Step (%):",          "Крок (%):"),
+    ("sweep_trials",       "Patterns per step:", "Шаблонів на крок:"),
+
     // Code parameters
     ("code_params", "Code Parameters", "Параметри коду"),
     ("select_decoders", "Select decoders", "Виберіть декодери"),
     ("select_all", "Select all", "Вибрати всі"),
     ("clear_selection", "Clear selection", "Очистити вибір"),
+    ("code_source", "Code source:", "Джерело коду:"),
+    ("code_source_ar4ja", "AR4JA (CCSDS)", "AR4JA (CCSDS)"),
+    ("code_source_dvbs2", "DVB-S2", "DVB-S2"),
+    ("code_source_nr5g", "5G NR", "5G NR"),
+    ("code_source_rs", "Reed–Solomon", "Ріда–Соломона"),
+    ("code_source_alist", "Alist file", "Файл alist"),
+    ("alist_path_hint", "Path to a parity-check matrix in alist format", "Шлях до перевірочної матриці у форматі alist"),
+    ("browse", "Browse...", "Огляд..."),
     ("code_rate", "Code rate:", "Швидкість коду:"),
     ("info_block_size", "Information block size:", "Розмір блоку інформації:"),
+    ("frame_size", "Frame size:", "Розмір кадру:"),
+    ("base_graph", "Base graph:", "Базовий граф:"),
+    ("lifting_size", "Lifting size:", "Розмір підйому:"),
+    ("symbol_bits", "Symbol bits m:", "Біт на символ m:"),
+    ("rs_length", "Length n:", "Довжина n:"),
+    ("rs_message_symbols", "Message symbols k:", "Символів повідомлення k:"),
 
     // Output settings
     ("output_settings", "Output Settings", "Налаштування виводу"),
@@ -119,6 +165,7 @@ const TRANSLATIONS: &[(&str, &str, &str)] = &[
     ("col_min_time", "Min Time", "Мін. час"),
     ("col_max_time", "Max Time", "Макс. час"),
     ("col_success_rate", "Success Rate", "Успішність"),
+    ("col_wrong_secret_rate", "Wrong Secret", "Хибний секрет"),
     ("col_median_time", "Median", "Медіана"),
     ("col_std_dev", "Std Dev", "Стд. відхил."),
     ("col_throughput", "Throughput", "Пропускна зд."),
@@ -145,9 +192,17 @@ const TRANSLATIONS: &[(&str, &str, &str)] = &[
     ("axis_parameters", "Parameters", "Параметри"),
     ("impl_sequential", "Sequential", "Послідовно"),
     ("impl_parallel", "Parallel", "Паралельно"),
+    ("impl_bitsliced", "Bitsliced", "Побітово"),
     ("legend_sequential", "Sequential", "Послідовна"),
     ("legend_parallel", "Parallel", "Паралельна"),
+    ("legend_bitsliced", "Bitsliced", "Побітова"),
     ("chart_comparison_title", "Execution Time Comparison", "Порівняння часу виконання"),
+    ("chart_type_sweep", "Erasure Sweep", "Розгортка стирань"),
+    ("sweep_chart_title", "Reconstruction Failure Rate", "Частка невдалих відновлень"),
+    ("axis_removed_percent", "Removed shares (%)", "Видалені шари (%)"),
+    ("axis_frame_error_rate", "Frame error rate", "Частка помилкових кадрів"),
+    ("sweep_thresholds", "Success thresholds (largest removal still reconstructing in 50% / 99% of trials):", "Пороги успіху (найбільше видалення, що ще відновлюється в 50% / 99% випробувань):"),
+    ("sweep_threshold_none", "none", "немає"),
 
     // Acceleration tab
     ("speedup_info_title", "Speedup Information", "Інформація про прискорення"),
@@ -20,8 +20,8 @@ impl ConfigKey {
     fn from_params(params: &BenchmarkParams) -> Self {
         Self {
             secret_bits: params.secret.bit_len,
-            ldpc_info_size: format!("{:?}", params.ldpc_info_size),
-            ldpc_rate: format!("{:?}", params.ldpc_rate),
+            ldpc_info_size: params.code.size_label(),
+            ldpc_rate: params.code.rate_label(),
             decoder_type: format!("{:?}", params.decoder_type),
         }
     }
@@ -102,6 +102,8 @@ impl AccelerationTab {
                 match params.implementation {
                     Implementation::Sequential => has_sequential = true,
                     Implementation::Parallel => has_parallel = true,
+                    // Single-threaded; its speedup is in the summary table.
+                    Implementation::Bitsliced => {}
                 }
                 if has_sequential && has_parallel {
                     break;
@@ -223,10 +223,10 @@ impl DetailsTab {
                     ui.label(format!("{}", params.secret.bit_len));
                 });
                 row.col(|ui| {
-                    ui.label(format!("{:?}", params.ldpc_info_size));
+                    ui.label(params.code.size_label());
                 });
                 row.col(|ui| {
-                    ui.label(format!("{:?}", params.ldpc_rate));
+                    ui.label(params.code.rate_label());
                 });
                 row.col(|ui| {
                     ui.label(format!("{:?}", params.decoder_type));
@@ -4,7 +4,8 @@ use ldpc_toolbox::decoder::factory::DecoderImplementation;
 use std::collections::HashMap;
 
 use crate::benchmark::{BenchmarkParams, BenchmarkStats, BenchmarkSummary, PhaseStats};
-use crate::types::DecodingStats;
+use crate::code::family::BuiltinCode;
+use crate::types::{CodeSource, DecodingStats};
 use crate::ui::constants::{self, heading_size, small_size};
 use crate::ui::localization::Localization;
 
@@ -35,8 +36,11 @@ impl PhasesTab {
         self.summary = Some(summary.clone());
     }
 
-    fn format_info_size(&self, size: &AR4JAInfoSize) -> String {
-        let key = match size {
+    fn format_info_size(&self, code: &CodeSource) -> String {
+        let CodeSource::Builtin(BuiltinCode::Ar4ja { info_size, .. }) = code else {
+            return code.size_label();
+        };
+        let key = match info_size {
             AR4JAInfoSize::K1024 => "info_size_k1024",
             AR4JAInfoSize::K4096 => "info_size_k4096",
             AR4JAInfoSize::K16384 => "info_size_k16384",
@@ -44,7 +48,10 @@ impl PhasesTab {
         self.localization.get(key).to_string()
     }
 
-    fn format_rate(&self, rate: &AR4JARate) -> String {
+    fn format_rate(&self, code: &CodeSource) -> String {
+        let CodeSource::Builtin(BuiltinCode::Ar4ja { rate, .. }) = code else {
+            return code.rate_label();
+        };
         let key = match rate {
             AR4JARate::R1_2 => "rate_r1_2",
             AR4JARate::R2_3 => "rate_r2_3",
@@ -69,21 +76,16 @@ impl PhasesTab {
             "{} | ell={} | {} | {} | {}",
             params.implementation,
             params.secret.bit_len,
-            self.format_info_size(&params.ldpc_info_size),
-            self.format_rate(&params.ldpc_rate),
+            self.format_info_size(&params.code),
+            self.format_rate(&params.code),
             self.format_decoder(&params.decoder_type)
         )
     }
 
     fn make_section_id(prefix: &str, params: &BenchmarkParams) -> String {
         format!(
-            "{}_{:?}_{:?}_{:?}_{:?}_{:?}",
-            prefix,
-            params.implementation,
-            params.secret.bit_len,
-            params.ldpc_info_size,
-            params.ldpc_rate,
-            params.decoder_type
+            "{}_{:?}_{:?}_{:?}_{:?}",
+            prefix, params.implementation, params.secret.bit_len, params.code, params.decoder_type
         )
     }
 
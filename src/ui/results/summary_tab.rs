@@ -42,6 +42,7 @@ pub enum SortColumn {
     StdDev,
     Throughput,
     SuccessRate,
+    WrongSecretRate,
 }
 
 #[derive(Clone)]
@@ -171,6 +172,11 @@ impl SummaryTab {
                 TableColumn::new(self.localization.get("col_success_rate")).with_min_width(70.0),
                 SortColumn::SuccessRate,
             ),
+            (
+                TableColumn::new(self.localization.get("col_wrong_secret_rate"))
+                    .with_min_width(70.0),
+                SortColumn::WrongSecretRate,
+            ),
         ]
     }
 
@@ -250,10 +256,10 @@ impl SummaryTab {
                         ui.label(format!("{}", params.secret.bit_len));
                     });
                     row.col(|ui| {
-                        ui.label(format!("{:?}", params.ldpc_info_size));
+                        ui.label(params.code.size_label());
                     });
                     row.col(|ui| {
-                        ui.label(format!("{:?}", params.ldpc_rate));
+                        ui.label(params.code.rate_label());
                     });
                     row.col(|ui| {
                         ui.label(format!("{:?}", params.decoder_type));
@@ -293,6 +299,10 @@ impl SummaryTab {
                         let success_color = constants::rate_color(ui, stats.success_rate);
                         ui.label(RichText::new(success_text).color(success_color));
                     });
+
+                    row.col(|ui| {
+                        ui.label(format!("{:.0}%", stats.wrong_secret_rate * 100.0));
+                    });
                 });
             });
 
@@ -321,12 +331,8 @@ impl SummaryTab {
                         .cmp(&b.0.implementation.to_string())
                 }
                 SortColumn::SecretBits => a.0.secret.bit_len.cmp(&b.0.secret.bit_len),
-                SortColumn::BlockSize => {
-                    format!("{:?}", a.0.ldpc_info_size).cmp(&format!("{:?}", b.0.ldpc_info_size))
-                }
-                SortColumn::Rate => {
-                    format!("{:?}", a.0.ldpc_rate).cmp(&format!("{:?}", b.0.ldpc_rate))
-                }
+                SortColumn::BlockSize => a.0.code.size_label().cmp(&b.0.code.size_label()),
+                SortColumn::Rate => a.0.code.rate_label().cmp(&b.0.code.rate_label()),
                 SortColumn::Decoder => {
                     format!("{:?}", a.0.decoder_type).cmp(&format!("{:?}", b.0.decoder_type))
                 }
@@ -352,6 +358,11 @@ impl SummaryTab {
                         .partial_cmp(&b.1.success_rate)
                         .unwrap_or(std::cmp::Ordering::Equal)
                 }
+                SortColumn::WrongSecretRate => {
+                    a.1.wrong_secret_rate
+                        .partial_cmp(&b.1.wrong_secret_rate)
+                        .unwrap_or(std::cmp::Ordering::Equal)
+                }
             };
 
             match sort_direction {
@@ -1,4 +1,4 @@
-use crate::benchmark::{BenchmarkParams, Implementation, PhaseStats};
+use crate::benchmark::{BenchmarkParams, PhaseStats};
 use eframe::egui::{self, Color32, Ui};
 use std::cmp::Ordering;
 use std::collections::HashMap;
@@ -8,17 +8,14 @@ use std::time::Duration;
 ///
 /// Sorts by decoder type, then rate, then implementation (sequential first).
 /// Uses Debug representation for enum ordering because external ldpc-toolbox
-/// enums (`DecoderImplementation`, `AR4JARate`) don't implement `Ord`.
+/// enums (`DecoderImplementation`, `AR4JARate`) don't implement `Ord`; rates of
+/// alist codes sort by their label.
 pub fn compare_benchmark_params(a: &BenchmarkParams, b: &BenchmarkParams) -> Ordering {
     format!("{:?}", a.decoder_type)
         .cmp(&format!("{:?}", b.decoder_type))
-        .then_with(|| format!("{:?}", a.ldpc_rate).cmp(&format!("{:?}", b.ldpc_rate)))
+        .then_with(|| a.code.rate_label().cmp(&b.code.rate_label()))
         .then_with(|| a.secret.bit_len.cmp(&b.secret.bit_len))
-        .then_with(|| match (a.implementation, b.implementation) {
-            (Implementation::Sequential, Implementation::Parallel) => Ordering::Less,
-            (Implementation::Parallel, Implementation::Sequential) => Ordering::Greater,
-            _ => Ordering::Equal,
-        })
+        .then_with(|| a.implementation.cmp(&b.implementation))
 }
 
 pub fn format_duration(duration: Duration) -> String {
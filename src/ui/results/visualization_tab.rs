@@ -1,14 +1,18 @@
 use super::utils::compare_benchmark_params;
-use crate::benchmark::{BenchmarkSummary, Implementation};
+use crate::benchmark::{BenchmarkSummary, Decoding, Implementation, SweepCurve};
+use crate::channel_model::ChannelModel;
+use crate::erasure_model::ErasureModel;
 use crate::ui::constants::{self, heading_size, small_size};
 use crate::ui::localization::Localization;
-use eframe::egui::{RichText, ScrollArea, Ui};
+use eframe::egui::{Color32, RichText, ScrollArea, Ui};
 use egui_plot as plot;
 
 #[derive(Clone, Copy, PartialEq)]
 pub enum ChartType {
     Bar,
     Line,
+    /// Failure rate against removed shares; only offered for sweep results.
+    Sweep,
 }
 
 #[derive(Clone)]
@@ -83,6 +87,13 @@ impl VisualizationTab {
     }
 
     pub fn update_with_summary(&mut self, summary: &BenchmarkSummary) {
+        if summary.sweep.is_empty() {
+            if self.chart_type == ChartType::Sweep {
+                self.chart_type = ChartType::Bar;
+            }
+        } else if summary.total_stats.is_empty() {
+            self.chart_type = ChartType::Sweep;
+        }
         self.summary = Some(summary.clone());
     }
 
@@ -116,6 +127,16 @@ impl VisualizationTab {
                     {
                         self.chart_type = ChartType::Line;
                     }
+                    if !summary.sweep.is_empty()
+                        && ui
+                            .selectable_label(
+                                self.chart_type == ChartType::Sweep,
+                                self.localization.get("chart_type_sweep"),
+                            )
+                            .clicked()
+                    {
+                        self.chart_type = ChartType::Sweep;
+                    }
                 });
 
                 ui.add_space(constants::ITEM_SPACING);
@@ -123,6 +144,7 @@ impl VisualizationTab {
                 match self.chart_type {
                     ChartType::Bar => self.show_bar_chart(ui, summary, plot_height),
                     ChartType::Line => self.show_line_chart(ui, summary, plot_height),
+                    ChartType::Sweep => self.show_sweep_chart(ui, summary, plot_height),
                 }
             }
         });
@@ -157,8 +179,10 @@ impl VisualizationTab {
 
             let impl_sequential = self.localization.get("impl_sequential").to_string();
             let impl_parallel = self.localization.get("impl_parallel").to_string();
+            let impl_bitsliced = self.localization.get("impl_bitsliced").to_string();
             let legend_sequential = self.localization.get("legend_sequential").to_string();
             let legend_parallel = self.localization.get("legend_parallel").to_string();
+            let legend_bitsliced = self.localization.get("legend_bitsliced").to_string();
             let chart_comparison_title =
                 self.localization.get("chart_comparison_title").to_string();
             let title_size = heading_size(ui);
@@ -171,24 +195,30 @@ impl VisualizationTab {
 
                     let mut seq_values = Vec::new();
                     let mut par_values = Vec::new();
+                    let mut bit_values = Vec::new();
                     let mut param_labels: Vec<(f64, String)> = Vec::new();
                     let mut bar_index = 0.0_f64;
 
                     for (params, stats) in entries {
                         let avg_ms = stats.avg.as_millis() as f64;
                         let param_label = format!(
-                            "{:?}\n{:?}\n{:?}",
-                            params.ldpc_rate, params.ldpc_info_size, params.decoder_type
+                            "{}\n{}\n{:?}",
+                            params.code.rate_label(),
+                            params.code.size_label(),
+                            params.decoder_type
                         );
                         param_labels.push((bar_index, param_label));
 
                         let impl_name = match params.implementation {
                             Implementation::Sequential => &impl_sequential,
                             Implementation::Parallel => &impl_parallel,
+                            Implementation::Bitsliced => &impl_bitsliced,
                         };
                         let tooltip_label = format!(
-                            "{:?}_{:?}_{:?}",
-                            params.ldpc_rate, params.ldpc_info_size, params.decoder_type
+                            "{}_{}_{:?}",
+                            params.code.rate_label(),
+                            params.code.size_label(),
+                            params.decoder_type
                         );
                         let bar_value = plot::Bar::new(bar_index, avg_ms)
                             .name(format!(
@@ -200,6 +230,7 @@ impl VisualizationTab {
                         match params.implementation {
                             Implementation::Sequential => seq_values.push(bar_value),
                             Implementation::Parallel => par_values.push(bar_value),
+                            Implementation::Bitsliced => bit_values.push(bar_value),
                         }
 
                         bar_index += 1.0;
@@ -219,6 +250,12 @@ impl VisualizationTab {
                                 .color(constants::parallel_color()),
                         );
                     }
+                    if !bit_values.is_empty() {
+                        plot_ui.bar_chart(
+                            plot::BarChart::new(&legend_bitsliced, bit_values)
+                                .color(constants::bitsliced_color()),
+                        );
+                    }
 
                     draw_chart_title(
                         plot_ui,
@@ -248,6 +285,7 @@ impl VisualizationTab {
 
             let legend_sequential = self.localization.get("legend_sequential").to_string();
             let legend_parallel = self.localization.get("legend_parallel").to_string();
+            let legend_bitsliced = self.localization.get("legend_bitsliced").to_string();
             let chart_comparison_title =
                 self.localization.get("chart_comparison_title").to_string();
             let title_size = heading_size(ui);
@@ -260,6 +298,7 @@ impl VisualizationTab {
 
                     let mut seq_points: Vec<[f64; 2]> = Vec::new();
                     let mut par_points: Vec<[f64; 2]> = Vec::new();
+                    let mut bit_points: Vec<[f64; 2]> = Vec::new();
                     let mut param_labels: Vec<(f64, String)> = Vec::new();
                     let mut config_index = 0.0_f64;
                     let mut configs_seen = std::collections::HashMap::new();
@@ -270,11 +309,8 @@ impl VisualizationTab {
                         // Each unique (rate, info_size, decoder, ell) configuration gets one X position,
                         // shared by both sequential and parallel implementations.
                         let config_key = format!(
-                            "{:?}_{:?}_{:?}_{}",
-                            params.ldpc_rate,
-                            params.ldpc_info_size,
-                            params.decoder_type,
-                            params.secret.bit_len
+                            "{:?}_{:?}_{}",
+                            params.code, params.decoder_type, params.secret.bit_len
                         );
 
                         let x_index = if let Some(&idx) = configs_seen.get(&config_key) {
@@ -285,8 +321,10 @@ impl VisualizationTab {
                             param_labels.push((
                                 idx,
                                 format!(
-                                    "{:?}\n{:?}\n{:?}",
-                                    params.ldpc_rate, params.ldpc_info_size, params.decoder_type
+                                    "{}\n{}\n{:?}",
+                                    params.code.rate_label(),
+                                    params.code.size_label(),
+                                    params.decoder_type
                                 ),
                             ));
                             config_index += 1.0;
@@ -296,11 +334,13 @@ impl VisualizationTab {
                         match params.implementation {
                             Implementation::Sequential => seq_points.push([x_index, avg_ms]),
                             Implementation::Parallel => par_points.push([x_index, avg_ms]),
+                            Implementation::Bitsliced => bit_points.push([x_index, avg_ms]),
                         }
                     }
 
                     seq_points.sort_by(|a, b| a[0].partial_cmp(&b[0]).unwrap());
                     par_points.sort_by(|a, b| a[0].partial_cmp(&b[0]).unwrap());
+                    bit_points.sort_by(|a, b| a[0].partial_cmp(&b[0]).unwrap());
 
                     draw_grid(plot_ui, y_max);
 
@@ -334,6 +374,21 @@ impl VisualizationTab {
                                 .radius(5.0),
                         );
                     }
+                    if !bit_points.is_empty() {
+                        plot_ui.line(
+                            plot::Line::new(
+                                &legend_bitsliced,
+                                plot::PlotPoints::from(bit_points.clone()),
+                            )
+                            .color(constants::bitsliced_color())
+                            .width(2.5),
+                        );
+                        plot_ui.points(
+                            plot::Points::new("", plot::PlotPoints::from(bit_points))
+                                .color(constants::bitsliced_color())
+                                .radius(5.0),
+                        );
+                    }
 
                     draw_chart_title(
                         plot_ui,
@@ -351,4 +406,99 @@ impl VisualizationTab {
                 });
         });
     }
+
+    fn show_sweep_chart(&self, ui: &mut Ui, summary: &BenchmarkSummary, plot_height: f32) {
+        let mut curves: Vec<_> = summary.sweep.iter().collect();
+        curves.sort_by_key(|curve| {
+            (
+                curve.code.rate_label(),
+                curve.code.size_label(),
+                format!("{:?}", curve.decoder_type),
+                curve.erasure_model.label(),
+                curve.channel.label(),
+                curve.decoding.label(),
+            )
+        });
+        let curve_label = |curve: &SweepCurve| {
+            let mut label = format!(
+                "{} {} {:?}",
+                curve.code.rate_label(),
+                curve.code.size_label(),
+                curve.decoder_type
+            );
+            if curve.erasure_model != ErasureModel::Uniform {
+                label.push_str(&format!(" {}", curve.erasure_model));
+            }
+            if curve.channel != ChannelModel::Clean {
+                label.push_str(&format!(" {}", curve.channel));
+            }
+            if curve.decoding != Decoding::BeliefPropagation {
+                label.push_str(&format!(" {}", curve.decoding));
+            }
+            label
+        };
+
+        ui.push_id("sweep_chart_section", |ui| {
+            ui.label(RichText::new(self.localization.get("sweep_chart_title")).strong());
+            plot::Plot::new("sweep_chart_plot")
+                .height(plot_height)
+                .legend(plot::Legend::default().position(plot::Corner::LeftTop))
+                .y_axis_label(
+                    RichText::new(self.localization.get("axis_frame_error_rate"))
+                        .size(small_size(ui)),
+                )
+                .x_axis_label(
+                    RichText::new(self.localization.get("axis_removed_percent"))
+                        .size(small_size(ui)),
+                )
+                .view_aspect(2.0)
+                .include_y(0.0)
+                .include_y(1.0)
+                .include_x(0.0)
+                .show(ui, |plot_ui| {
+                    // Frame error rates at the 50% and 99% success thresholds.
+                    for level in [0.5, 0.01] {
+                        plot_ui.hline(
+                            plot::HLine::new("", level)
+                                .color(Color32::GRAY)
+                                .style(plot::LineStyle::dashed_dense()),
+                        );
+                    }
+                    for (index, curve) in curves.iter().enumerate() {
+                        let color = constants::series_color(index);
+                        let points: Vec<[f64; 2]> = curve
+                            .points
+                            .iter()
+                            .map(|point| [point.removed_percent, point.frame_error_rate])
+                            .collect();
+                        let name = curve_label(curve);
+                        plot_ui.line(
+                            plot::Line::new(&name, plot::PlotPoints::from(points.clone()))
+                                .color(color)
+                                .width(2.0),
+                        );
+                        plot_ui.points(
+                            plot::Points::new(&name, plot::PlotPoints::from(points))
+                                .color(color)
+                                .radius(3.0),
+                        );
+                    }
+                });
+        });
+
+        ui.add_space(constants::ITEM_SPACING);
+        ui.label(RichText::new(self.localization.get("sweep_thresholds")).strong());
+        let none = self.localization.get("sweep_threshold_none");
+        let threshold = |value: Option<f64>| {
+            value.map_or_else(|| none.to_string(), |percent| format!("{:.1}%", percent))
+        };
+        for curve in curves {
+            ui.label(format!(
+                "{}: {} / {}",
+                curve_label(curve),
+                threshold(curve.threshold_50()),
+                threshold(curve.threshold_99())
+            ));
+        }
+    }
 }
@@ -1,7 +1,13 @@
 use eframe::egui::{self, Color32, RichText, ScrollArea, Ui};
 use ldpc_toolbox::codes::ccsds::{AR4JAInfoSize, AR4JARate};
 
-use crate::benchmark::Implementation;
+use std::collections::HashMap;
+
+use crate::benchmark::{Decoding, Implementation, SweepSettings};
+use crate::channel_model::ChannelModel;
+use crate::code::family::CodeFamily;
+use crate::code::reed_solomon::ReedSolomonParams;
+use crate::erasure_model::ErasureModel;
 use crate::types::F2PowElement;
 use crate::ui::benchmark_config::BenchmarkConfig;
 use crate::ui::components::DecoderSelector;
@@ -20,6 +26,10 @@ pub struct ConfigureTab {
     selected_implementation: usize,
     selected_rates: Vec<bool>,
     selected_size: usize,
+    /// Selected rates (parallel to `rate_labels`) and size of each family but AR4JA.
+    family_selection: HashMap<CodeFamily, (Vec<bool>, String)>,
+    /// Reed–Solomon parameters, kept while the code is toggled off.
+    reed_solomon: ReedSolomonParams,
     runs_value: String,
     warmup_value: String,
     shares_to_remove_value: String,
@@ -33,6 +43,20 @@ pub struct ConfigureTab {
     secret_valid: bool,
     removal_seed_enabled: bool,
     removal_seed_text: String,
+    participants_enabled: bool,
+    participants_count: usize,
+    participants_to_remove: usize,
+    erasure_model: ErasureModel,
+    /// Participant count of [`ErasureModel::Groups`], kept while another model is selected.
+    erasure_groups: usize,
+    channel: ChannelModel,
+    /// Flip rate of [`ChannelModel::Bsc`] and Es/N0 of [`ChannelModel::Awgn`], kept
+    /// while the other channel is selected.
+    bsc_flip_rate: f64,
+    awgn_snr_db: f64,
+    decoding: Decoding,
+    sweep_enabled: bool,
+    sweep: SweepSettings,
     command_line_display: Option<String>,
 }
 
@@ -44,6 +68,12 @@ impl ConfigureTab {
             selected_implementation: 0,
             selected_rates: vec![false, false, true],
             selected_size: 0,
+            family_selection: CodeFamily::ALL
+                .into_iter()
+                .filter(|&family| family != CodeFamily::Ar4ja)
+                .map(|family| (family, family_selection(&config, family)))
+                .collect(),
+            reed_solomon: config.reed_solomon.unwrap_or_default(),
             runs_value: config.runs_per_config.to_string(),
             warmup_value: config.warmup_runs.to_string(),
             shares_to_remove_value: config
@@ -64,6 +94,26 @@ impl ConfigureTab {
             removal_seed_text: config
                 .removal_seed
                 .map_or(String::new(), |seed| seed.to_string()),
+            participants_enabled: config.participants.is_some(),
+            participants_count: config.participants.unwrap_or(10),
+            participants_to_remove: config.participants_to_remove.first().copied().unwrap_or(0),
+            erasure_model: config.erasure_model,
+            erasure_groups: match config.erasure_model {
+                ErasureModel::Groups(groups) => groups,
+                _ => 10,
+            },
+            channel: config.channel,
+            bsc_flip_rate: match config.channel {
+                ChannelModel::Bsc(flip_rate) => flip_rate,
+                _ => 0.001,
+            },
+            awgn_snr_db: match config.channel {
+                ChannelModel::Awgn(snr_db) => snr_db,
+                _ => 3.0,
+            },
+            decoding: config.decoding,
+            sweep_enabled: config.sweep.is_some(),
+            sweep: config.sweep.unwrap_or_default(),
             command_line_display: None,
             config,
         }
@@ -157,6 +207,138 @@ impl ConfigureTab {
                             }
                         });
 
+                        ui.horizontal(|ui| {
+                            ui.label(self.localization.get("erasure_model"));
+                            egui::ComboBox::from_id_salt("erasure_model")
+                                .selected_text(
+                                    self.localization.get(erasure_model_key(self.erasure_model)),
+                                )
+                                .show_ui(ui, |ui| {
+                                    for model in [
+                                        ErasureModel::Uniform,
+                                        ErasureModel::Burst,
+                                        ErasureModel::SystematicOnly,
+                                        ErasureModel::ParityOnly,
+                                        ErasureModel::Groups(self.erasure_groups),
+                                        ErasureModel::Adversarial,
+                                    ] {
+                                        ui.selectable_value(
+                                            &mut self.erasure_model,
+                                            model,
+                                            self.localization.get(erasure_model_key(model)),
+                                        );
+                                    }
+                                });
+                            if let ErasureModel::Groups(_) = self.erasure_model {
+                                ui.add(
+                                    egui::DragValue::new(&mut self.erasure_groups).range(1..=1000),
+                                );
+                                self.erasure_model = ErasureModel::Groups(self.erasure_groups);
+                            }
+                        });
+
+                        ui.horizontal(|ui| {
+                            ui.label(self.localization.get("channel_model"));
+                            egui::ComboBox::from_id_salt("channel_model")
+                                .selected_text(self.localization.get(channel_key(self.channel)))
+                                .show_ui(ui, |ui| {
+                                    for channel in [
+                                        ChannelModel::Clean,
+                                        ChannelModel::Bsc(self.bsc_flip_rate),
+                                        ChannelModel::Awgn(self.awgn_snr_db),
+                                    ] {
+                                        ui.selectable_value(
+                                            &mut self.channel,
+                                            channel,
+                                            self.localization.get(channel_key(channel)),
+                                        );
+                                    }
+                                });
+                            match self.channel {
+                                ChannelModel::Clean => {}
+                                ChannelModel::Bsc(_) => {
+                                    ui.label(self.localization.get("channel_flip_rate"));
+                                    ui.add(
+                                        egui::DragValue::new(&mut self.bsc_flip_rate)
+                                            .range(0.0..=0.5)
+                                            .speed(0.0001)
+                                            .max_decimals(5),
+                                    );
+                                    self.channel = ChannelModel::Bsc(self.bsc_flip_rate);
+                                }
+                                ChannelModel::Awgn(_) => {
+                                    ui.label(self.localization.get("channel_snr_db"));
+                                    ui.add(
+                                        egui::DragValue::new(&mut self.awgn_snr_db)
+                                            .range(-10.0..=20.0)
+                                            .speed(0.1),
+                                    );
+                                    self.channel = ChannelModel::Awgn(self.awgn_snr_db);
+                                }
+                            }
+                        });
+
+                        ui.horizontal(|ui| {
+                            ui.label(self.localization.get("decoding"));
+                            egui::ComboBox::from_id_salt("decoding")
+                                .selected_text(self.localization.get(decoding_key(self.decoding)))
+                                .show_ui(ui, |ui| {
+                                    for decoding in
+                                        [Decoding::BeliefPropagation, Decoding::MaximumLikelihood]
+                                    {
+                                        ui.selectable_value(
+                                            &mut self.decoding,
+                                            decoding,
+                                            self.localization.get(decoding_key(decoding)),
+                                        );
+                                    }
+                                });
+                        });
+
+                        ui.checkbox(
+                            &mut self.participants_enabled,
+                            self.localization.get("participants_enabled"),
+                        );
+                        if self.participants_enabled {
+                            ui.horizontal(|ui| {
+                                ui.label(self.localization.get("participants_count"));
+                                ui.add(
+                                    egui::DragValue::new(&mut self.participants_count)
+                                        .range(1..=1000),
+                                );
+                                ui.label(self.localization.get("participants_remove"));
+                                ui.add(
+                                    egui::DragValue::new(&mut self.participants_to_remove)
+                                        .range(0..=self.participants_count),
+                                );
+                            });
+                        }
+
+                        ui.checkbox(
+                            &mut self.sweep_enabled,
+                            self.localization.get("sweep_enabled"),
+                        );
+                        if self.sweep_enabled {
+                            ui.horizontal(|ui| {
+                                ui.label(self.localization.get("sweep_max_percent"));
+                                ui.add(
+                                    egui::DragValue::new(&mut self.sweep.max_percent)
+                                        .range(0.0..=100.0)
+                                        .speed(0.5),
+                                );
+                                ui.label(self.localization.get("sweep_step_percent"));
+                                ui.add(
+                                    egui::DragValue::new(&mut self.sweep.step_percent)
+                                        .range(0.1..=50.0)
+                                        .speed(0.1),
+                                );
+                                ui.label(self.localization.get("sweep_trials"));
+                                ui.add(
+                                    egui::DragValue::new(&mut self.sweep.trials).range(1..=10000),
+                                );
+                            });
+                        }
+
                         self.sync_shares_config();
                     });
 
@@ -258,6 +440,7 @@ impl ConfigureTab {
                         self.localization.get("implementation_both"),
                         self.localization.get("implementation_sequential"),
                         self.localization.get("implementation_parallel"),
+                        self.localization.get("implementation_bitsliced"),
                     ];
 
                     for (idx, label) in labels.iter().enumerate() {
@@ -269,6 +452,7 @@ impl ConfigureTab {
                                 0 => vec![Implementation::Sequential, Implementation::Parallel],
                                 1 => vec![Implementation::Sequential],
                                 2 => vec![Implementation::Parallel],
+                                3 => vec![Implementation::Sequential, Implementation::Bitsliced],
                                 _ => unreachable!("implementation index out of range"),
                             };
                         }
@@ -290,59 +474,74 @@ impl ConfigureTab {
                 );
                 ui.add_space(5.0);
 
-                ui.label(RichText::new(self.localization.get("code_rate")).strong());
+                ui.label(RichText::new(self.localization.get("code_source")).strong());
                 ui.horizontal_wrapped(|ui| {
-                    let rates = [
-                        ("R1_2", AR4JARate::R1_2),
-                        ("R2_3", AR4JARate::R2_3),
-                        ("R4_5", AR4JARate::R4_5),
-                    ];
-
-                    if self.selected_rates.len() < rates.len() {
-                        self.selected_rates.resize(rates.len(), false);
-                    }
-
-                    for (i, (name, _)) in rates.iter().enumerate() {
-                        if ui.selectable_label(self.selected_rates[i], *name).clicked() {
-                            self.selected_rates[i] = !self.selected_rates[i];
+                    for family in CodeFamily::ALL {
+                        let selected =
+                            !self.config.use_alist && self.config.code_families.contains(&family);
+                        let label = self.localization.get(family_key(family));
+                        if ui.selectable_label(selected, label).clicked() {
+                            self.toggle_family(family);
                         }
                     }
-
-                    self.config.ldpc_rates = rates
-                        .iter()
-                        .enumerate()
-                        .filter_map(|(i, (_, rate))| {
-                            if self.selected_rates[i] {
-                                Some(*rate)
-                            } else {
-                                None
-                            }
-                        })
-                        .collect();
-
-                    if self.config.ldpc_rates.is_empty() {
-                        self.config.ldpc_rates = vec![AR4JARate::R4_5];
+                    let selected = !self.config.use_alist && self.config.reed_solomon.is_some();
+                    let label = self.localization.get("code_source_rs");
+                    if ui.selectable_label(selected, label).clicked() {
+                        self.toggle_reed_solomon();
+                    }
+                    let alist = self.localization.get("code_source_alist");
+                    if ui.selectable_label(self.config.use_alist, alist).clicked() {
+                        self.config.use_alist = true;
                     }
                 });
 
-                ui.label(RichText::new(self.localization.get("info_block_size")).strong());
-                ui.horizontal(|ui| {
-                    let sizes = [
-                        ("K1024", AR4JAInfoSize::K1024),
-                        ("K4096", AR4JAInfoSize::K4096),
-                        ("K16384", AR4JAInfoSize::K16384),
-                    ];
-
-                    for (i, (name, size)) in sizes.iter().enumerate() {
-                        if ui
-                            .selectable_label(self.selected_size == i, *name)
-                            .clicked()
-                        {
-                            self.selected_size = i;
-                            self.config.ldpc_info_sizes = vec![*size];
+                if self.config.use_alist {
+                    ui.horizontal(|ui| {
+                        if ui.button(self.localization.get("browse")).clicked() {
+                            if let Some(path) = rfd::FileDialog::new()
+                                .add_filter("alist", &["alist", "txt"])
+                                .pick_file()
+                            {
+                                self.config.alist_path = path.display().to_string();
+                            }
+                        }
+                        ui.add(
+                            egui::TextEdit::singleline(&mut self.config.alist_path)
+                                .desired_width(ui.available_width() - 10.0)
+                                .hint_text(self.localization.get("alist_path_hint")),
+                        );
+                    });
+                } else {
+                    let show_headings = self.config.code_families.len()
+                        + usize::from(self.config.reed_solomon.is_some())
+                        > 1;
+                    for family in CodeFamily::ALL {
+                        if !self.config.code_families.contains(&family) {
+                            continue;
+                        }
+                        if show_headings {
+                            ui.add_space(4.0);
+                            ui.label(
+                                RichText::new(self.localization.get(family_key(family)))
+                                    .color(Color32::from_rgb(80, 150, 230)),
+                            );
+                        }
+                        match family {
+                            CodeFamily::Ar4ja => self.render_ar4ja_params(ui),
+                            _ => self.render_family_params(ui, family),
                         }
                     }
-                });
+                    if self.config.reed_solomon.is_some() {
+                        if show_headings {
+                            ui.add_space(4.0);
+                            ui.label(
+                                RichText::new(self.localization.get("code_source_rs"))
+                                    .color(Color32::from_rgb(80, 150, 230)),
+                            );
+                        }
+                        self.render_reed_solomon_params(ui);
+                    }
+                }
 
                 ui.collapsing(
                     RichText::new(self.localization.get("select_decoders")).strong(),
@@ -354,6 +553,156 @@ impl ConfigureTab {
             });
     }
 
+    fn render_ar4ja_params(&mut self, ui: &mut Ui) {
+        ui.label(RichText::new(self.localization.get("code_rate")).strong());
+        ui.horizontal_wrapped(|ui| {
+            let rates = [
+                ("R1_2", AR4JARate::R1_2),
+                ("R2_3", AR4JARate::R2_3),
+                ("R4_5", AR4JARate::R4_5),
+            ];
+
+            if self.selected_rates.len() < rates.len() {
+                self.selected_rates.resize(rates.len(), false);
+            }
+
+            for (i, (name, _)) in rates.iter().enumerate() {
+                if ui.selectable_label(self.selected_rates[i], *name).clicked() {
+                    self.selected_rates[i] = !self.selected_rates[i];
+                }
+            }
+
+            self.config.ldpc_rates = rates
+                .iter()
+                .enumerate()
+                .filter_map(|(i, (_, rate))| {
+                    if self.selected_rates[i] {
+                        Some(*rate)
+                    } else {
+                        None
+                    }
+                })
+                .collect();
+
+            if self.config.ldpc_rates.is_empty() {
+                self.config.ldpc_rates = vec![AR4JARate::R4_5];
+            }
+        });
+
+        ui.label(RichText::new(self.localization.get("info_block_size")).strong());
+        ui.horizontal(|ui| {
+            let sizes = [
+                ("K1024", AR4JAInfoSize::K1024),
+                ("K4096", AR4JAInfoSize::K4096),
+                ("K16384", AR4JAInfoSize::K16384),
+            ];
+
+            for (i, (name, size)) in sizes.iter().enumerate() {
+                if ui
+                    .selectable_label(self.selected_size == i, *name)
+                    .clicked()
+                {
+                    self.selected_size = i;
+                    self.config.ldpc_info_sizes = vec![*size];
+                }
+            }
+        });
+    }
+
+    /// Add or remove a family, leaving alist mode; at least one family or the
+    /// Reed–Solomon code stays selected.
+    fn toggle_family(&mut self, family: CodeFamily) {
+        let families = &mut self.config.code_families;
+        if self.config.use_alist {
+            self.config.use_alist = false;
+            if !families.contains(&family) {
+                families.push(family);
+            }
+        } else if let Some(pos) = families.iter().position(|&f| f == family) {
+            if families.len() > 1 || self.config.reed_solomon.is_some() {
+                families.remove(pos);
+            }
+        } else {
+            families.push(family);
+        }
+        families.sort_by_key(|f| CodeFamily::ALL.iter().position(|all| all == f));
+    }
+
+    /// Add or remove the Reed–Solomon code, leaving alist mode; it is only removed
+    /// while a family is selected.
+    fn toggle_reed_solomon(&mut self) {
+        if self.config.use_alist {
+            self.config.use_alist = false;
+            self.config.reed_solomon = Some(self.reed_solomon);
+        } else if self.config.reed_solomon.is_none() {
+            self.config.reed_solomon = Some(self.reed_solomon);
+        } else if !self.config.code_families.is_empty() {
+            self.config.reed_solomon = None;
+        }
+    }
+
+    fn render_reed_solomon_params(&mut self, ui: &mut Ui) {
+        let params = &mut self.reed_solomon;
+        ui.horizontal(|ui| {
+            ui.label(RichText::new(self.localization.get("symbol_bits")).strong());
+            ui.add(egui::DragValue::new(&mut params.symbol_bits).range(2..=16));
+            let max_n = (1 << params.symbol_bits) - 1;
+            ui.label(RichText::new(self.localization.get("rs_length")).strong());
+            ui.add(egui::DragValue::new(&mut params.n).range(2..=max_n));
+            params.n = params.n.min(max_n);
+            ui.label(RichText::new(self.localization.get("rs_message_symbols")).strong());
+            ui.add(egui::DragValue::new(&mut params.k).range(1..=params.n - 1));
+            params.k = params.k.clamp(1, params.n - 1);
+        });
+        self.config.reed_solomon = Some(*params);
+    }
+
+    fn render_family_params(&mut self, ui: &mut Ui, family: CodeFamily) {
+        let (rate_key, size_key) = match family {
+            CodeFamily::Nr5g => ("base_graph", "lifting_size"),
+            _ => ("code_rate", "frame_size"),
+        };
+        let rate_labels = family.rate_labels();
+        let (selected_rates, size) = self
+            .family_selection
+            .entry(family)
+            .or_insert_with(|| family_selection(&self.config, family));
+
+        ui.label(RichText::new(self.localization.get(rate_key)).strong());
+        ui.horizontal_wrapped(|ui| {
+            for (i, name) in rate_labels.iter().enumerate() {
+                if ui.selectable_label(selected_rates[i], *name).clicked() {
+                    selected_rates[i] = !selected_rates[i];
+                }
+            }
+        });
+        if !selected_rates.contains(&true) {
+            selected_rates[0] = true;
+        }
+
+        ui.horizontal(|ui| {
+            ui.label(RichText::new(self.localization.get(size_key)).strong());
+            egui::ComboBox::from_id_salt(("family_size", family.name()))
+                .selected_text(size.as_str())
+                .show_ui(ui, |ui| {
+                    for label in family.size_labels() {
+                        ui.selectable_value(size, label.clone(), label);
+                    }
+                });
+        });
+
+        self.config
+            .family_codes
+            .retain(|code| code.family() != family);
+        self.config.family_codes.extend(
+            rate_labels
+                .iter()
+                .zip(selected_rates.iter())
+                .filter(|(_, &selected)| selected)
+                .filter_map(|(rate, _)| family.code(rate, size).ok()),
+        );
+    }
+
     fn render_output_settings(&mut self, ui: &mut Ui) {
         egui::Frame::group(ui.style())
             .stroke(egui::Stroke::new(1.0, Color32::from_rgb(150, 150, 180)))
@@ -545,9 +894,72 @@ impl ConfigureTab {
             }
             self.config.shares_to_remove = vec![value];
         }
+        self.config.participants = self.participants_enabled.then_some(self.participants_count);
+        self.config.participants_to_remove = vec![self.participants_to_remove];
+        self.config.erasure_model = self.erasure_model;
+        self.config.channel = self.channel;
+        self.config.decoding = self.decoding;
+        self.config.sweep = self.sweep_enabled.then_some(self.sweep);
     }
 
     pub fn get_config(&self) -> BenchmarkConfig {
         self.config.clone()
     }
 }
+
+fn erasure_model_key(model: ErasureModel) -> &'static str {
+    match model {
+        ErasureModel::Uniform => "erasure_uniform",
+        ErasureModel::Burst => "erasure_burst",
+        ErasureModel::SystematicOnly => "erasure_systematic",
+        ErasureModel::ParityOnly => "erasure_parity",
+        ErasureModel::Groups(_) => "erasure_groups",
+        ErasureModel::Adversarial => "erasure_adversarial",
+    }
+}
+
+fn channel_key(channel: ChannelModel) -> &'static str {
+    match channel {
+        ChannelModel::Clean => "channel_clean",
+        ChannelModel::Bsc(_) => "channel_bsc",
+        ChannelModel::Awgn(_) => "channel_awgn",
+    }
+}
+
+fn decoding_key(decoding: Decoding) -> &'static str {
+    match decoding {
+        Decoding::BeliefPropagation => "decoding_bp",
+        Decoding::MaximumLikelihood => "decoding_ml",
+    }
+}
+
+fn family_key(family: CodeFamily) -> &'static str {
+    match family {
+        CodeFamily::Ar4ja => "code_source_ar4ja",
+        CodeFamily::DvbS2 => "code_source_dvbs2",
+        CodeFamily::Nr5g => "code_source_nr5g",
+    }
+}
+
+/// Rate toggles and size of `family` as stored in `config`, or its lowest rate at
+/// the default size when none of its codes is selected.
+fn family_selection(config: &BenchmarkConfig, family: CodeFamily) -> (Vec<bool>, String) {
+    let codes: Vec<_> = config
+        .family_codes
+        .iter()
+        .filter(|code| code.family() == family)
+        .collect();
+    let mut rates: Vec<bool> = family
+        .rate_labels()
+        .iter()
+        .map(|rate| codes.iter().any(|code| code.rate_label() == *rate))
+        .collect();
+    if !rates.contains(&true) {
+        rates[0] = true;
+    }
+    let size = codes.first().map_or_else(
+        || family.default_size().to_string(),
+        |code| code.size_label(),
+    );
+    (rates, size)
+}
@@ -1,5 +1,6 @@
 //! Shared utility functions.
 
+use crate::access::ParticipantBundle;
 use crate::types::Share;
 use rand::rngs::StdRng;
 use rand::seq::SliceRandom;
@@ -11,13 +12,7 @@ use rand::SeedableRng;
 /// * Negative `num_to_remove` — treat absolute value as percentage to remove
 /// * `seed` — when `Some(s)`, uses a deterministic RNG seeded with `s`
 pub fn remove_random_shares(shares: &mut Vec<Share>, num_to_remove: isize, seed: Option<u64>) {
-    if let Some(s) = seed {
-        let mut rng = StdRng::seed_from_u64(s);
-        shares.shuffle(&mut rng);
-    } else {
-        let mut rng = rand::rng();
-        shares.shuffle(&mut rng);
-    }
+    shuffle(shares, seed);
 
     let count = if num_to_remove < 0 {
         let pct = (-num_to_remove) as f64;
@@ -31,18 +26,38 @@ pub fn remove_random_shares(shares: &mut Vec<Share>, num_to_remove: isize, seed:
     }
 }
 
+/// Drop `count` random participants (all of them if there are fewer), with
+/// `seed` as in [`remove_random_shares`].
+pub fn remove_random_participants(
+    bundles: &mut Vec<ParticipantBundle>,
+    count: usize,
+    seed: Option<u64>,
+) {
+    shuffle(bundles, seed);
+    bundles.truncate(bundles.len().saturating_sub(count));
+}
+
+fn shuffle<T>(items: &mut [T], seed: Option<u64>) {
+    if let Some(s) = seed {
+        let mut rng = StdRng::seed_from_u64(s);
+        items.shuffle(&mut rng);
+    } else {
+        let mut rng = rand::rng();
+        items.shuffle(&mut rng);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use ldpc_toolbox::gf2::GF2;
-    use ndarray::Array1;
-    use num_traits::Zero;
+    use crate::types::F2PowElement;
 
     fn make_shares(n: usize) -> Vec<Share> {
         (0..n)
             .map(|i| Share {
-                y: Array1::from_elem(1, GF2::zero()),
+                y: F2PowElement::zero(1),
                 i: i as u32,
+                proof: None,
             })
             .collect()
     }
@@ -114,4 +129,18 @@ mod tests {
             "Different seeds should produce different removal patterns"
         );
     }
+
+    #[test]
+    fn test_remove_participants() {
+        let mut bundles: Vec<ParticipantBundle> = (0..5)
+            .map(|p| ParticipantBundle {
+                participant: format!("P{}", p),
+                shares: make_shares(2),
+            })
+            .collect();
+        remove_random_participants(&mut bundles, 2, Some(7));
+        assert_eq!(bundles.len(), 3);
+        remove_random_participants(&mut bundles, 10, Some(7));
+        assert!(bundles.is_empty());
+    }
 }
@@ -1,8 +1,11 @@
 //! Integration tests for the secret sharing scheme.
 
 use schema_code::aos;
+use schema_code::aos_bitsliced;
 use schema_code::aos_parallel;
-use schema_code::types::{CodeInitParams, F2PowElement};
+use schema_code::code::ldpc_impl::LdpcCode;
+use schema_code::error::SchemeError;
+use schema_code::types::{CodeInitParams, F2PowElement, SecretParams, Shares};
 use schema_code::utils::remove_random_shares;
 
 fn default_test_params() -> CodeInitParams {
@@ -10,6 +13,7 @@ fn default_test_params() -> CodeInitParams {
     use ldpc_toolbox::decoder::factory::DecoderImplementation;
 
     CodeInitParams {
+        code_source: None,
         decoder_type: Some(DecoderImplementation::Aminstarf32),
         ldpc_rate: Some(AR4JARate::R4_5),
         ldpc_info_size: Some(AR4JAInfoSize::K1024),
@@ -19,6 +23,13 @@ fn default_test_params() -> CodeInitParams {
     }
 }
 
+fn small_secret_params() -> CodeInitParams {
+    CodeInitParams {
+        secret_bits: Some(64),
+        ..default_test_params()
+    }
+}
+
 fn secret(hex: &str) -> F2PowElement {
     F2PowElement::from_hex(hex, 128).unwrap()
 }
@@ -29,10 +40,10 @@ mod sequential_tests {
     #[test]
     fn test_deal_reconstruct_no_erasures() {
         let params = default_test_params();
-        let pp = aos::setup(params);
+        let pp = aos::setup::<LdpcCode>(params).unwrap();
         let secret = super::secret("2a");
-        let shares = aos::deal(&pp, &secret);
-        let (reconstructed, _metrics) = aos::reconstruct(&pp, &shares);
+        let shares = aos::deal(&pp, &secret).unwrap();
+        let (reconstructed, _metrics) = aos::reconstruct(&pp, &shares).unwrap();
 
         assert_eq!(Some(secret), reconstructed);
     }
@@ -40,12 +51,12 @@ mod sequential_tests {
     #[test]
     fn test_deal_reconstruct_with_small_erasure() {
         let params = default_test_params();
-        let pp = aos::setup(params);
+        let pp = aos::setup::<LdpcCode>(params).unwrap();
         let secret = super::secret("3039");
-        let mut shares = aos::deal(&pp, &secret);
+        let mut shares = aos::deal(&pp, &secret).unwrap();
 
         remove_random_shares(&mut shares.shares, 50, None);
-        let (reconstructed, _metrics) = aos::reconstruct(&pp, &shares);
+        let (reconstructed, _metrics) = aos::reconstruct(&pp, &shares).unwrap();
 
         assert_eq!(Some(secret), reconstructed);
     }
@@ -53,7 +64,7 @@ mod sequential_tests {
     #[test]
     fn test_deal_reconstruct_different_secrets() {
         let params = default_test_params();
-        let pp = aos::setup(params);
+        let pp = aos::setup::<LdpcCode>(params).unwrap();
 
         let secrets = [
             super::secret("00"),
@@ -63,8 +74,8 @@ mod sequential_tests {
         ];
 
         for secret in secrets {
-            let shares = aos::deal(&pp, &secret);
-            let (reconstructed, _) = aos::reconstruct(&pp, &shares);
+            let shares = aos::deal(&pp, &secret).unwrap();
+            let (reconstructed, _) = aos::reconstruct(&pp, &shares).unwrap();
             assert_eq!(Some(secret), reconstructed);
         }
     }
@@ -76,10 +87,10 @@ mod parallel_tests {
     #[test]
     fn test_parallel_deal_reconstruct_no_erasures() {
         let params = default_test_params();
-        let pp = aos_parallel::setup(params);
+        let pp = aos_parallel::setup::<LdpcCode>(params).unwrap();
         let secret = super::secret("2a");
-        let shares = aos_parallel::deal(&pp, &secret);
-        let (reconstructed, _metrics) = aos_parallel::reconstruct(&pp, &shares);
+        let shares = aos_parallel::deal(&pp, &secret).unwrap();
+        let (reconstructed, _metrics) = aos_parallel::reconstruct(&pp, &shares).unwrap();
 
         assert_eq!(Some(secret), reconstructed);
     }
@@ -87,12 +98,12 @@ mod parallel_tests {
     #[test]
     fn test_parallel_deal_reconstruct_with_small_erasure() {
         let params = default_test_params();
-        let pp = aos_parallel::setup(params);
+        let pp = aos_parallel::setup::<LdpcCode>(params).unwrap();
         let secret = super::secret("3039");
-        let mut shares = aos_parallel::deal(&pp, &secret);
+        let mut shares = aos_parallel::deal(&pp, &secret).unwrap();
 
         remove_random_shares(&mut shares.shares, 50, None);
-        let (reconstructed, _metrics) = aos_parallel::reconstruct(&pp, &shares);
+        let (reconstructed, _metrics) = aos_parallel::reconstruct(&pp, &shares).unwrap();
 
         assert_eq!(Some(secret), reconstructed);
     }
@@ -104,10 +115,10 @@ mod consistency_tests {
     #[test]
     fn test_sequential_and_parallel_setup_produce_same_lengths() {
         let params = default_test_params();
-        let pp_seq = aos::setup(params);
+        let pp_seq = aos::setup::<LdpcCode>(params).unwrap();
 
         let params2 = default_test_params();
-        let pp_par = aos_parallel::setup(params2);
+        let pp_par = aos_parallel::setup::<LdpcCode>(params2).unwrap();
 
         assert_eq!(pp_seq.code.input_length, pp_par.code.input_length);
         assert_eq!(pp_seq.code.output_length, pp_par.code.output_length);
@@ -118,9 +129,9 @@ mod consistency_tests {
     #[test]
     fn test_share_count_matches_output_length() {
         let params = default_test_params();
-        let pp = aos::setup(params);
+        let pp = aos::setup::<LdpcCode>(params).unwrap();
         let secret = super::secret("03e7");
-        let shares = aos::deal(&pp, &secret);
+        let shares = aos::deal(&pp, &secret).unwrap();
 
         assert_eq!(shares.shares.len(), pp.code.output_length as usize);
     }
@@ -128,7 +139,7 @@ mod consistency_tests {
     #[test]
     fn test_setup_generates_binary_mask_of_length_k() {
         let params = default_test_params();
-        let pp = aos::setup(params);
+        let pp = aos::setup::<LdpcCode>(params).unwrap();
 
         assert_eq!(pp.a_bits.len(), pp.code.input_length as usize);
         assert!(pp.code.input_length as usize >= pp.ell);
@@ -137,12 +148,1245 @@ mod consistency_tests {
     #[test]
     fn test_reconstruct_returns_none_when_decoding_fails() {
         let params = default_test_params();
-        let pp = aos::setup(params);
+        let pp = aos::setup::<LdpcCode>(params).unwrap();
         let secret = super::secret("deadbeef");
-        let mut shares = aos::deal(&pp, &secret);
+        let mut shares = aos::deal(&pp, &secret).unwrap();
         shares.shares.clear();
 
-        let (reconstructed, _metrics) = aos::reconstruct(&pp, &shares);
+        let (reconstructed, _metrics) = aos::reconstruct(&pp, &shares).unwrap();
         assert!(reconstructed.is_none());
     }
 }
+
+mod error_tests {
+    use super::*;
+
+    #[test]
+    fn test_setup_rejects_secret_longer_than_k() {
+        let mut params = default_test_params();
+        params.secret_bits = Some(2048);
+
+        let err = aos::setup::<LdpcCode>(params).err().unwrap();
+        assert_eq!(
+            err,
+            SchemeError::SecretTooLong {
+                ell: 2048,
+                info_length: 1024
+            }
+        );
+    }
+
+    #[test]
+    fn test_deal_rejects_wrong_secret_length() {
+        let pp = aos::setup::<LdpcCode>(default_test_params()).unwrap();
+        let secret = F2PowElement::from_hex("2a", 64).unwrap();
+
+        let err = aos::deal(&pp, &secret).err().unwrap();
+        assert_eq!(
+            err,
+            SchemeError::ParameterMismatch {
+                parameter: "secret bit length",
+                expected: 128,
+                actual: 64
+            }
+        );
+    }
+
+    #[test]
+    fn test_reconstruct_rejects_malformed_shares() {
+        let pp = aos::setup::<LdpcCode>(default_test_params()).unwrap();
+        let shares = aos::deal(&pp, &super::secret("2a")).unwrap();
+
+        let mut out_of_range = shares.clone();
+        out_of_range.shares[0].i = pp.code.output_length;
+        assert_eq!(
+            aos::reconstruct(&pp, &out_of_range).err(),
+            Some(SchemeError::ShareIndexOutOfRange {
+                index: pp.code.output_length,
+                output_length: pp.code.output_length
+            })
+        );
+
+        let mut duplicated = shares.clone();
+        duplicated.shares[1].i = 0;
+        assert_eq!(
+            aos_parallel::reconstruct(&pp, &duplicated).err(),
+            Some(SchemeError::DuplicateShare { index: 0 })
+        );
+
+        let mut truncated = shares;
+        truncated.shares[2].y = F2PowElement::zero(64);
+        assert_eq!(
+            aos::reconstruct(&pp, &truncated).err(),
+            Some(SchemeError::ShareLengthMismatch {
+                index: 2,
+                expected: 128,
+                actual: 64
+            })
+        );
+    }
+}
+
+mod serialization_tests {
+    use super::*;
+
+    #[test]
+    fn test_reconstruct_from_serialized_params_and_shares() {
+        let pp = aos::setup(default_test_params()).unwrap();
+        let secret = super::secret("c0ffee");
+        let mut shares = aos::deal(&pp, &secret).unwrap();
+        remove_random_shares(&mut shares.shares, 50, Some(7));
+
+        let pp_bytes = pp.to_bytes();
+        let shares_json = shares.to_json().unwrap();
+
+        let remote_pp = SecretParams::from_bytes(&pp_bytes).unwrap();
+        let remote_shares = Shares::from_json(&shares_json).unwrap();
+        assert_eq!(remote_pp.a_bits, pp.a_bits);
+        assert_eq!(remote_pp.code.output_length, pp.code.output_length);
+
+        let (reconstructed, _) = aos_parallel::reconstruct(&remote_pp, &remote_shares).unwrap();
+        assert_eq!(Some(secret), reconstructed);
+
+        let from_json = SecretParams::from_json(&pp.to_json()).unwrap();
+        let from_bytes = Shares::from_bytes(&shares.to_bytes().unwrap()).unwrap();
+        let (reconstructed, _) = aos::reconstruct(&from_json, &from_bytes).unwrap();
+        assert_eq!(Some(super::secret("c0ffee")), reconstructed);
+    }
+
+    #[test]
+    fn test_alist_params_embed_their_matrix() {
+        use schema_code::types::CodeSource;
+
+        let builtin = aos::setup::<LdpcCode>(default_test_params()).unwrap();
+        let path = std::env::temp_dir().join(format!("aos_{}.alist", std::process::id()));
+        std::fs::write(&path, builtin.code.code_impl.parity_check().alist()).unwrap();
+
+        let params = CodeInitParams {
+            code_source: Some(CodeSource::Alist(path.clone())),
+            ..default_test_params()
+        };
+        let pp = aos::setup(params).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let secret = super::secret("a115");
+        let mut shares = aos::deal(&pp, &secret).unwrap();
+        remove_random_shares(&mut shares.shares, 50, Some(2));
+
+        for remote_pp in [
+            SecretParams::from_bytes(&pp.to_bytes()).unwrap(),
+            SecretParams::from_json(&pp.to_json()).unwrap(),
+        ] {
+            assert_eq!(
+                remote_pp.code.code_impl.init_params().code_source,
+                Some(CodeSource::Alist(path.clone()))
+            );
+            let (reconstructed, _) = aos::reconstruct(&remote_pp, &shares).unwrap();
+            assert_eq!(Some(secret.clone()), reconstructed);
+        }
+    }
+}
+
+mod code_family_tests {
+    use super::*;
+    use schema_code::types::{parse_code_spec, CodeSource};
+
+    fn family_params(spec: &str) -> CodeInitParams {
+        let mut codes = parse_code_spec(spec).unwrap();
+        assert_eq!(codes.len(), 1, "{} names one code", spec);
+        CodeInitParams {
+            code_source: codes.pop(),
+            ..default_test_params()
+        }
+    }
+
+    #[test]
+    fn test_dvbs2_deal_reconstruct_with_small_erasure() {
+        // 16 rows keep the 16200-bit frames affordable in debug builds.
+        let params = CodeInitParams {
+            secret_bits: Some(16),
+            ..family_params("DVBS2:R8_9:N16200")
+        };
+        let pp = aos_parallel::setup(params).unwrap();
+        assert_eq!(pp.code.output_length, 16200);
+
+        let secret = F2PowElement::from_hex("d7b5", 16).unwrap();
+        let mut shares = aos_bitsliced::deal(&pp, &secret).unwrap();
+        remove_random_shares(&mut shares.shares, 100, Some(3));
+        let (reconstructed, _) = aos_parallel::reconstruct(&pp, &shares).unwrap();
+        assert_eq!(Some(secret), reconstructed);
+    }
+
+    #[test]
+    fn test_nr5g_params_roundtrip() {
+        let pp = aos::setup(family_params("NR5G:BG2:Z16")).unwrap();
+        let secret = super::secret("5a");
+        let mut shares = aos::deal(&pp, &secret).unwrap();
+        remove_random_shares(&mut shares.shares, 20, Some(4));
+
+        for remote_pp in [
+            SecretParams::from_bytes(&pp.to_bytes()).unwrap(),
+            SecretParams::from_json(&pp.to_json()).unwrap(),
+        ] {
+            assert!(matches!(
+                remote_pp.code.code_impl.init_params().code_source,
+                Some(CodeSource::Builtin(_))
+            ));
+            let (reconstructed, _) = aos::reconstruct(&remote_pp, &shares).unwrap();
+            assert_eq!(Some(secret.clone()), reconstructed);
+        }
+    }
+
+    #[test]
+    fn test_code_spec_expands_family() {
+        let codes = parse_code_spec("DVBS2").unwrap();
+        assert_eq!(codes.len(), 10, "every rate but 9/10 has a short frame");
+        assert!(parse_code_spec("DVBS2:R9_10:N16200").is_err());
+        assert!(parse_code_spec("LTE").is_err());
+    }
+}
+
+mod reed_solomon_tests {
+    use super::*;
+    use schema_code::code::reed_solomon::ReedSolomonCode;
+    use schema_code::types::parse_code_spec;
+
+    /// RS(63, 32) over GF(2^6): 378 share columns, 31 parity symbols.
+    fn rs_params() -> CodeInitParams {
+        CodeInitParams {
+            code_source: parse_code_spec("RS:N63_K32:M6").unwrap().pop(),
+            ..default_test_params()
+        }
+    }
+
+    #[test]
+    fn test_recovers_from_up_to_n_minus_k_erased_symbols() {
+        let pp = aos::setup::<ReedSolomonCode>(rs_params()).unwrap();
+        assert_eq!(pp.code.output_length, 378);
+        let secret = super::secret("c0ffee");
+
+        let mut shares = aos::deal(&pp, &secret).unwrap();
+        // Each removed column erases at most one symbol.
+        remove_random_shares(&mut shares.shares, 31, Some(8));
+        let (reconstructed, metrics) = aos_parallel::reconstruct(&pp, &shares).unwrap();
+        assert_eq!(Some(secret.clone()), reconstructed);
+        let stats = metrics.and_then(|metrics| metrics.decoding_stats).unwrap();
+        assert_eq!(stats.max_iterations_hit, 0);
+
+        let mut shares = aos_parallel::deal(&pp, &secret).unwrap();
+        shares.shares.retain(|share| share.i / 6 >= 32);
+        let (reconstructed, _) = aos::reconstruct(&pp, &shares).unwrap();
+        assert_eq!(None, reconstructed, "32 erased symbols exceed n - k");
+    }
+
+    #[test]
+    fn test_ldpc_setup_rejects_reed_solomon_source() {
+        assert!(matches!(
+            aos::setup::<LdpcCode>(rs_params()),
+            Err(SchemeError::InvalidCodeParameters(_))
+        ));
+        assert!(parse_code_spec("RS:N64_K32:M6").is_err());
+    }
+
+    #[test]
+    fn test_parity_repair_needs_a_sparse_parity_check() {
+        let pp = aos::setup::<ReedSolomonCode>(rs_params()).unwrap();
+        let shares = aos::deal(&pp, &super::secret("01")).unwrap();
+        assert!(matches!(
+            aos::repair_share_parity(&pp, &shares, 0),
+            Err(SchemeError::InvalidCodeParameters(_))
+        ));
+    }
+
+    #[test]
+    fn test_subset_analysis_needs_a_sparse_parity_check() {
+        let pp = aos::setup::<ReedSolomonCode>(rs_params()).unwrap();
+        assert!(matches!(
+            schema_code::analysis::analyze_subset(&pp, &[0, 1, 2]),
+            Err(SchemeError::InvalidCodeParameters(_))
+        ));
+    }
+}
+
+mod integrity_tests {
+    use super::*;
+    use schema_code::integrity::ShareCommitment;
+
+    #[test]
+    fn test_tampered_shares_become_erasures() {
+        let pp = aos::setup::<LdpcCode>(default_test_params()).unwrap();
+        let secret = super::secret("7a3f");
+        let mut shares = aos_parallel::deal_committed(&pp, &secret).unwrap();
+        let commitment = shares.commitment.clone().unwrap();
+
+        remove_random_shares(&mut shares.shares, 20, Some(6));
+        let tampered: Vec<u32> = shares.shares[..3].iter().map(|share| share.i).collect();
+        for share in &mut shares.shares[..3] {
+            share.y.set_bit(0, !share.y.bit(0));
+        }
+        shares.shares[3].proof = None;
+
+        let (reconstructed, metrics) =
+            aos::reconstruct_verified(&pp, &shares, &commitment).unwrap();
+        assert_eq!(Some(secret), reconstructed);
+        let mut rejected = metrics.unwrap().rejected_shares;
+        rejected.sort_unstable();
+        let mut expected = tampered;
+        expected.push(shares.shares[3].i);
+        expected.sort_unstable();
+        assert_eq!(expected, rejected);
+    }
+
+    #[test]
+    fn test_commitment_survives_serialization() {
+        let pp = aos::setup::<LdpcCode>(default_test_params()).unwrap();
+        let secret = super::secret("c4");
+        let shares = aos::deal_committed(&pp, &secret).unwrap();
+        let commitment =
+            ShareCommitment::from_bytes(&shares.commitment.as_ref().unwrap().to_bytes()).unwrap();
+        let remote = Shares::from_json(&shares.to_json().unwrap()).unwrap();
+
+        let (reconstructed, metrics) =
+            aos_parallel::reconstruct_verified(&pp, &remote, &commitment).unwrap();
+        assert_eq!(Some(secret), reconstructed);
+        assert!(metrics.unwrap().rejected_shares.is_empty());
+
+        let mut forged = remote;
+        forged.z0.set_bit(3, !forged.z0.bit(3));
+        assert!(matches!(
+            aos::reconstruct_verified(&pp, &forged, &commitment),
+            Err(SchemeError::CommitmentMismatch(_))
+        ));
+    }
+
+    #[test]
+    fn test_commitment_is_opt_in() {
+        let pp = aos::setup::<LdpcCode>(default_test_params()).unwrap();
+        let mut shares = aos::deal_with_seed(&pp, &super::secret("c4"), 8).unwrap();
+        assert!(shares.commitment.is_none());
+        assert!(shares.shares.iter().all(|share| share.proof.is_none()));
+
+        let mut partial = shares.clone();
+        partial.shares.remove(5);
+        assert_eq!(
+            partial.commit().err(),
+            Some(SchemeError::ShareIndexMismatch {
+                expected: 5,
+                actual: 6
+            })
+        );
+
+        let commitment = shares.commit().unwrap().clone();
+        assert!(commitment.verify_z0(&shares.z0));
+        assert!(shares
+            .shares
+            .iter()
+            .all(|share| commitment.verify_share(share)));
+    }
+}
+
+mod error_tolerant_tests {
+    use super::*;
+
+    /// Invert every bit of every `step`-th share, `count` shares in all, and return
+    /// their indices.
+    fn corrupt(shares: &mut Shares, count: usize, step: usize) -> Vec<u32> {
+        let mut corrupted = Vec::new();
+        for share in shares.shares.iter_mut().step_by(step).take(count) {
+            for bit in 0..share.y.bit_len {
+                share.y.set_bit(bit, !share.y.bit(bit));
+            }
+            corrupted.push(share.i);
+        }
+        corrupted.sort_unstable();
+        corrupted
+    }
+
+    #[test]
+    fn test_low_weights_let_corrupted_shares_be_corrected() {
+        let pp = aos::setup::<LdpcCode>(default_test_params()).unwrap();
+        let secret = super::secret("b17b07");
+        let mut shares = aos::deal(&pp, &secret).unwrap();
+        remove_random_shares(&mut shares.shares, 50, Some(2));
+        let corrupted = corrupt(&mut shares, 40, 1);
+
+        let weights: Vec<f64> = (0..shares.shares.len())
+            .map(|s| if s < 40 { 0.05 } else { 4.0 })
+            .collect();
+        let (reconstructed, metrics) =
+            aos_parallel::reconstruct_with_errors(&pp, &shares, &weights).unwrap();
+        assert_eq!(Some(secret), reconstructed);
+        assert_eq!(corrupted, metrics.unwrap().corrected_shares);
+    }
+
+    #[test]
+    fn test_uniform_weights_correct_a_few_errors() {
+        let pp = aos::setup::<LdpcCode>(default_test_params()).unwrap();
+        let secret = super::secret("0ddba11");
+        let mut shares = aos::deal(&pp, &secret).unwrap();
+        let corrupted = corrupt(&mut shares, 3, 397);
+
+        let weights = vec![4.0; shares.shares.len()];
+        let (reconstructed, metrics) =
+            aos::reconstruct_with_errors(&pp, &shares, &weights).unwrap();
+        assert_eq!(Some(secret), reconstructed);
+        assert_eq!(corrupted, metrics.unwrap().corrected_shares);
+
+        assert!(matches!(
+            aos::reconstruct_with_errors(&pp, &shares, &weights[1..]),
+            Err(SchemeError::ParameterMismatch { .. })
+        ));
+    }
+}
+
+mod outcome_tests {
+    use super::*;
+
+    #[test]
+    fn test_outcome_of_a_successful_reconstruct() {
+        let pp = aos::setup::<LdpcCode>(default_test_params()).unwrap();
+        let secret = super::secret("0c0de");
+        let mut shares = aos::deal(&pp, &secret).unwrap();
+        remove_random_shares(&mut shares.shares, 50, Some(4));
+
+        let outcome = aos_parallel::reconstruct_outcome(&pp, &shares).unwrap();
+        assert_eq!(Some(secret), outcome.secret);
+        assert_eq!(outcome.erasures, 50);
+        assert_eq!(outcome.rows.len(), pp.ell);
+        assert!(outcome.failed_rows().is_empty());
+        assert_eq!(outcome.recommended_extra_shares, 0);
+    }
+
+    #[test]
+    fn test_outcome_explains_failed_rows() {
+        let params = CodeInitParams {
+            max_iterations: Some(20),
+            secret_bits: Some(64),
+            ..default_test_params()
+        };
+        let pp = aos::setup::<LdpcCode>(params).unwrap();
+        let mut shares = aos::deal(&pp, &F2PowElement::from_hex("0c0de", 64).unwrap()).unwrap();
+        remove_random_shares(&mut shares.shares, 400, Some(4));
+
+        let outcome = aos_parallel::reconstruct_outcome(&pp, &shares).unwrap();
+        assert_eq!(outcome.secret, None);
+        assert_eq!(outcome.erasures, 400);
+        assert!(!outcome.failed_rows().is_empty());
+        for row in outcome.failed_rows() {
+            assert!(outcome.rows[row].residual_syndrome_weight > 0);
+            assert_eq!(outcome.rows[row].iterations, 20);
+        }
+        assert!((1..=400).contains(&outcome.recommended_extra_shares));
+
+        let stats = outcome.metrics.decoding_stats.clone().unwrap();
+        assert_eq!(stats.failed_rows, outcome.failed_rows().len());
+
+        let sequential = aos::reconstruct_outcome(&pp, &shares).unwrap();
+        assert_eq!(sequential.rows, outcome.rows);
+    }
+}
+
+mod block_tests {
+    use super::*;
+    use schema_code::types::{BlockShares, BlockZ0};
+
+    #[test]
+    fn test_long_secret_roundtrip_with_per_block_z0() {
+        let pp = aos::setup::<LdpcCode>(small_secret_params()).unwrap();
+        let secret: Vec<u8> = (0..40u8).map(|b| b.wrapping_mul(37) ^ 0x5a).collect();
+        let mut shares = aos_parallel::deal_bytes(&pp, &secret, BlockZ0::PerBlock).unwrap();
+        assert_eq!(shares.z0.len(), 5);
+        assert!(shares.shares.iter().all(|share| share.y.bit_len == 5 * 64));
+        remove_random_shares(&mut shares.shares, 50, Some(8));
+
+        let (reconstructed, metrics) = aos_parallel::reconstruct_bytes(&pp, &shares).unwrap();
+        assert_eq!(Some(secret), reconstructed);
+        assert_eq!(metrics.unwrap().decoding_stats.unwrap().total_rows, 5 * 64);
+    }
+
+    #[test]
+    fn test_long_secret_roundtrip_with_shared_z0() {
+        let pp = aos::setup::<LdpcCode>(small_secret_params()).unwrap();
+        let secret = b"not a multiple of eight".to_vec();
+        assert_eq!(BlockShares::block_count(secret.len(), pp.ell), 3);
+
+        let shares = aos::deal_bytes_with_seed(&pp, &secret, BlockZ0::Shared, 9).unwrap();
+        assert_eq!(shares.z0.len(), 1);
+        let (reconstructed, _) = aos_parallel::reconstruct_bytes(&pp, &shares).unwrap();
+        assert_eq!(Some(secret), reconstructed);
+    }
+
+    #[test]
+    fn test_block_shares_are_validated() {
+        let pp = aos::setup::<LdpcCode>(small_secret_params()).unwrap();
+        let mut shares = aos::deal_bytes(&pp, &[0xab; 20], BlockZ0::PerBlock).unwrap();
+        shares.z0.pop();
+        assert!(matches!(
+            aos::reconstruct_bytes(&pp, &shares),
+            Err(SchemeError::ParameterMismatch {
+                parameter: "z0 count",
+                ..
+            })
+        ));
+
+        shares.z0.truncate(1);
+        shares.secret_len = 30;
+        assert!(matches!(
+            aos::reconstruct_bytes(&pp, &shares),
+            Err(SchemeError::ShareLengthMismatch { .. })
+        ));
+    }
+}
+
+mod combine_tests {
+    use super::*;
+
+    #[test]
+    fn test_combined_shares_reconstruct_xor_of_secrets() {
+        let pp = aos::setup::<LdpcCode>(default_test_params()).unwrap();
+        let s1 = secret("0123456789abcdef0011223344556677");
+        let s2 = secret("deadbeef");
+        let mut shares1 = aos::deal(&pp, &s1).unwrap();
+        let mut shares2 = aos_parallel::deal(&pp, &s2).unwrap();
+        let removed: Vec<u32> = (0..60).map(|i| i * 7).collect();
+        shares1.shares.retain(|share| !removed.contains(&share.i));
+        shares2.shares.retain(|share| !removed.contains(&share.i));
+        shares2.shares.reverse();
+
+        let combined = shares1.combine(&pp, &shares2).unwrap();
+        let mut expected = s1.clone();
+        expected.xor_assign(&s2);
+        let (reconstructed, _) = aos::reconstruct(&pp, &combined).unwrap();
+        assert_eq!(Some(expected), reconstructed);
+    }
+
+    #[test]
+    fn test_combine_requires_the_same_columns() {
+        let pp = aos::setup::<LdpcCode>(default_test_params()).unwrap();
+        let mut shares1 = aos::deal(&pp, &secret("01")).unwrap();
+        let mut shares2 = aos::deal(&pp, &secret("02")).unwrap();
+        shares2.shares.remove(5);
+        assert!(matches!(
+            shares1.combine(&pp, &shares2),
+            Err(SchemeError::ParameterMismatch {
+                parameter: "share count",
+                ..
+            })
+        ));
+
+        shares1.shares.remove(6);
+        assert_eq!(
+            shares1.combine(&pp, &shares2).err(),
+            Some(SchemeError::ShareIndexMismatch {
+                expected: 5,
+                actual: 6
+            })
+        );
+    }
+
+    #[test]
+    fn test_combine_checks_both_sides_against_params() {
+        let pp = aos::setup::<LdpcCode>(default_test_params()).unwrap();
+        let other_pp = aos::setup::<LdpcCode>(small_secret_params()).unwrap();
+        let shares = aos::deal(&pp, &secret("01")).unwrap();
+        let other = aos::deal(&other_pp, &F2PowElement::from_hex("02", 64).unwrap()).unwrap();
+
+        let expected = Some(SchemeError::ParameterMismatch {
+            parameter: "z0 bit length",
+            expected: 128,
+            actual: 64,
+        });
+        assert_eq!(shares.combine(&pp, &other).err(), expected);
+        assert_eq!(other.combine(&pp, &shares).err(), expected);
+    }
+}
+
+mod refresh_tests {
+    use super::*;
+    use schema_code::access::AccessLayout;
+
+    #[test]
+    fn test_refresh_keeps_secret_and_z0() {
+        let pp = aos::setup::<LdpcCode>(CodeInitParams {
+            max_iterations: Some(30),
+            ..small_secret_params()
+        })
+        .unwrap();
+        let secret = F2PowElement::from_hex("0123456789abcdef", 64).unwrap();
+        let shares = aos::deal(&pp, &secret).unwrap();
+        let mut refreshed = aos_parallel::refresh_with_seed(&pp, &shares, 3).unwrap();
+        assert_eq!(refreshed.z0, shares.z0);
+        assert!(refreshed
+            .shares
+            .iter()
+            .zip(&shares.shares)
+            .any(|(new, old)| new.y != old.y));
+
+        remove_random_shares(&mut refreshed.shares, 50, Some(5));
+        let (reconstructed, _) = aos::reconstruct(&pp, &refreshed).unwrap();
+        assert_eq!(Some(secret.clone()), reconstructed);
+
+        let mut mixed = aos::refresh_with_seed(&pp, &shares, 4).unwrap();
+        for share in mixed.shares.iter_mut().step_by(2) {
+            share.y = shares.shares[share.i as usize].y.clone();
+        }
+        let (reconstructed, _) = aos::reconstruct(&pp, &mixed).unwrap();
+        assert_ne!(Some(secret), reconstructed);
+    }
+
+    #[test]
+    fn test_participants_apply_their_own_update() {
+        let pp = aos::setup::<LdpcCode>(CodeInitParams {
+            max_iterations: Some(30),
+            ..small_secret_params()
+        })
+        .unwrap();
+        let secret = F2PowElement::from_hex("fedcba9876543210", 64).unwrap();
+        let layout = AccessLayout::numbered(20, pp.code.output_length);
+        let mut bundled = aos::deal_bundles(&pp, &secret, &layout).unwrap();
+        let update = aos_parallel::refresh_bundles(&pp, &layout).unwrap();
+        let commitment = update.commitment.clone().unwrap();
+        assert_eq!(update.z0, F2PowElement::zero(64));
+
+        for (bundle, own_update) in bundled.bundles.iter_mut().zip(&update.bundles) {
+            assert!(own_update
+                .shares
+                .iter()
+                .all(|share| commitment.verify_share(share)));
+            bundle.apply_update(own_update).unwrap();
+        }
+        bundled.bundles.remove(7);
+        let (reconstructed, _) = aos::reconstruct_bundles(&pp, &bundled).unwrap();
+        assert_eq!(Some(secret), reconstructed);
+    }
+}
+
+mod repair_tests {
+    use super::*;
+
+    #[test]
+    fn test_repair_share_by_decoding() {
+        let pp = aos::setup::<LdpcCode>(small_secret_params()).unwrap();
+        let shares = aos::deal(&pp, &F2PowElement::from_hex("5eed", 64).unwrap()).unwrap();
+        let mut available = shares.clone();
+        remove_random_shares(&mut available.shares, 60, Some(2));
+
+        for index in [3, pp.code.input_length + 17] {
+            let repaired = aos_parallel::repair_share(&pp, &available, index)
+                .unwrap()
+                .unwrap();
+            assert_eq!(repaired.i, index);
+            assert_eq!(repaired.y, shares.shares[index as usize].y);
+        }
+        assert!(matches!(
+            aos::repair_share(&pp, &available, pp.code.output_length),
+            Err(SchemeError::ShareIndexOutOfRange { .. })
+        ));
+    }
+
+    #[test]
+    fn test_repair_share_from_parity_checks() {
+        let pp = aos::setup::<LdpcCode>(small_secret_params()).unwrap();
+        let shares = aos::deal(&pp, &F2PowElement::from_hex("c0ffee", 64).unwrap()).unwrap();
+
+        // Only the lost share is missing: one parity check through it suffices.
+        let repaired = aos::repair_share_parity(&pp, &shares, 42).unwrap().unwrap();
+        assert_eq!(repaired.y, shares.shares[42].y);
+
+        // With many erasures the whole erasure system is solved.
+        let mut available = shares.clone();
+        remove_random_shares(&mut available.shares, 200, Some(6));
+        let lost = (0..pp.code.output_length)
+            .find(|&i| available.shares.iter().all(|share| share.i != i))
+            .unwrap();
+        let repaired = aos::repair_share_parity(&pp, &available, lost)
+            .unwrap()
+            .unwrap();
+        assert_eq!(repaired.y, shares.shares[lost as usize].y);
+    }
+}
+
+mod joint_tests {
+    use super::*;
+    use schema_code::aos_core::joint::combine_dealings;
+
+    #[test]
+    fn test_simulated_joint_deal_shares_xor_of_contributions() {
+        let pp = aos::setup::<LdpcCode>(small_secret_params()).unwrap();
+        let mut simulation = aos_parallel::simulate_joint_deal_with_seed(&pp, 4, 11).unwrap();
+        assert_eq!(simulation.contributions.len(), 4);
+        remove_random_shares(&mut simulation.shares.shares, 50, Some(12));
+
+        let (reconstructed, _) = aos::reconstruct(&pp, &simulation.shares).unwrap();
+        assert_eq!(simulation.secret(), reconstructed);
+    }
+
+    #[test]
+    fn test_combined_dealings_match_their_commitments() {
+        let pp = aos::setup::<LdpcCode>(small_secret_params()).unwrap();
+        let mut dealings = vec![
+            aos::contribute(&pp).unwrap(),
+            aos_parallel::contribute(&pp).unwrap(),
+            aos::contribute(&pp).unwrap(),
+        ];
+        let combined = combine_dealings(&pp, &dealings).unwrap();
+        let expected = dealings
+            .iter()
+            .map(|dealing| aos::reconstruct(&pp, dealing).unwrap().0.unwrap())
+            .reduce(|mut acc, secret| {
+                acc.xor_assign(&secret);
+                acc
+            });
+        assert_eq!(aos::reconstruct(&pp, &combined).unwrap().0, expected);
+
+        let y = &mut dealings[1].shares[9].y;
+        y.set_bit(0, !y.bit(0));
+        assert_eq!(
+            combine_dealings(&pp, &dealings).err(),
+            Some(SchemeError::CommitmentMismatch(
+                "share 9 of dealing 1".to_string()
+            ))
+        );
+        assert_eq!(
+            combine_dealings(&pp, &[]).err(),
+            Some(SchemeError::NoDealings)
+        );
+    }
+}
+
+mod subset_analysis_tests {
+    use super::*;
+    use schema_code::analysis::{analyze_subset, Decodability};
+
+    fn kept_after_removing(shares: &Shares, count: isize, seed: u64) -> Shares {
+        let mut kept = shares.clone();
+        remove_random_shares(&mut kept.shares, count, Some(seed));
+        kept
+    }
+
+    #[test]
+    fn test_prediction_matches_reconstruction() {
+        // Belief propagation on erasures only behaves like peeling when the known
+        // bits enter the decoder with a strong LLR.
+        let pp = aos::setup::<LdpcCode>(CodeInitParams {
+            max_iterations: Some(50),
+            llr_value: Some(10.0),
+            ..small_secret_params()
+        })
+        .unwrap();
+        let secret = F2PowElement::from_hex("a11ce", 64).unwrap();
+        let shares = aos::deal(&pp, &secret).unwrap();
+
+        for (removed, expected) in [
+            (120, Decodability::BeliefPropagation),
+            (256, Decodability::MaximumLikelihood),
+            (390, Decodability::Insufficient),
+        ] {
+            let kept = kept_after_removing(&shares, removed, 0);
+            let indices: Vec<u32> = kept.shares.iter().map(|share| share.i).collect();
+            let analysis = analyze_subset(&pp, &indices).unwrap();
+            assert_eq!(analysis.decodability, expected, "{} removed", removed);
+            assert_eq!(analysis.erased, removed as usize);
+
+            let bp = aos::reconstruct(&pp, &kept).unwrap().0;
+            let ml = aos::reconstruct_erasures(&pp, &kept).unwrap().0;
+            assert_eq!(
+                bp.as_ref() == Some(&secret),
+                expected == Decodability::BeliefPropagation
+            );
+            assert_eq!(
+                ml.as_ref() == Some(&secret),
+                expected != Decodability::Insufficient
+            );
+        }
+    }
+
+    #[test]
+    fn test_subset_indices_are_validated() {
+        let pp = aos::setup::<LdpcCode>(CodeInitParams {
+            max_iterations: Some(50),
+            llr_value: Some(10.0),
+            ..small_secret_params()
+        })
+        .unwrap();
+        assert_eq!(
+            analyze_subset(&pp, &[1, 2, 1]),
+            Err(SchemeError::DuplicateShare { index: 1 })
+        );
+        assert!(matches!(
+            analyze_subset(&pp, &[pp.code.output_length]),
+            Err(SchemeError::ShareIndexOutOfRange { .. })
+        ));
+    }
+}
+
+mod privacy_tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use schema_code::analysis::{analyze_privacy, estimate_privacy_threshold, sampled_leak_rate};
+
+    #[test]
+    fn test_systematic_columns_of_the_mask_leak() {
+        let pp = aos::setup_with_seed(small_secret_params(), 3).unwrap();
+        let masked: Vec<u32> = (0..pp.a_bits.len() as u32)
+            .filter(|&j| pp.a_bits[j as usize])
+            .collect();
+
+        let analysis = analyze_privacy(&pp, &masked).unwrap();
+        assert!(analysis.leaks);
+        assert_eq!(analysis.rank, masked.len());
+        assert!(!analyze_privacy(&pp, &masked[1..]).unwrap().leaks);
+    }
+
+    #[test]
+    fn test_all_shares_leak_and_none_do_not() {
+        let pp = aos::setup_with_seed(small_secret_params(), 3).unwrap();
+        let all: Vec<u32> = (0..pp.code.output_length).collect();
+
+        let analysis = analyze_privacy(&pp, &all).unwrap();
+        assert!(analysis.leaks);
+        assert_eq!(analysis.rank, pp.code.input_length as usize);
+        assert!(!analyze_privacy(&pp, &[]).unwrap().leaks);
+        assert_eq!(
+            analyze_privacy(&pp, &[4, 4]),
+            Err(SchemeError::DuplicateShare { index: 4 })
+        );
+    }
+
+    #[test]
+    fn test_threshold_separates_sampled_sizes() {
+        let pp = aos::setup_with_seed(small_secret_params(), 3).unwrap();
+        let mut rng = StdRng::seed_from_u64(5);
+        let threshold = estimate_privacy_threshold(&pp, 5, &mut rng);
+
+        assert_eq!(threshold.first_leak_size, threshold.leak_free_size + 1);
+        assert!(threshold.first_leak_size <= pp.code.output_length as usize);
+        assert_eq!(
+            sampled_leak_rate(&pp, threshold.leak_free_size / 2, 5, &mut rng),
+            0.0
+        );
+        assert_eq!(
+            sampled_leak_rate(&pp, pp.code.output_length as usize, 1, &mut rng),
+            1.0
+        );
+    }
+}
+
+mod erasure_model_tests {
+    use super::*;
+    use schema_code::access::AccessLayout;
+    use schema_code::analysis::{analyze_subset, Decodability};
+    use schema_code::erasure_model::ErasureModel;
+
+    fn kept_indices(
+        pp: &SecretParams<LdpcCode>,
+        shares: &Shares,
+        model: ErasureModel,
+        count: isize,
+    ) -> Vec<u32> {
+        let mut kept = shares.shares.clone();
+        model.erase(&pp.code.code_impl, &mut kept, count, Some(7));
+        kept.iter().map(|share| share.i).collect()
+    }
+
+    #[test]
+    fn test_models_remove_from_their_columns() {
+        let pp = aos::setup(CodeInitParams {
+            max_iterations: Some(50),
+            llr_value: Some(10.0),
+            ..small_secret_params()
+        })
+        .unwrap();
+        let shares = aos::deal(&pp, &F2PowElement::from_hex("e7a5", 64).unwrap()).unwrap();
+        let (k, n) = (pp.code.input_length, pp.code.output_length);
+        let erased = |kept: &[u32]| -> Vec<u32> { (0..n).filter(|i| !kept.contains(i)).collect() };
+
+        let systematic = erased(&kept_indices(
+            &pp,
+            &shares,
+            ErasureModel::SystematicOnly,
+            50,
+        ));
+        assert_eq!(systematic.len(), 50);
+        assert!(systematic.iter().all(|&i| i < k));
+
+        let parity = erased(&kept_indices(&pp, &shares, ErasureModel::ParityOnly, 1000));
+        assert_eq!(parity, (k..n).collect::<Vec<_>>());
+
+        let burst = erased(&kept_indices(&pp, &shares, ErasureModel::Burst, -5));
+        assert_eq!(burst.len(), 70);
+        let gaps = burst
+            .windows(2)
+            .filter(|pair| pair[1] != pair[0] + 1)
+            .count();
+        assert!(gaps <= 1, "a cyclic run wraps at most once");
+
+        let layout = AccessLayout::numbered(8, n);
+        let groups = erased(&kept_indices(
+            &pp,
+            &shares,
+            ErasureModel::Groups(8),
+            2 * n as isize / 8,
+        ));
+        let lost: Vec<_> = layout
+            .participants()
+            .iter()
+            .filter(|participant| participant.columns.iter().all(|i| groups.contains(i)))
+            .collect();
+        assert_eq!(lost.len(), 2);
+        assert_eq!(groups.len(), 2 * n as usize / 8);
+    }
+
+    #[test]
+    fn test_adversarial_erasures_stop_belief_propagation() {
+        let pp = aos::setup(CodeInitParams {
+            max_iterations: Some(50),
+            llr_value: Some(10.0),
+            ..small_secret_params()
+        })
+        .unwrap();
+        let secret = F2PowElement::from_hex("bad5e7", 64).unwrap();
+        let shares = aos::deal(&pp, &secret).unwrap();
+
+        let uniform = kept_indices(&pp, &shares, ErasureModel::Uniform, 60);
+        let adversarial = kept_indices(&pp, &shares, ErasureModel::Adversarial, 60);
+        assert_eq!(adversarial.len(), uniform.len());
+        assert_eq!(
+            analyze_subset(&pp, &uniform).unwrap().decodability,
+            Decodability::BeliefPropagation
+        );
+        let analysis = analyze_subset(&pp, &adversarial).unwrap();
+        assert!(analysis.stopping_set_size > 0);
+        assert_ne!(analysis.decodability, Decodability::BeliefPropagation);
+    }
+}
+
+mod channel_model_tests {
+    use super::*;
+    use schema_code::channel_model::{ChannelModel, Received};
+
+    #[test]
+    fn test_bsc_flips_are_corrected() {
+        let pp = aos::setup::<LdpcCode>(CodeInitParams {
+            max_iterations: Some(50),
+            llr_value: Some(4.0),
+            ..small_secret_params()
+        })
+        .unwrap();
+        let secret = F2PowElement::from_hex("b5c", 64).unwrap();
+        let mut shares = aos::deal(&pp, &secret).unwrap();
+        remove_random_shares(&mut shares.shares, 20, Some(3));
+
+        let output_length = pp.code.output_length as usize;
+        let Received::Hard(received) =
+            ChannelModel::Bsc(0.002).transmit(shares.clone(), output_length, Some(4))
+        else {
+            panic!("a binary symmetric channel delivers hard bits");
+        };
+        assert!(received
+            .shares
+            .iter()
+            .zip(&shares.shares)
+            .any(|(received, sent)| received.y != sent.y));
+        let (reconstructed, _) = aos::reconstruct(&pp, &received).unwrap();
+        assert_eq!(Some(secret), reconstructed);
+    }
+
+    #[test]
+    fn test_awgn_llrs_reconstruct_soft() {
+        let pp = aos::setup::<LdpcCode>(CodeInitParams {
+            max_iterations: Some(50),
+            llr_value: Some(4.0),
+            ..small_secret_params()
+        })
+        .unwrap();
+        let secret = F2PowElement::from_hex("a3c9", 64).unwrap();
+        let mut shares = aos::deal(&pp, &secret).unwrap();
+        remove_random_shares(&mut shares.shares, 20, Some(5));
+
+        let output_length = pp.code.output_length as usize;
+        let Received::Soft { z0, row_llrs } =
+            ChannelModel::Awgn(4.0).transmit(shares, output_length, Some(6))
+        else {
+            panic!("an AWGN channel delivers LLRs");
+        };
+        let (reconstructed, metrics) = aos::reconstruct_soft(&pp, &z0, &row_llrs).unwrap();
+        assert_eq!(Some(secret.clone()), reconstructed);
+        assert!(metrics.is_some());
+        let (reconstructed, _) = aos_parallel::reconstruct_soft(&pp, &z0, &row_llrs).unwrap();
+        assert_eq!(Some(secret), reconstructed);
+
+        assert!(matches!(
+            aos::reconstruct_soft(&pp, &z0, &row_llrs[1..]),
+            Err(SchemeError::ParameterMismatch {
+                parameter: "LLR row count",
+                ..
+            })
+        ));
+    }
+}
+
+mod participant_tests {
+    use super::*;
+    use schema_code::access::AccessLayout;
+    use schema_code::utils::remove_random_participants;
+
+    #[test]
+    fn test_reconstruct_without_one_participant() {
+        let pp = aos::setup::<LdpcCode>(small_secret_params()).unwrap();
+        let secret = F2PowElement::from_hex("0123456789abcdef", 64).unwrap();
+        let layout = AccessLayout::numbered(20, pp.code.output_length);
+        let mut bundled = aos_parallel::deal_bundles(&pp, &secret, &layout).unwrap();
+        assert_eq!(bundled.bundles.len(), 20);
+        assert_eq!(
+            bundled.to_shares().shares.len(),
+            pp.code.output_length as usize
+        );
+
+        remove_random_participants(&mut bundled.bundles, 1, Some(4));
+        let (reconstructed, metrics) = aos::reconstruct_bundles(&pp, &bundled).unwrap();
+        assert_eq!(Some(secret), reconstructed);
+        let participants = metrics.unwrap().participants;
+        assert_eq!(participants.len(), 19);
+        assert_eq!(participants, bundled.participant_names());
+    }
+
+    #[test]
+    fn test_layout_must_cover_the_code() {
+        let pp = aos::setup::<LdpcCode>(small_secret_params()).unwrap();
+        let layout = AccessLayout::numbered(4, pp.code.output_length - 1);
+        assert!(matches!(
+            aos::deal_bundles(&pp, &F2PowElement::from_hex("2a", 64).unwrap(), &layout),
+            Err(SchemeError::ParameterMismatch {
+                parameter: "layout column count",
+                ..
+            })
+        ));
+    }
+}
+
+mod determinism_tests {
+    use super::*;
+
+    #[test]
+    fn test_seeded_setup_is_reproducible() {
+        let pp1 = aos::setup_with_seed::<LdpcCode>(default_test_params(), 11).unwrap();
+        let pp2 = aos_parallel::setup_with_seed::<LdpcCode>(default_test_params(), 11).unwrap();
+        let pp3 = aos::setup_with_seed::<LdpcCode>(default_test_params(), 12).unwrap();
+
+        assert_eq!(pp1.a_bits, pp2.a_bits);
+        assert_ne!(pp1.a_bits, pp3.a_bits);
+    }
+
+    #[test]
+    fn test_seeded_deal_is_identical_across_strategies() {
+        let pp = aos::setup_with_seed::<LdpcCode>(default_test_params(), 5).unwrap();
+        let secret = super::secret("5eed");
+
+        let sequential = aos::deal_with_seed(&pp, &secret, 42).unwrap();
+        let parallel = aos_parallel::deal_with_seed(&pp, &secret, 42).unwrap();
+        let other = aos::deal_with_seed(&pp, &secret, 43).unwrap();
+
+        assert_eq!(sequential.z0, parallel.z0);
+        assert_eq!(sequential.to_bytes().unwrap(), parallel.to_bytes().unwrap());
+        assert_ne!(sequential.to_bytes().unwrap(), other.to_bytes().unwrap());
+
+        let (reconstructed, _) = aos_parallel::reconstruct(&pp, &sequential).unwrap();
+        assert_eq!(Some(secret), reconstructed);
+    }
+
+    #[test]
+    fn test_seeded_deal_matches_known_answer() {
+        let params = CodeInitParams {
+            secret_bits: Some(64),
+            max_iterations: Some(50),
+            ..default_test_params()
+        };
+        let pp = aos::setup_with_seed::<LdpcCode>(params, 1).unwrap();
+        let a_prefix: String = pp.a_bits[..16]
+            .iter()
+            .map(|&bit| if bit { '1' } else { '0' })
+            .collect();
+        assert_eq!(a_prefix, "0101100111101100");
+
+        let secret = F2PowElement::from_hex("5eed", 64).unwrap();
+        for shares in [
+            aos::deal_with_seed(&pp, &secret, 2).unwrap(),
+            aos_parallel::deal_with_seed(&pp, &secret, 2).unwrap(),
+        ] {
+            assert_eq!(shares.z0.to_hex(), "9010df2be3209320");
+            assert_eq!(shares.shares[0].y.to_hex(), "dcceeb3707a6bc36");
+            assert_eq!(shares.shares[1023].y.to_hex(), "403ef16a2fc09001");
+            assert_eq!(shares.shares[1407].y.to_hex(), "77a6264614c49829");
+        }
+    }
+}
+
+mod erasure_tests {
+    use super::*;
+
+    #[test]
+    fn test_erasure_reconstruct_matches_bp() {
+        let pp = aos::setup::<LdpcCode>(default_test_params()).unwrap();
+        let secret = super::secret("e7a5");
+        let mut shares = aos::deal(&pp, &secret).unwrap();
+        remove_random_shares(&mut shares.shares, 50, Some(3));
+
+        let (erasure, metrics) = aos::reconstruct_erasures(&pp, &shares).unwrap();
+        let (bp, _) = aos::reconstruct(&pp, &shares).unwrap();
+
+        assert_eq!(Some(secret), erasure);
+        assert_eq!(bp, erasure);
+        assert_eq!(metrics.unwrap().decoding_stats.unwrap().failed_rows, 0);
+    }
+
+    #[test]
+    fn test_erasure_reconstruct_survives_heavy_erasure() {
+        let pp = aos::setup::<LdpcCode>(default_test_params()).unwrap();
+        let secret = super::secret("e7a5");
+        let mut shares = aos::deal(&pp, &secret).unwrap();
+        remove_random_shares(&mut shares.shares, 200, Some(3));
+
+        let (reconstructed, _) = aos::reconstruct_erasures(&pp, &shares).unwrap();
+        assert_eq!(Some(secret), reconstructed);
+    }
+
+    #[test]
+    fn test_parallel_erasure_reconstruct_matches_sequential() {
+        let pp = aos::setup::<LdpcCode>(default_test_params()).unwrap();
+        let secret = super::secret("e7a5");
+        let mut shares = aos::deal(&pp, &secret).unwrap();
+        remove_random_shares(&mut shares.shares, 250, Some(5));
+
+        let (sequential, _) = aos::reconstruct_erasures(&pp, &shares).unwrap();
+        let (parallel, _) = aos_parallel::reconstruct_erasures(&pp, &shares).unwrap();
+        assert_eq!(sequential, parallel);
+        assert_eq!(Some(secret), parallel);
+    }
+
+    #[test]
+    fn test_erasure_reconstruct_fails_without_enough_shares() {
+        let pp = aos::setup::<LdpcCode>(default_test_params()).unwrap();
+        let mut shares = aos::deal(&pp, &super::secret("e7a5")).unwrap();
+        shares.shares.truncate(pp.code.input_length as usize - 1);
+
+        let (reconstructed, metrics) = aos::reconstruct_erasures(&pp, &shares).unwrap();
+        assert!(reconstructed.is_none());
+        assert_eq!(metrics.unwrap().decoding_stats.unwrap().failed_rows, 128);
+    }
+
+    #[test]
+    fn test_erasure_reconstruct_rejects_tampered_share() {
+        let pp = aos::setup::<LdpcCode>(default_test_params()).unwrap();
+        let mut shares = aos::deal(&pp, &super::secret("e7a5")).unwrap();
+        remove_random_shares(&mut shares.shares, 20, Some(9));
+        let flipped = !shares.shares[0].y.bit(5);
+        shares.shares[0].y.set_bit(5, flipped);
+
+        let (reconstructed, metrics) = aos::reconstruct_erasures(&pp, &shares).unwrap();
+        assert!(reconstructed.is_none());
+        assert_eq!(metrics.unwrap().decoding_stats.unwrap().failed_rows, 1);
+    }
+}
+
+mod bitsliced_tests {
+    use super::*;
+
+    #[test]
+    fn test_bitsliced_deal_matches_sequential() {
+        let pp = aos::setup_with_seed(default_test_params(), 21).unwrap();
+        let secret = super::secret("b175");
+
+        let bitsliced = aos_bitsliced::deal_with_seed(&pp, &secret, 8).unwrap();
+        let sequential = aos::deal_with_seed(&pp, &secret, 8).unwrap();
+        assert_eq!(
+            bitsliced.to_bytes().unwrap(),
+            sequential.to_bytes().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_bitsliced_deal_reconstruct_with_small_erasure() {
+        let pp = aos_bitsliced::setup(default_test_params()).unwrap();
+        let secret = super::secret("b175");
+        let mut shares = aos_bitsliced::deal(&pp, &secret).unwrap();
+
+        remove_random_shares(&mut shares.shares, 50, None);
+        let (reconstructed, _) = aos_bitsliced::reconstruct(&pp, &shares).unwrap();
+        assert_eq!(Some(secret), reconstructed);
+    }
+}
+
+mod generic_code_tests {
+    use super::*;
+    use ldpc_toolbox::gf2::GF2;
+    use ndarray::Array1;
+    use num_traits::{One, Zero};
+    use schema_code::code::{AdditiveCode, DecodeResult};
+
+    /// Single parity check code `[k + 1, k]`: recovers any one erased position.
+    struct ParityCode {
+        k: usize,
+    }
+
+    impl AdditiveCode for ParityCode {
+        fn setup(params: CodeInitParams) -> Result<Self, SchemeError> {
+            Ok(ParityCode {
+                k: params.secret_bits.unwrap_or(128),
+            })
+        }
+
+        fn encode(&self, input: &Array1<GF2>) -> Array1<GF2> {
+            let parity = input.iter().fold(GF2::zero(), |acc, &bit| acc + bit);
+            input.iter().copied().chain([parity]).collect()
+        }
+
+        fn decode(&self, input: &Array1<GF2>, present_positions: &[bool]) -> DecodeResult {
+            let erased: Vec<usize> = (0..input.len())
+                .filter(|&i| !present_positions[i])
+                .collect();
+            let mut codeword: Vec<u8> = input.iter().map(|bit| bit.is_one() as u8).collect();
+            if let [missing] = erased[..] {
+                codeword[missing] = 0;
+                codeword[missing] = codeword.iter().fold(0, |acc, bit| acc ^ bit);
+            }
+            DecodeResult {
+                codeword,
+                iterations: 0,
+                success: erased.len() <= 1,
+            }
+        }
+
+        fn input_length(&self) -> u32 {
+            self.k as u32
+        }
+
+        fn output_length(&self) -> u32 {
+            self.k as u32 + 1
+        }
+
+        fn max_iterations(&self) -> usize {
+            1
+        }
+    }
+
+    #[test]
+    fn test_custom_code_deal_reconstruct() {
+        let pp: SecretParams<ParityCode> = aos::setup(default_test_params()).unwrap();
+        assert_eq!(pp.code.output_length, 129);
+        let secret = super::secret("c0de");
+
+        let mut shares = aos::deal_with_seed(&pp, &secret, 3).unwrap();
+        let parallel = aos_parallel::deal_with_seed(&pp, &secret, 3).unwrap();
+        assert_eq!(shares.to_bytes().unwrap(), parallel.to_bytes().unwrap());
+
+        remove_random_shares(&mut shares.shares, 1, Some(1));
+        let (reconstructed, _) = aos_parallel::reconstruct(&pp, &shares).unwrap();
+        assert_eq!(Some(secret), reconstructed);
+
+        remove_random_shares(&mut shares.shares, 1, Some(1));
+        let (reconstructed, _) = aos::reconstruct(&pp, &shares).unwrap();
+        assert_eq!(None, reconstructed);
+    }
+}